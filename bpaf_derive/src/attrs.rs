@@ -41,6 +41,9 @@ pub enum Consumer {
     Switch {
         span: Span,
     },
+    Negatable {
+        span: Span,
+    },
     Flag {
         present: Expr,
         absent: Expr,
@@ -59,6 +62,8 @@ pub enum Consumer {
     Argument {
         metavar: Option<LitStr>,
         ty: Option<Type>,
+        /// Set with a separate `delimiter('c')` annotation
+        delimiter: Option<LitChar>,
         span: Span,
     },
     Positional {
@@ -84,6 +89,7 @@ impl Consumer {
     pub fn span(&self) -> Span {
         match self {
             Consumer::Switch { span }
+            | Consumer::Negatable { span }
             | Consumer::Flag { span, .. }
             | Consumer::ReqFlag { span, .. }
             | Consumer::Any { span, .. }
@@ -98,6 +104,7 @@ impl Consumer {
     pub(crate) fn help_placement(&self) -> HelpPlacement {
         match self {
             Consumer::Switch { .. }
+            | Consumer::Negatable { .. }
             | Consumer::Flag { .. }
             | Consumer::ReqFlag { .. }
             | Consumer::Argument { .. } => HelpPlacement::AtName,
@@ -119,6 +126,7 @@ impl Consumer {
     pub(crate) fn needs_name(&self) -> bool {
         match self {
             Consumer::Switch { .. }
+            | Consumer::Negatable { .. }
             | Consumer::Flag { .. }
             | Consumer::ReqFlag { .. }
             | Consumer::Argument { .. } => true,
@@ -238,6 +246,7 @@ impl ToTokens for PostDecor {
             PostDecor::Hide { .. } => quote!(hide()),
             PostDecor::CustomUsage { usage, .. } => quote!(custom_usage(#usage)),
             PostDecor::HideUsage { .. } => quote!(hide_usage()),
+            PostDecor::PossibleValues { .. } => quote!(possible_values()),
         }
         .to_tokens(tokens);
     }
@@ -323,6 +332,9 @@ pub(crate) enum PostDecor {
     HideUsage {
         span: Span,
     },
+    PossibleValues {
+        span: Span,
+    },
 }
 impl PostDecor {
     fn span(&self) -> Span {
@@ -339,7 +351,8 @@ impl PostDecor {
             | Self::Guard { span, .. }
             | Self::Hide { span }
             | Self::CustomUsage { span, .. }
-            | Self::HideUsage { span } => *span,
+            | Self::HideUsage { span }
+            | Self::PossibleValues { span } => *span,
         }
     }
 }
@@ -374,6 +387,9 @@ pub(crate) struct FieldAttrs {
     /// help specified by help(xxx)
     pub help: Vec<CustomHelp>,
 
+    /// delimiter specified by delimiter('c'), applies to the argument consumer
+    pub delimiter: Vec<LitChar>,
+
     pub(crate) ignore_rustdoc: bool,
 }
 
@@ -409,7 +425,12 @@ impl Consumer {
         Ok(Some(if kw == "argument" {
             let ty = type_fish(input)?;
             let metavar = parse_opt_metavar(input)?;
-            Consumer::Argument { metavar, ty, span }
+            Consumer::Argument {
+                metavar,
+                ty,
+                delimiter: None,
+                span,
+            }
         } else if kw == "positional" {
             let ty = type_fish(input)?;
             let metavar = parse_opt_metavar(input)?;
@@ -425,6 +446,8 @@ impl Consumer {
             }
         } else if kw == "switch" {
             Consumer::Switch { span }
+        } else if kw == "negatable" {
+            Consumer::Negatable { span }
         } else if kw == "flag" {
             let (present, absent) = parse_arg2(input)?;
             Consumer::Flag {
@@ -520,6 +543,8 @@ impl PostDecor {
             Self::Hide { span }
         } else if kw == "hide_usage" {
             Self::HideUsage { span }
+        } else if kw == "possible_values" {
+            Self::PossibleValues { span }
         } else if kw == "custom_usage" {
             let usage = parse_arg(input)?;
             Self::CustomUsage { usage, span }
@@ -578,6 +603,8 @@ impl Parse for FieldAttrs {
                 res.postpr.push(Post::Decor(pp));
             } else if let Some(help) = CustomHelp::parse(input, &kw)? {
                 res.help.push(help);
+            } else if kw == "delimiter" {
+                res.delimiter.push(parse_lit_char(input)?);
             } else {
                 return Err(fork.error("Unexpected attribute in field annotation"));
             }
@@ -611,6 +638,13 @@ impl FieldAttrs {
             ));
         }
 
+        if self.delimiter.len() > 1 {
+            return Err(Error::new(
+                self.delimiter[1].span(),
+                "Structure annotation can have only one delimiter attribute",
+            ));
+        }
+
         Ok(())
     }
 }
@@ -121,6 +121,35 @@ fn adjacent_argument() {
     assert_eq!(input.to_token_stream().to_string(), output.to_string());
 }
 
+#[test]
+fn delimited_argument() {
+    let input: NamedField = parse_quote! {
+        #[bpaf(argument("TAG"), delimiter(','))]
+        tag: Vec<u32>
+    };
+    let output = quote! {
+        ::bpaf::long("tag").argument::<u32>("TAG").delimiter(',').many()
+    };
+    assert_eq!(input.to_token_stream().to_string(), output.to_string());
+
+    let input: NamedField = parse_quote! {
+        #[bpaf(delimiter(':'))]
+        path: Vec<String>
+    };
+    let output = quote! {
+        ::bpaf::long("path").argument::<String>("ARG").delimiter(':').many()
+    };
+    assert_eq!(input.to_token_stream().to_string(), output.to_string());
+
+    field_trans_fail(
+        quote!(
+            #[bpaf(positional, delimiter(','))]
+            tag: Vec<u32>
+        ),
+        "delimiter can only be used with argument",
+    );
+}
+
 #[test]
 fn derive_fallback_with() {
     let input: NamedField = parse_quote! {
@@ -615,6 +644,19 @@ fn explicit_switch_argument() {
     assert_eq!(input.to_token_stream().to_string(), output.to_string());
 }
 
+#[test]
+fn negatable_switch() {
+    let input: NamedField = parse_quote! {
+        #[bpaf(negatable)]
+        /// Use colors
+        color: bool
+    };
+    let output = quote! {
+        ::bpaf::long("color").help("Use colors").negatable()
+    };
+    assert_eq!(input.to_token_stream().to_string(), output.to_string());
+}
+
 #[test]
 fn explicit_req_flag_argument() {
     let input: NamedField = parse_quote! {
@@ -866,6 +908,18 @@ fn hide_usage() {
     assert_eq!(input.to_token_stream().to_string(), output.to_string());
 }
 
+#[test]
+fn possible_values() {
+    let input: NamedField = parse_quote! {
+        #[bpaf(argument("MODE"), possible_values)]
+        mode: Mode
+    };
+    let output = quote! {
+        ::bpaf::long("mode").argument::<Mode>("MODE").possible_values()
+    };
+    assert_eq!(input.to_token_stream().to_string(), output.to_string());
+}
+
 #[test]
 fn custom_usage() {
     let input: NamedField = parse_quote! {
@@ -7,6 +7,7 @@ mod field;
 mod named_field;
 mod top;
 mod utils;
+mod value_enum;
 
 #[cfg(test)]
 mod field_tests;
@@ -51,7 +51,12 @@ fn derive_consumer(name_present: bool, ty: &Type) -> Result<Consumer> {
             let ty = Some(t);
             let metavar = None;
             if name_present {
-                Consumer::Argument { metavar, ty, span }
+                Consumer::Argument {
+                    metavar,
+                    ty,
+                    delimiter: None,
+                    span,
+                }
             } else {
                 Consumer::Positional { metavar, ty, span }
             }
@@ -73,6 +78,7 @@ impl ToTokens for Consumer {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         match self {
             Consumer::Switch { .. } => quote!(switch()),
+            Consumer::Negatable { .. } => quote!(negatable()),
             Consumer::Flag {
                 present, absent, ..
             } => quote!(flag(#present, #absent)),
@@ -83,10 +89,16 @@ impl ToTokens for Consumer {
                 Some(ty) => quote!(::bpaf::any::<#ty, _, _>(#metavar, #check)),
                 None => quote!(::bpaf::any(#metavar, #check)),
             },
-            Consumer::Argument { metavar, ty, .. } => {
+            Consumer::Argument {
+                metavar,
+                ty,
+                delimiter,
+                ..
+            } => {
                 let metavar = MMetavar(metavar.as_ref());
                 let tf = ty.as_ref().map(TurboFish);
-                quote!(argument #tf(#metavar))
+                let delimiter = delimiter.iter();
+                quote!(argument #tf(#metavar) #(.delimiter(#delimiter))*)
             }
             Consumer::Positional { metavar, ty, .. } => {
                 let metavar = MMetavar(metavar.as_ref());
@@ -202,6 +214,18 @@ impl StructField {
             }
         }
 
+        if let Some(d) = field_attrs.delimiter.pop() {
+            match &mut cons {
+                Consumer::Argument { delimiter, .. } => *delimiter = Some(d),
+                _ => {
+                    return Err(Error::new(
+                        d.span(),
+                        "delimiter can only be used with argument",
+                    ))
+                }
+            }
+        }
+
         let mut env = Vec::new();
         let mut naming = Vec::new();
         for attr in field_attrs.naming {
@@ -288,7 +312,7 @@ impl StructField {
                 Shape::Bool => {
                     if name.is_none()
                         && naming.is_empty()
-                        && matches!(cons, Consumer::Switch { .. })
+                        && matches!(cons, Consumer::Switch { .. } | Consumer::Negatable { .. })
                     {
                         let msg = "Can't derive consumer for unnamed boolean field, try adding one of #[bpaf(positional)], #[bpaf(long(\"name\")] or #[bpaf(short('n'))] annotations to it";
                         let err = Error::new_spanned(ty, msg);
@@ -5,6 +5,7 @@ use crate::{
 };
 use quote::{quote, ToTokens};
 use syn::{
+    parenthesized,
     parse::{Parse, ParseStream},
     parse_quote, token, Error, Expr, Ident, LitChar, LitStr, Result,
 };
@@ -48,6 +49,8 @@ pub(crate) enum Mode {
     Parser {
         parser: ParserCfg,
     },
+    /// `FromStr` and friends for an enum with unit variants instead of a parser
+    ValueEnum,
 }
 
 #[derive(Debug)]
@@ -77,6 +80,35 @@ impl ToTokens for HelpMsg {
     }
 }
 
+/// Constraint between struct fields: `requires(a, b)`, `conflicts(a, b, c)`, etc.
+#[derive(Debug)]
+pub(crate) struct FieldGroup {
+    /// Name of the constraint, same as the name of a function in `bpaf`
+    pub(crate) kind: Ident,
+    pub(crate) fields: Vec<Ident>,
+}
+
+impl FieldGroup {
+    fn parse(input: ParseStream, kind: Ident) -> Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        let fields = content
+            .parse_terminated(Ident::parse, token::Comma)?
+            .into_iter()
+            .collect::<Vec<_>>();
+        if kind == "requires" && fields.len() != 2 {
+            return Err(Error::new_spanned(
+                kind,
+                "`requires` takes exactly two fields: `requires(cert, key)`",
+            ));
+        }
+        if !(2..=8).contains(&fields.len()) {
+            return Err(Error::new_spanned(kind, "Group must contain 2 to 8 fields"));
+        }
+        Ok(FieldGroup { kind, fields })
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct TopInfo {
     /// Should visibility for generated function to be inherited?
@@ -91,6 +123,8 @@ pub(crate) struct TopInfo {
     pub(crate) adjacent: bool,
     pub(crate) mode: Mode,
     pub(crate) attrs: Vec<PostDecor>,
+    /// constraints between fields
+    pub(crate) groups: Vec<FieldGroup>,
 }
 
 impl Default for TopInfo {
@@ -105,6 +139,7 @@ impl Default for TopInfo {
             },
             attrs: Vec::new(),
             ignore_rustdoc: false,
+            groups: Vec::new(),
         }
     }
 }
@@ -170,11 +205,21 @@ impl Parse for TopInfo {
         let mut parser = Some(ParserCfg::default());
         let mut adjacent = false;
         let mut attrs = Vec::new();
+        let mut groups = Vec::new();
         let mut first = true;
+        let mut value_enum = false;
         loop {
             let kw = input.parse::<Ident>()?;
 
-            if first && kw == "options" {
+            if first && kw == "value_enum" {
+                value_enum = true;
+                parser = None;
+            } else if value_enum {
+                return Err(Error::new_spanned(
+                    kw,
+                    "value_enum can't be combined with other annotations",
+                ));
+            } else if first && kw == "options" {
                 let mut cfg = OptionsCfg::default();
                 if let Some(helper) = parse_opt_arg(input)? {
                     cfg.cargo_helper = Some(helper);
@@ -231,6 +276,12 @@ impl Parse for TopInfo {
                 with_parser(&kw, parser.as_mut(), |opt| {
                     opt.group_help = Some(group_help)
                 })?;
+            } else if kw == "requires"
+                || kw == "conflicts"
+                || kw == "exactly_one_of"
+                || kw == "at_least_one_of"
+            {
+                groups.push(FieldGroup::parse(input, kw)?);
             } else if kw == "ignore_rustdoc" {
                 ignore_rustdoc = true;
             } else if kw == "descr" {
@@ -259,6 +310,7 @@ impl Parse for TopInfo {
         }
 
         let mode = match (options, command) {
+            _ if value_enum => Mode::ValueEnum,
             (Some(options), Some(command)) => Mode::Command { command, options },
             (Some(options), None) => Mode::Options { options },
             _ => Mode::Parser {
@@ -274,6 +326,7 @@ impl Parse for TopInfo {
             adjacent,
             mode,
             attrs,
+            groups,
         })
     }
 }
@@ -12,8 +12,9 @@ use crate::{
     attrs::{parse_bpaf_doc_attrs, EnumPrefix, PostDecor, StrictName},
     field::StructField,
     help::Help,
-    td::{CommandCfg, EAttr, Ed, Mode, OptionsCfg, ParserCfg, TopInfo},
+    td::{CommandCfg, EAttr, Ed, FieldGroup, Mode, OptionsCfg, ParserCfg, TopInfo},
     utils::{to_kebab_case, to_snake_case, LineIter},
+    value_enum::ValueEnum,
 };
 
 #[derive(Debug)]
@@ -61,6 +62,7 @@ impl Parse for Top {
             attrs,
             ignore_rustdoc,
             adjacent,
+            groups,
         } = top_decor.unwrap_or_default();
 
         if ignore_rustdoc {
@@ -68,8 +70,13 @@ impl Parse for Top {
         }
         let vis = input.parse::<Visibility>()?;
 
-        let mut body = Body::parse(input)?;
+        let mut body = if matches!(mode, Mode::ValueEnum) {
+            Body::ValueEnum(input.parse::<ValueEnum>()?)
+        } else {
+            Body::parse(input)?
+        };
         let ty = body.ty();
+        body.set_groups(groups)?;
 
         if let Mode::Command { command, .. } = &mut mode {
             if let Some(name) = &command.name {
@@ -96,6 +103,7 @@ impl Parse for Top {
                         parser.group_help = Some(help);
                     }
                 }
+                Mode::ValueEnum => {}
             }
         }
 
@@ -279,6 +287,7 @@ impl ToTokens for Top {
                     }
                 }
             }
+            Mode::ValueEnum => quote!(#body),
         }
         .to_tokens(tokens)
     }
@@ -293,6 +302,7 @@ enum Body {
     // {{{
     Single(Branch),
     Alternatives(Ident, Vec<EnumBranch>),
+    ValueEnum(ValueEnum),
 }
 
 impl Parse for Body {
@@ -327,19 +337,33 @@ impl Body {
         match self {
             Body::Single(b) => &b.ident,
             Body::Alternatives(n, _) => n,
+            Body::ValueEnum(v) => &v.ty,
         }
         .clone()
     }
 }
 
 impl Body {
+    fn set_groups(&mut self, groups: Vec<FieldGroup>) -> Result<()> {
+        match (self, groups.first()) {
+            (_, None) => Ok(()),
+            (Body::Single(branch), Some(_)) => branch.set_groups(groups),
+            (Body::Alternatives(_, _) | Body::ValueEnum(_), Some(group)) => {
+                Err(Error::new_spanned(
+                    &group.kind,
+                    "Field groups can only be used on structs with named fields",
+                ))
+            }
+        }
+    }
+
     fn set_named_command(&mut self, span: Span) -> Result<()> {
         match self {
             Body::Single(branch) => {
                 branch.set_command();
                 Ok(())
             }
-            Body::Alternatives(_, _) => Err(Error::new(
+            Body::Alternatives(_, _) | Body::ValueEnum(_) => Err(Error::new(
                 span,
                 "You can't annotate `enum` with a named command.",
             )),
@@ -368,6 +392,7 @@ impl Body {
                 }*/
                 Ok(())
             }
+            Body::ValueEnum(_) => Ok(()),
         }
     }
 }
@@ -390,6 +415,7 @@ impl ToTokens for Body {
                     ::bpaf::construct!([ #( #name_t, )* ])
                 }}
             }
+            Body::ValueEnum(v) => quote!(#v),
         }
         .to_tokens(tokens);
     }
@@ -515,9 +541,37 @@ pub struct Branch {
     enum_name: Option<EnumPrefix>,
     ident: Ident,
     fields: FieldSet,
+    groups: Vec<FieldGroup>,
 }
 
 impl Branch {
+    fn set_groups(&mut self, groups: Vec<FieldGroup>) -> Result<()> {
+        let fields = match &self.fields {
+            FieldSet::Named(fields) => fields,
+            _ => {
+                return Err(Error::new_spanned(
+                    &groups[0].kind,
+                    "Field groups can only be used on structs with named fields",
+                ))
+            }
+        };
+        let mut seen = Vec::new();
+        for field in groups.iter().flat_map(|g| g.fields.iter()) {
+            if !fields.iter().any(|f| f.name.as_ref() == Some(field)) {
+                return Err(Error::new_spanned(field, "No such field"));
+            }
+            if seen.contains(&field) {
+                return Err(Error::new_spanned(
+                    field,
+                    "Field can be a part of only one group",
+                ));
+            }
+            seen.push(field);
+        }
+        self.groups = groups;
+        Ok(())
+    }
+
     fn set_command(&mut self) {
         if let FieldSet::Unit(_, _, _) = self.fields {
             let ident = &self.ident;
@@ -582,6 +636,7 @@ impl Parse for Branch {
             ident,
             //decor,
             fields,
+            groups: Vec::new(),
         })
     }
 }
@@ -592,8 +647,38 @@ impl ToTokens for Branch {
             enum_name,
             ident,
             fields,
+            groups,
         } = self;
         match fields {
+            FieldSet::Named(fields) if !groups.is_empty() => {
+                let name = fields
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, field)| field.var_name(ix))
+                    .collect::<Vec<_>>();
+                let value = fields.iter();
+
+                // fields stay in their order, group members record their presence and
+                // constraints are checked once the whole struct is parsed
+                let group = (0..groups.len())
+                    .map(|ix| Ident::new(&format!("group{}", ix), Span::call_site()))
+                    .collect::<Vec<_>>();
+                let group_defs = groups.iter().zip(&group).map(|(g, group)| {
+                    let FieldGroup { kind, fields } = g;
+                    quote! {
+                        let #group = ::bpaf::GroupCheck::#kind();
+                        #( let #fields = #group.member(#fields); )*
+                    }
+                });
+                quote! {{
+                    #( let #name = #value; )*
+                    #( #group_defs )*
+                    ::bpaf::check_groups(
+                        ::bpaf::construct!( #enum_name #ident { #( #name, )* }),
+                        vec![ #( #group ),* ],
+                    )
+                }}
+            }
             FieldSet::Named(fields) => {
                 let name = fields
                     .iter()
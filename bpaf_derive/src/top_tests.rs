@@ -1176,6 +1176,112 @@ fn top_comment_is_group_help_struct() {
     assert_eq!(top.to_token_stream().to_string(), expected.to_string());
 }
 
+#[test]
+fn top_struct_field_groups() {
+    let top: Top = parse_quote! {
+        #[bpaf(conflicts(json, yaml), requires(cert, key))]
+        struct Opts {
+            cert: Option<String>,
+            json: bool,
+            verbose: bool,
+            yaml: bool,
+            key: Option<String>,
+        }
+    };
+
+    let expected = quote! {
+        fn opts() -> impl ::bpaf::Parser<Opts> {
+            #[allow(unused_imports)]
+            use ::bpaf::Parser;
+            {
+                let cert = ::bpaf::long("cert").argument::<String>("ARG").optional();
+                let json = ::bpaf::long("json").switch();
+                let verbose = ::bpaf::long("verbose").switch();
+                let yaml = ::bpaf::long("yaml").switch();
+                let key = ::bpaf::long("key").argument::<String>("ARG").optional();
+                let group0 = ::bpaf::GroupCheck::conflicts();
+                let json = group0.member(json);
+                let yaml = group0.member(yaml);
+                let group1 = ::bpaf::GroupCheck::requires();
+                let cert = group1.member(cert);
+                let key = group1.member(key);
+                ::bpaf::check_groups(
+                    ::bpaf::construct!(Opts { cert, json, verbose, yaml, key, }),
+                    vec![group0, group1],
+                )
+            }
+        }
+    };
+    assert_eq!(top.to_token_stream().to_string(), expected.to_string());
+}
+
+#[test]
+fn top_enum_field_groups() {
+    let err = syn::parse2::<Top>(quote! {
+        #[bpaf(conflicts(a, b))]
+        enum Opts {
+            A { a: bool },
+            B { b: bool },
+        }
+    })
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Field groups can only be used on structs with named fields"
+    );
+}
+
+#[test]
+fn value_enum() {
+    let top: Top = parse_quote! {
+        #[bpaf(value_enum)]
+        enum Mode {
+            /// Go fast
+            Fast,
+            #[bpaf(long("snail"))]
+            SlowPace,
+            #[bpaf(skip)]
+            Hidden,
+        }
+    };
+
+    let expected = quote! {
+        impl ::bpaf::ValueEnum for Mode {
+            fn variants() -> &'static [(&'static str, ::std::option::Option<&'static str>)] {
+                &[
+                    ("fast", ::std::option::Option::Some("Go fast")),
+                    ("snail", ::std::option::Option::None),
+                ]
+            }
+        }
+
+        impl ::std::str::FromStr for Mode {
+            type Err = ::std::string::String;
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                match s {
+                    "fast" => ::std::result::Result::Ok(Mode::Fast),
+                    "snail" => ::std::result::Result::Ok(Mode::SlowPace),
+                    _ => ::std::result::Result::Err(::bpaf::unknown_value(
+                        s,
+                        <Mode as ::bpaf::ValueEnum>::variants(),
+                    )),
+                }
+            }
+        }
+
+        impl ::std::fmt::Display for Mode {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(match self {
+                    Mode::Fast => "fast",
+                    Mode::SlowPace => "snail",
+                    Mode::Hidden => "hidden",
+                })
+            }
+        }
+    };
+    assert_eq!(top.to_token_stream().to_string(), expected.to_string());
+}
+
 /*
 #[test]
 fn push_down_command() {
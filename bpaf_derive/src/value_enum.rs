@@ -0,0 +1,134 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    token, Attribute, Error, Fields, Ident, LitStr, Result, Variant,
+};
+
+use crate::{
+    attrs::parse_bpaf_doc_attrs,
+    help::Help,
+    td::{EAttr, Ed},
+    utils::{to_kebab_case, LineIter},
+};
+
+/// Enum with unit variants annotated with `value_enum`
+///
+/// Instead of a parser generates `FromStr`, `Display` and `bpaf::ValueEnum` implementations
+#[derive(Debug)]
+pub(crate) struct ValueEnum {
+    pub(crate) ty: Ident,
+    variants: Vec<ValueVariant>,
+}
+
+#[derive(Debug)]
+struct ValueVariant {
+    ident: Ident,
+    name: LitStr,
+    help: Option<String>,
+    /// skipped variants can't be parsed but still can be displayed
+    skip: bool,
+}
+
+impl Parse for ValueEnum {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if !input.peek(token::Enum) {
+            return Err(input.error("value_enum can only be used with enums"));
+        }
+        input.parse::<token::Enum>()?;
+        let ty = input.parse::<Ident>()?;
+        if input.peek(token::Lt) {
+            return Err(input.error("value_enum doesn't support generic enums"));
+        }
+        let content;
+        braced!(content in input);
+
+        let mut variants = Vec::new();
+        for variant in content.parse_terminated(Variant::parse, token::Comma)? {
+            if !matches!(variant.fields, Fields::Unit) {
+                return Err(Error::new_spanned(
+                    variant.fields,
+                    "value_enum only supports variants without fields",
+                ));
+            }
+            variants.push(ValueVariant::resolve(variant.ident, &variant.attrs)?);
+        }
+        Ok(ValueEnum { ty, variants })
+    }
+}
+
+impl ValueVariant {
+    fn resolve(ident: Ident, attrs: &[Attribute]) -> Result<Self> {
+        let (decor, help) = parse_bpaf_doc_attrs::<Ed>(attrs)?;
+        let Ed { skip, attrs } = decor.unwrap_or_default();
+
+        let mut name = LitStr::new(&to_kebab_case(&ident.to_string()), ident.span());
+        for attr in attrs {
+            match attr {
+                EAttr::UnitLong(Some(n)) => name = n,
+                _ => {
+                    return Err(Error::new(
+                        ident.span(),
+                        "value_enum variants only support `skip` and `long(\"name\")` annotations",
+                    ))
+                }
+            }
+        }
+
+        let help = match help {
+            Some(Help::Doc(doc)) => LineIter::from(doc.as_str()).next(),
+            _ => None,
+        };
+        Ok(ValueVariant {
+            ident,
+            name,
+            help,
+            skip,
+        })
+    }
+}
+
+impl ToTokens for ValueEnum {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let ty = &self.ty;
+        let parsed = self.variants.iter().filter(|v| !v.skip).collect::<Vec<_>>();
+        let ident = parsed.iter().map(|v| &v.ident).collect::<Vec<_>>();
+        let name = parsed.iter().map(|v| &v.name).collect::<Vec<_>>();
+        let help = parsed.iter().map(|v| match &v.help {
+            Some(h) => quote!(::std::option::Option::Some(#h)),
+            None => quote!(::std::option::Option::None),
+        });
+        let all_ident = self.variants.iter().map(|v| &v.ident);
+        let all_name = self.variants.iter().map(|v| &v.name);
+        quote! {
+            impl ::bpaf::ValueEnum for #ty {
+                fn variants() -> &'static [(&'static str, ::std::option::Option<&'static str>)] {
+                    &[ #( (#name, #help), )* ]
+                }
+            }
+
+            impl ::std::str::FromStr for #ty {
+                type Err = ::std::string::String;
+                fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                    match s {
+                        #( #name => ::std::result::Result::Ok(#ty::#ident), )*
+                        _ => ::std::result::Result::Err(::bpaf::unknown_value(
+                            s,
+                            <#ty as ::bpaf::ValueEnum>::variants(),
+                        )),
+                    }
+                }
+            }
+
+            impl ::std::fmt::Display for #ty {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    f.write_str(match self {
+                        #( #ty::#all_ident => #all_name, )*
+                    })
+                }
+            }
+        }
+        .to_tokens(tokens);
+    }
+}
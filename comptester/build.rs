@@ -30,6 +30,7 @@ fn main() -> Result<()> {
         "../dotfiles/zsh",
         "../dotfiles/fish/completions",
         "../dotfiles/elvish",
+        "../dotfiles/nu",
     ] {
         std::fs::create_dir_all(dir)?;
     }
@@ -109,6 +110,36 @@ fn main() -> Result<()> {
         }
     }
 
+    // nushell config
+    {
+        let mut env = std::fs::File::create("../dotfiles/nu/env.nu")?;
+        writeln!(env, "$env.PROMPT_COMMAND = {{|| \"\" }}")?;
+        writeln!(env, "$env.PROMPT_COMMAND_RIGHT = {{|| \"\" }}")?;
+        writeln!(env, "$env.PROMPT_INDICATOR = {{|| \"% \" }}")?;
+
+        let mut config = std::fs::File::create("../dotfiles/nu/config.nu")?;
+        writeln!(config, "$env.config.show_banner = false")?;
+
+        for example in &examples {
+            let common = [
+                "run",
+                "--release",
+                "--package=bpaf",
+                "--example",
+                example,
+                "--",
+            ];
+
+            let mut cmd = Command::new("cargo");
+            let nu = cmd
+                .args(common)
+                .arg("--bpaf-complete-style-nushell")
+                .output()?
+                .stdout;
+            writeln!(config, "{}", std::str::from_utf8(&nu)?)?;
+        }
+    }
+
     for example in &examples {
         let common = [
             "run",
@@ -48,6 +48,16 @@ cat";
     assert_eq!(buf, expected);
 }
 
+#[test]
+fn all_options_nu() {
+    // nushell menu layout depends on the version, only check for candidates
+    let buf = nu_comptest("coreutils \t").unwrap();
+    for cmd in ["arch", "b2sum", "base32", "basename", "cat"] {
+        assert!(buf.contains(cmd), "{cmd} is missing in {buf}");
+    }
+    assert!(buf.contains("Print machine architecture."));
+}
+
 #[test]
 fn cat_zsh() {
     let buf = zsh_comptest("coreutils cat -- \t").unwrap();
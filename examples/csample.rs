@@ -29,7 +29,9 @@ fn main() {
     $ csample --bpaf-complete-style-bash
     $ csample --bpaf-complete-style-zsh
     $ csample --bpaf-complete-style-fish
-    $ csample --bpaf-complete-style-elvish",
+    $ csample --bpaf-complete-style-elvish
+    $ csample --bpaf-complete-style-powershell
+    $ csample --bpaf-complete-style-nushell",
         );
 
     println!("{:?}", parser.fallback_to_usage().run());
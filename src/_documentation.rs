@@ -2241,6 +2241,16 @@
             //! 		$ your_program --bpaf-complete-style-elvish >> ~/.config/elvish/rc.elv
             //! 		```
             //! 
+            //!      1. **PowerShell**
+            //!         ```console
+            //!         $ your_program --bpaf-complete-style-powershell >> $PROFILE
+            //!         ```
+            //! 
+            //!      1. **nushell**: external completer, loaded from `config.nu`
+            //!         ```console
+            //!         $ your_program --bpaf-complete-style-nushell >> $nu.config-path
+            //!         ```
+            //! 
             //! 4. Restart your shell - you need to done it only once or optionally after bpaf major version
             //!     upgrade: generated completion files contain only instructions how to ask your program for
             //!     possible completions and don’t change even if options are different.
@@ -2248,6 +2258,15 @@
             //! 
             //! 5. Generated scripts rely on your program being accessible in $PATH
             //! 
+            //! 6. If running your program for every completion request is too slow or impossible you can
+            //!    generate a static script with `OptionParser::render_completion_script` and ship it with your
+            //!    program, for example in a distro package. Static scripts are available for bash, zsh and fish
+            //!    and only call your program to complete items with `complete` attached.
+            //! 
+            //! 7. To let users set up completion without looking up where their shell keeps completion files add
+            //!    `batteries::completion_command` to your parser: `your_program completions install` detects the
+            //!    shell from `$SHELL` and writes the script to a directory the shell loads completions from.
+            //! 
             //! 
             //! 
             //!
@@ -2,10 +2,12 @@ use std::ffi::OsString;
 
 pub(crate) use crate::arg::*;
 use crate::{
+    buffer::{is_terminal, Stream},
     error::{Message, MissingItem},
     item::Item,
     meta_help::Metavar,
     parsers::NamedArg,
+    prompt::Input,
     Error,
 };
 
@@ -37,6 +39,7 @@ pub struct Args<'a> {
     name: Option<String>,
     #[cfg(feature = "autocomplete")]
     c_rev: Option<usize>,
+    prompt: Option<Input>,
 }
 
 impl Args<'_> {
@@ -77,6 +80,49 @@ impl Args<'_> {
         self.name = Some(name.to_owned());
         self
     }
+
+    /// Answer prompts with these lines instead of asking the user
+    ///
+    /// Without this prompts are only shown for [`current_args`](Args::current_args) when `stdin`
+    /// is a terminal, see [`prompt`](crate::parsers::ParseArgument::prompt).
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = long("token")
+    ///     .argument::<String>("TOKEN")
+    ///     .prompt_hidden("Enter API token")
+    ///     .to_options();
+    /// let r = parser
+    ///     .run_inner(Args::from(&[]).set_prompt_input(&["s3cr3t"]))
+    ///     .unwrap();
+    /// assert_eq!(r, "s3cr3t");
+    /// ```
+    #[must_use]
+    pub fn set_prompt_input(mut self, lines: &[&str]) -> Self {
+        let lines = lines.iter().map(|l| (*l).to_owned()).collect();
+        self.prompt = Some(Input::Scripted(lines));
+        self
+    }
+}
+
+impl<'a> Args<'a> {
+    /// Replace `@path` items with contents of response files, unless completion is requested
+    pub(crate) fn expand_response_files(mut self) -> Result<Self, Message> {
+        #[cfg(feature = "autocomplete")]
+        if self.c_rev.is_some() {
+            return Ok(self);
+        }
+        let items = self.items.collect::<Vec<_>>();
+        let completing = items.iter().any(|i| {
+            i.to_str()
+                .map_or(false, |s| s.starts_with("--bpaf-complete-"))
+        });
+        self.items = if completing {
+            Box::new(items.into_iter())
+        } else {
+            Box::new(crate::response::expand(items.into_iter())?.into_iter())
+        };
+        Ok(self)
+    }
 }
 
 impl<const N: usize> From<&'static [&'static str; N]> for Args<'_> {
@@ -86,6 +132,7 @@ impl<const N: usize> From<&'static [&'static str; N]> for Args<'_> {
             #[cfg(feature = "autocomplete")]
             c_rev: None,
             name: None,
+            prompt: None,
         }
     }
 }
@@ -97,6 +144,7 @@ impl<'a> From<&'a [&'a std::ffi::OsStr]> for Args<'a> {
             #[cfg(feature = "autocomplete")]
             c_rev: None,
             name: None,
+            prompt: None,
         }
     }
 }
@@ -108,6 +156,7 @@ impl<'a> From<&'a [&'a str]> for Args<'a> {
             #[cfg(feature = "autocomplete")]
             c_rev: None,
             name: None,
+            prompt: None,
         }
     }
 }
@@ -119,6 +168,7 @@ impl<'a> From<&'a [String]> for Args<'a> {
             #[cfg(feature = "autocomplete")]
             c_rev: None,
             name: None,
+            prompt: None,
         }
     }
 }
@@ -130,6 +180,7 @@ impl<'a> From<&'a [OsString]> for Args<'a> {
             #[cfg(feature = "autocomplete")]
             c_rev: None,
             name: None,
+            prompt: None,
         }
     }
 }
@@ -145,11 +196,18 @@ impl Args<'_> {
             let s = file_name.to_str()?;
             Some(s.to_owned())
         });
+        // prompts need someone to answer them
+        let prompt = if is_terminal(Stream::Stdin) {
+            Some(Input::Terminal)
+        } else {
+            None
+        };
         Self {
             items: Box::new(value),
             #[cfg(feature = "autocomplete")]
             c_rev: None,
             name,
+            prompt,
         }
     }
 }
@@ -240,14 +298,64 @@ fn disambiguate_short(
     None
 }
 
+/// How to match partial or differently cased long names and commands, see
+/// [`allow_abbreviations`](crate::OptionParser::allow_abbreviations) and
+/// [`case_insensitive_longs`](crate::OptionParser::case_insensitive_longs)
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct LongMatching {
+    pub(crate) abbreviations: bool,
+    pub(crate) case_insensitive: bool,
+}
+
+impl LongMatching {
+    pub(crate) fn enabled(self) -> bool {
+        self.abbreviations || self.case_insensitive
+    }
+
+    /// Find the name `input` stands for
+    ///
+    /// Exact match wins, then a match ignoring case, then a unique prefix. `Ok(None)` means
+    /// `input` should be left as is, `Err` contains all the names it could stand for.
+    pub(crate) fn resolve<'a>(
+        self,
+        input: &str,
+        names: &'a [String],
+    ) -> Result<Option<&'a str>, Vec<String>> {
+        if input.is_empty() || names.iter().any(|n| n == input) {
+            return Ok(None);
+        }
+        let lower = input.to_lowercase();
+        let mut found = Vec::new();
+        if self.case_insensitive {
+            found.extend(names.iter().filter(|n| n.to_lowercase() == lower));
+        }
+        if found.is_empty() && self.abbreviations {
+            found.extend(names.iter().filter(|n| {
+                if self.case_insensitive {
+                    n.to_lowercase().starts_with(&lower)
+                } else {
+                    n.starts_with(input)
+                }
+            }));
+        }
+        found.sort();
+        found.dedup();
+        match found.as_slice() {
+            [] => Ok(None),
+            [name] => Ok(Some(name.as_str())),
+            _ => Err(found.into_iter().cloned().collect()),
+        }
+    }
+}
+
 pub use inner::State;
 /// Hides [`State`] internal implementation
 mod inner {
-    use std::{ops::Range, rc::Rc};
+    use std::{ffi::OsString, ops::Range, rc::Rc};
 
     use crate::{error::Message, Args};
 
-    use super::{split_os_argument, Arg, ArgType, ItemState};
+    use super::{split_os_argument, Arg, ArgType, ItemState, LongMatching};
     #[derive(Clone, Debug)]
     #[doc(hidden)]
     pub struct State {
@@ -259,18 +367,57 @@ mod inner {
         /// performance optimization mostly - tracks removed item and gives cheap is_empty and len
         remaining: usize,
 
+        /// names whose values were split with a delimiter, see [`State::push_back`]
+        pub(crate) split_keys: Vec<usize>,
+
+        /// each split makes a value shorter, so total length of the values bounds the number
+        /// of splits: `len` keeps decreasing even when splitting gives an item back
+        split_budget: usize,
+
         #[doc(hidden)]
         /// Used to render an error message for [`parse`][crate::Parser::parse]
         /// contains an index of a currently consumed item if we are parsing a single
         /// item
         pub current: Option<usize>,
 
+        /// index of the name the current value was taken with, see [`State::push_back`]
+        pub(crate) current_key: Option<usize>,
+
         /// path to current command, "deeper" parser should win in or_else branches
         pub(crate) path: Vec<String>,
 
         #[cfg(feature = "autocomplete")]
         comp: Option<crate::complete_gen::Complete>,
 
+        /// Config source of the innermost [`OptionParser`](crate::OptionParser) that has one
+        pub(crate) config: Option<crate::config::Config>,
+
+        /// Message catalog of the innermost [`OptionParser`](crate::OptionParser) that has one
+        pub(crate) locale: Option<crate::locale::Catalog>,
+
+        /// Source of answers for prompts, absent if nobody can answer them
+        pub(crate) prompts: Option<crate::prompt::Prompts>,
+
+        /// index of a command line argument each item came from, items can be split
+        /// so `-abc` and `--key=val` produce several items from a single argument
+        origins: Rc<[usize]>,
+
+        /// Sources of consumed values, only present when tracking was requested
+        pub(crate) provenance: Option<Vec<(String, crate::ValueSource)>>,
+
+        /// Problems that don't prevent parsing from succeeding, such as deprecated items
+        pub(crate) warnings: Vec<crate::Warning>,
+
+        /// How to match partial or differently cased long names and commands
+        pub(crate) long_matching: LongMatching,
+
+        /// Commands available at the current level, used to resolve abbreviated commands
+        pub(crate) commands: Rc<[String]>,
+
+        /// Long names available at the current level, used to resolve abbreviated and
+        /// differently cased long names, see [`State::resolve_longs`]
+        pub(crate) longs: Rc<[String]>,
+
         //        /// A way to customize behavior for --help and error handling
         //        pub(crate) improve_error: super::Improve,
         /// Describes scope current parser will be consuming elements from. Usually it will be
@@ -289,6 +436,52 @@ mod inner {
         pub(crate) fn depth(&self) -> usize {
             self.path.len()
         }
+
+        /// Index of a command line argument item at ixth position came from
+        pub(crate) fn origin(&self, ix: usize) -> usize {
+            self.origins.get(ix).copied().unwrap_or(ix)
+        }
+
+        /// Source of the value consumed last from the command line
+        pub(crate) fn cli_source(&self) -> crate::ValueSource {
+            crate::ValueSource::Cli(self.current.map_or(0, |ix| self.origin(ix)))
+        }
+
+        /// Answer to a prompt for a missing item, see [`Prompts`](crate::prompt::Prompts)
+        pub(crate) fn prompt(
+            &self,
+            name: &str,
+            prompt: &Option<crate::prompt::Prompt>,
+        ) -> Option<std::ffi::OsString> {
+            self.prompts.as_ref()?.answer(name, prompt.as_ref()?)
+        }
+
+        pub(crate) fn tracks_sources(&self) -> bool {
+            self.provenance.is_some()
+        }
+
+        /// Remember where the value for an item came from, if tracking was requested
+        pub(crate) fn record_source<F>(&mut self, name: F, source: crate::ValueSource)
+        where
+            F: FnOnce() -> String,
+        {
+            if let Some(provenance) = &mut self.provenance {
+                provenance.push((name(), source));
+            }
+        }
+
+        /// Warn about a consumed item if it is deprecated
+        pub(crate) fn check_deprecated<F>(&mut self, name: F, deprecated: Option<&'static str>)
+        where
+            F: FnOnce() -> String,
+        {
+            if let Some(message) = deprecated {
+                self.warnings.push(crate::Warning::Deprecated {
+                    name: name(),
+                    message,
+                });
+            }
+        }
     }
 
     pub(crate) struct ArgsIter<'a> {
@@ -314,6 +507,7 @@ mod inner {
             args: Args,
             short_flags: &[char],
             short_args: &[char],
+            long_matching: LongMatching,
             err: &mut Option<Message>,
         ) -> State {
             let mut items = Vec::new();
@@ -326,7 +520,14 @@ mod inner {
                 name: args.name.as_deref(),
             };
 
-            for os in args.items {
+            let mut origins = Vec::new();
+            let mut last_origin = 0;
+
+            for (origin, os) in args.items.enumerate() {
+                // all the items pushed so far came from the previous argument
+                origins.resize(items.len(), last_origin);
+                last_origin = origin;
+
                 if pos_only {
                     items.push(Arg::PosWord(os));
                     continue;
@@ -379,6 +580,9 @@ mod inner {
                 }
             }
 
+            origins.resize(items.len(), last_origin);
+
+            let split_budget = items.iter().map(|arg| arg.os_str().len()).sum();
             let mut item_state = vec![ItemState::Unparsed; items.len()];
             let mut remaining = items.len();
             if let Some(ix) = double_dash_marker {
@@ -397,18 +601,35 @@ mod inner {
             #[cfg(feature = "autocomplete")]
             let comp = comp_scanner.done();
 
+            let prompts = args.prompt.map(crate::prompt::Prompts::new);
+            // there's no one to answer prompts during completion
+            #[cfg(feature = "autocomplete")]
+            let prompts = prompts.filter(|_| comp.is_none());
+
             if let Some(name) = args.name {
                 path.push(name);
             }
             State {
                 item_state,
                 remaining,
+                split_keys: Vec::new(),
+                split_budget,
                 scope: 0..items.len(),
                 items: items.into(),
                 current: None,
+                current_key: None,
                 path,
                 #[cfg(feature = "autocomplete")]
                 comp,
+                config: None,
+                locale: None,
+                prompts,
+                origins: origins.into(),
+                provenance: None,
+                warnings: Vec::new(),
+                long_matching,
+                commands: Rc::from(Vec::new()),
+                longs: Rc::from(Vec::new()),
             }
         }
     }
@@ -462,14 +683,129 @@ mod inner {
             }
         }
 
+        /// Index of the first item consumed since `before` was cloned from this state
+        pub(crate) fn first_consumed_since(&self, before: &State) -> Option<usize> {
+            self.item_state
+                .iter()
+                .zip(before.item_state.iter())
+                .position(|(now, was)| now.parsed() && !was.parsed())
+        }
+
         #[allow(dead_code)]
         // it is in use when autocomplete is enabled
         pub(crate) fn is_empty(&self) -> bool {
             self.remaining == 0
         }
 
+        /// Goes down every time a parser consumes something, including a part of a value
         pub(crate) fn len(&self) -> usize {
-            self.remaining
+            self.remaining + self.split_budget - self.split_keys.len()
+        }
+
+        /// Resolve long names in the current scope against [`longs`](State::longs)
+        ///
+        /// Each level of commands resolves the names it gets to see again, starting from the
+        /// name as typed: a name stays as typed unless it stands for exactly one of the names
+        /// available at this level. Names that could stand for several are reported only if
+        /// nobody consumes them, see [`suggest`](crate::meta_youmean::suggest)
+        pub(crate) fn resolve_longs(&mut self) {
+            // resolving partial names would get in the way of completing them
+            #[cfg(feature = "autocomplete")]
+            if self.comp.is_some() {
+                return;
+            }
+            let mut items = None;
+            for ix in self.scope.clone() {
+                if !self.item_state[ix].present() {
+                    continue;
+                }
+                if let Some(resolved) = self.resolve_long(ix) {
+                    items.get_or_insert_with(|| self.items.to_vec())[ix] = resolved;
+                }
+            }
+            if let Some(items) = items {
+                self.items = items.into();
+            }
+        }
+
+        /// Long name at `ix` resolved against the names available at this level, `None` if
+        /// it stays as is
+        fn resolve_long(&self, ix: usize) -> Option<Arg> {
+            if let Arg::Long(name, attached, os) = &self.items[ix] {
+                let typed = match split_os_argument(os) {
+                    Some((ArgType::Long, typed, _)) => typed,
+                    _ => return None,
+                };
+                let resolved = match self.long_matching.resolve(&typed, &self.longs) {
+                    Ok(Some(resolved)) => resolved.to_owned(),
+                    Ok(None) | Err(_) => typed,
+                };
+                if resolved != *name {
+                    return Some(Arg::Long(resolved, *attached, os.clone()));
+                }
+            }
+            None
+        }
+
+        /// All the items with long names resolved the same way
+        /// [`resolve_longs`](State::resolve_longs) does, except for the last one which is
+        /// being completed
+        #[cfg(feature = "autocomplete")]
+        pub(crate) fn resolved_items(&self) -> Rc<[Arg]> {
+            let len = self.items.len().saturating_sub(1);
+            let mut items = None;
+            for ix in 0..len {
+                if let Some(resolved) = self.resolve_long(ix) {
+                    items.get_or_insert_with(|| self.items.to_vec())[ix] = resolved;
+                }
+            }
+            match items {
+                Some(items) => items.into(),
+                None => self.items.clone(),
+            }
+        }
+
+        /// Replace the text of the value that was just taken, returns its index
+        pub(crate) fn set_value(&mut self, val: OsString) -> Option<usize> {
+            let ix = self.current?;
+            let mut items = self.items.to_vec();
+            items[ix] = match items.get(ix)? {
+                Arg::ArgWord(_) => Arg::ArgWord(val),
+                Arg::Word(_) => Arg::Word(val),
+                _ => return None,
+            };
+            self.items = items.into();
+            Some(ix)
+        }
+
+        /// Give back the rest of a delimited value that was just taken together with its name
+        ///
+        /// `--tag a,b,c` stays `--tag b,c` for the next parser to consume, `taken` is the text
+        /// before the rest: `a,`
+        #[cfg_attr(not(feature = "autocomplete"), allow(unused_variables))]
+        pub(crate) fn push_back(&mut self, rest: OsString, taken: &str) {
+            let key_ix = match self.current_key {
+                Some(ix) => ix,
+                None => return,
+            };
+            let val_ix = match self.set_value(rest) {
+                Some(ix) => ix,
+                None => return,
+            };
+            for ix in [key_ix, val_ix] {
+                if self.item_state[ix].parsed() {
+                    self.item_state[ix] = ItemState::Unparsed;
+                    self.remaining += 1;
+                }
+            }
+            self.split_keys.push(key_ix);
+
+            #[cfg(feature = "autocomplete")]
+            if val_ix + 1 == self.items.len() {
+                if let Some(comp) = &mut self.comp {
+                    comp.value_prefix.push_str(taken);
+                }
+            }
         }
 
         /// Get an argument from a scope that was not consumed yet
@@ -639,6 +975,50 @@ impl State {
         }
     }
 
+    /// Get all the occurrences of a negatable flag: `--flag` / `--no-flag`, last one wins
+    ///
+    /// Returns `Some(false)` if the last one is negated, `None` if flag isn't present
+    pub(crate) fn take_negatable_flag(&mut self, named: &NamedArg) -> Option<bool> {
+        let mut res = None;
+        while let Some((ix, present)) = self.items_iter().find_map(|(ix, arg)| {
+            if named.matches_arg(arg, false) {
+                Some((ix, true))
+            } else if named.matches_negated(arg) {
+                Some((ix, false))
+            } else {
+                None
+            }
+        }) {
+            self.remove(ix);
+            res = Some(present);
+        }
+        res
+    }
+
+    /// Get a short or long argument with an optional value: `--flag` or `--flag=value`
+    ///
+    /// Only a value attached to the flag is consumed, in `--flag value` the value is left for
+    /// other parsers. Returns `Some(None)` if flag is present without a value
+    pub(crate) fn take_opt_arg(&mut self, named: &NamedArg) -> Option<Option<OsString>> {
+        let (key_ix, arg) = self
+            .items_iter()
+            .find(|arg| named.matches_arg(arg.1, false))?;
+        let attached = matches!(arg, Arg::Short(_, true, _) | Arg::Long(_, true, _));
+        self.remove(key_ix);
+
+        let val_ix = key_ix + 1;
+        match self.get(val_ix) {
+            Some(Arg::Word(w) | Arg::ArgWord(w)) if attached => {
+                let val = w.clone();
+                self.current = Some(val_ix);
+                self.current_key = Some(key_ix);
+                self.remove(val_ix);
+                Some(Some(val))
+            }
+            _ => Some(None),
+        }
+    }
+
     /// get a short or long arguments
     ///
     /// Returns Ok(None) if flag isn't present
@@ -664,6 +1044,7 @@ impl State {
         };
         let val = val.clone();
         self.current = Some(val_ix);
+        self.current_key = Some(key_ix);
         self.remove(key_ix);
         self.remove(val_ix);
         Ok(Some(val))
@@ -708,7 +1089,12 @@ impl State {
         if let Some((ix, Arg::Word(w) | Arg::Short(_, _, w) | Arg::Long(_, false, w))) =
             self.items_iter().next()
         {
-            if w == word {
+            let matches = w == word
+                || (self.long_matching.enabled()
+                    && w.to_str().map_or(false, |w| {
+                        self.long_matching.resolve(w, &self.commands) == Ok(Some(word))
+                    }));
+            if matches {
                 self.remove(ix);
                 self.current = Some(ix);
                 return true;
@@ -736,7 +1122,7 @@ mod tests {
         fn from(value: &'static [&'static str; N]) -> Self {
             let args = Args::from(value);
             let mut msg = None;
-            let res = State::construct(args, &[], &[], &mut msg);
+            let res = State::construct(args, &[], &[], LongMatching::default(), &mut msg);
             if let Some(err) = &msg {
                 panic!("Couldn't construct state: {:?}/{:?}", err, res);
             }
@@ -766,7 +1152,7 @@ mod tests {
     fn multiple_short_flags() {
         let args = Args::from(&["-vvv"]);
         let mut err = None;
-        let mut a = State::construct(args, &['v'], &[], &mut err);
+        let mut a = State::construct(args, &['v'], &[], LongMatching::default(), &mut err);
         assert!(a.take_flag(&short('v')));
         assert!(a.take_flag(&short('v')));
         assert!(a.take_flag(&short('v')));
@@ -890,7 +1276,13 @@ mod tests {
     fn ambiguity_towards_flag() {
         let args = Args::from(&["-abc"]);
         let mut err = None;
-        let mut a = State::construct(args, &['a', 'b', 'c'], &[], &mut err);
+        let mut a = State::construct(
+            args,
+            &['a', 'b', 'c'],
+            &[],
+            LongMatching::default(),
+            &mut err,
+        );
 
         assert!(a.take_flag(&short('a')));
         assert!(a.take_flag(&short('b')));
@@ -901,7 +1293,7 @@ mod tests {
     fn ambiguity_towards_argument() {
         let args = Args::from(&["-abc"]);
         let mut err = None;
-        let mut a = State::construct(args, &[], &['a'], &mut err);
+        let mut a = State::construct(args, &[], &['a'], LongMatching::default(), &mut err);
 
         let r = a.take_arg(&short('a'), false, M).unwrap().unwrap();
         assert_eq!(r, "bc");
@@ -911,7 +1303,13 @@ mod tests {
     fn ambiguity_towards_error() {
         let args = Args::from(&["-abc"]);
         let mut err = None;
-        let _a = State::construct(args, &['a', 'b', 'c'], &['a'], &mut err);
+        let _a = State::construct(
+            args,
+            &['a', 'b', 'c'],
+            &['a'],
+            LongMatching::default(),
+            &mut err,
+        );
         assert!(err.is_some());
     }
 
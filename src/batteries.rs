@@ -102,6 +102,8 @@ pub fn verbose_by_slice<T: Copy + 'static, const N: usize>(
 /// Parser returns `Optional<T>` value, you can add a fallback with [`map`](Parser::map) or turn
 /// missing value info failure with a custom error message with [`parse`](Parser::parse).
 ///
+/// For `--flag` / `--no-flag` pairs that produce a `bool` see [`negatable`](NamedArg::negatable).
+///
 /// # Example
 /// ```console
 /// $ app --banana --no-banana --banana --banana
@@ -184,3 +186,216 @@ where
 {
     parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout()
 }
+
+/// What to do with shell completions, produced by [`completion_command`]
+#[cfg(feature = "autocomplete")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionAction {
+    /// Print the completion script to stdout
+    Print,
+    /// Write the completion script to a directory the shell loads completions from
+    Install,
+    /// Remove previously installed completion script
+    Uninstall,
+}
+
+/// Shell completion request parsed by [`completion_command`], use [`run`](Completions::run) to
+/// perform it
+#[cfg(feature = "autocomplete")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completions {
+    /// Name of the app as users invoke it
+    pub app: &'static str,
+    /// What to do
+    pub action: CompletionAction,
+    /// Shell given with `--shell` or detected from `$SHELL`
+    pub shell: crate::Shell,
+    /// Report what would be done without touching any files
+    pub dry_run: bool,
+}
+
+/// `completions` subcommand to print, install and uninstall shell completion scripts
+///
+/// Parser accepts `completions print`, `completions install` and `completions uninstall`, each
+/// with an optional `--shell bash|zsh|fish`, shell is detected from `$SHELL` otherwise.
+/// `install` and `uninstall` also take `--dry-run` to report what would be done without touching
+/// any files. Installed script asks the app for completions at runtime, see
+/// [`Shell::dynamic_script`](crate::Shell::dynamic_script), and goes into a directory the shell
+/// loads completions from:
+///
+/// - bash: `$XDG_DATA_HOME/bash-completion/completions/app`, requires `bash-completion`
+/// - zsh: `$XDG_DATA_HOME/zsh/site-functions/_app`, this directory must be in `$fpath`
+/// - fish: `$XDG_CONFIG_HOME/fish/completions/app.fish`
+///
+/// With `XDG_DATA_HOME` and `XDG_CONFIG_HOME` defaulting to `~/.local/share` and `~/.config`.
+/// **To use this function you need to enable both `batteries` and `autocomplete` features.**
+///
+/// # Usage
+/// ```rust
+/// # use bpaf::*;
+/// use bpaf::batteries::{completion_command, CompletionAction, Completions};
+///
+/// #[derive(Debug, Clone)]
+/// enum Cmd {
+///     Completions(Completions),
+///     Build { release: bool },
+/// }
+///
+/// let release = long("release").switch();
+/// let build = construct!(Cmd::Build { release })
+///     .to_options()
+///     .command("build");
+/// let completions = completion_command("app").map(Cmd::Completions);
+/// let parser = construct!([build, completions]).to_options();
+///
+/// let cmd = parser
+///     .run_inner(&["completions", "print", "--shell", "fish"])
+///     .unwrap();
+/// if let Cmd::Completions(completions) = cmd {
+///     assert_eq!(completions.action, CompletionAction::Print);
+///     let script = completions.run().unwrap();
+///     assert!(script.contains("--bpaf-complete-rev=9"));
+/// }
+/// ```
+#[cfg(feature = "autocomplete")]
+#[must_use]
+pub fn completion_command(app: &'static str) -> impl Parser<Completions> {
+    fn shell() -> impl Parser<crate::Shell> {
+        crate::long("shell")
+            .help("Shell to use: bash, zsh or fish, detected from $SHELL by default")
+            .argument::<String>("SHELL")
+            .parse(|name| {
+                shell_by_name(&name)
+                    .ok_or_else(|| format!("{} is not supported, use bash, zsh or fish", name))
+            })
+            .fallback_with(|| {
+                std::env::var("SHELL")
+                    .ok()
+                    .and_then(|path| shell_by_name(path.rsplit('/').next()?))
+                    .ok_or("can't detect the shell from $SHELL, pass it with --shell")
+            })
+    }
+
+    fn dry_run() -> impl Parser<bool> {
+        crate::long("dry-run")
+            .help("Report what would be done without changing any files")
+            .switch()
+    }
+
+    let print = shell()
+        .map(|shell| (CompletionAction::Print, shell, false))
+        .to_options()
+        .descr("Print the completion script")
+        .command("print");
+    let install = construct!(shell(), dry_run())
+        .map(|(shell, dry_run)| (CompletionAction::Install, shell, dry_run))
+        .to_options()
+        .descr("Install the completion script for the current user")
+        .command("install");
+    let uninstall = construct!(shell(), dry_run())
+        .map(|(shell, dry_run)| (CompletionAction::Uninstall, shell, dry_run))
+        .to_options()
+        .descr("Remove the installed completion script")
+        .command("uninstall");
+
+    construct!([print, install, uninstall])
+        .to_options()
+        .descr("Manage shell completions")
+        .command("completions")
+        .map(move |(action, shell, dry_run)| Completions {
+            app,
+            action,
+            shell,
+            dry_run,
+        })
+}
+
+#[cfg(feature = "autocomplete")]
+fn shell_by_name(name: &str) -> Option<crate::Shell> {
+    match name {
+        "bash" => Some(crate::Shell::Bash),
+        "zsh" => Some(crate::Shell::Zsh),
+        "fish" => Some(crate::Shell::Fish),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "autocomplete")]
+impl Completions {
+    /// Where [`Install`](CompletionAction::Install) puts the script
+    ///
+    /// # Errors
+    ///
+    /// Fails if neither XDG directory variable nor `$HOME` is set
+    pub fn path(&self) -> std::io::Result<std::path::PathBuf> {
+        use std::path::PathBuf;
+        let xdg = |var: &str, fallback: &str| -> std::io::Result<PathBuf> {
+            if let Some(dir) = std::env::var_os(var).filter(|d| !d.is_empty()) {
+                return Ok(PathBuf::from(dir));
+            }
+            match std::env::var_os("HOME") {
+                Some(home) => Ok(PathBuf::from(home).join(fallback)),
+                None => Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("neither ${} nor $HOME is set", var),
+                )),
+            }
+        };
+        Ok(match self.shell {
+            crate::Shell::Bash => xdg("XDG_DATA_HOME", ".local/share")?
+                .join("bash-completion/completions")
+                .join(self.app),
+            crate::Shell::Zsh => xdg("XDG_DATA_HOME", ".local/share")?
+                .join("zsh/site-functions")
+                .join(format!("_{}", self.app)),
+            crate::Shell::Fish => xdg("XDG_CONFIG_HOME", ".config")?
+                .join("fish/completions")
+                .join(format!("{}.fish", self.app)),
+        })
+    }
+
+    /// Perform the action, returns text to show to the user
+    ///
+    /// For [`Print`](CompletionAction::Print) that's the script itself, for other actions - a
+    /// short report of what was done.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the script can't be written or removed
+    pub fn run(&self) -> std::io::Result<String> {
+        let script = self.shell.dynamic_script(self.app);
+        match self.action {
+            CompletionAction::Print => Ok(script),
+            CompletionAction::Install => {
+                let path = self.path()?;
+                if !self.dry_run {
+                    if let Some(dir) = path.parent() {
+                        std::fs::create_dir_all(dir)?;
+                    }
+                    std::fs::write(&path, script)?;
+                }
+                let verb = if self.dry_run { "Would write" } else { "Wrote" };
+                let mut res = format!("{} {}\n", verb, path.display());
+                if let (crate::Shell::Zsh, Some(dir)) = (self.shell, path.parent()) {
+                    res.push_str(&format!("Make sure {} is in your $fpath\n", dir.display()));
+                }
+                Ok(res)
+            }
+            CompletionAction::Uninstall => {
+                let path = self.path()?;
+                if !path.exists() {
+                    return Ok(format!("{} is not installed\n", path.display()));
+                }
+                if !self.dry_run {
+                    std::fs::remove_file(&path)?;
+                }
+                let verb = if self.dry_run {
+                    "Would remove"
+                } else {
+                    "Removed"
+                };
+                Ok(format!("{} {}\n", verb, path.display()))
+            }
+        }
+    }
+}
@@ -14,7 +14,7 @@ mod html;
 mod manpage;
 mod splitter;
 
-pub(crate) use self::console::Color;
+pub(crate) use self::console::{is_terminal, Color, Layout, Stream};
 #[cfg(feature = "docgen")]
 pub use manpage::Section;
 
@@ -130,14 +130,14 @@ impl Doc {
 }
 
 impl Doc {
-    pub(crate) fn write_shortlong(&mut self, name: &ShortLong) {
+    pub(crate) fn write_shortlong(&mut self, name: &ShortLong, negatable: bool) {
         match name {
             ShortLong::Short(s) => {
                 self.write_char('-', Style::Literal);
                 self.write_char(*s, Style::Literal);
             }
             ShortLong::Long(l) | ShortLong::ShortLong(_, l) => {
-                self.write_str("--", Style::Literal);
+                self.write_str(if negatable { "--[no-]" } else { "--" }, Style::Literal);
                 self.write_str(l, Style::Literal);
             }
         }
@@ -154,25 +154,38 @@ impl Doc {
                 help: _,
                 meta: _,
                 info: _,
+                deprecated: _,
             } => {
                 self.write_str("COMMAND ...", Style::Metavar);
             }
             Item::Flag {
                 name,
                 shorts: _,
+                longs: _,
                 env: _,
                 help: _,
-            } => self.write_shortlong(name),
+                deprecated: _,
+                negatable,
+            } => self.write_shortlong(name, *negatable),
             Item::Argument {
                 name,
                 shorts: _,
+                longs: _,
                 metavar,
                 env: _,
                 help: _,
+                deprecated: _,
+                opt_value,
             } => {
-                self.write_shortlong(name);
-                self.write_char('=', Style::Text);
-                self.metavar(*metavar);
+                self.write_shortlong(name, false);
+                if *opt_value {
+                    self.write_str("[=", Style::Text);
+                    self.metavar(*metavar);
+                    self.write_char(']', Style::Text);
+                } else {
+                    self.write_char('=', Style::Text);
+                    self.metavar(*metavar);
+                }
             }
             Item::Any {
                 metavar,
@@ -222,7 +235,9 @@ impl Doc {
                 Meta::Adjacent(m) | Meta::Subsection(m, _) | Meta::Suffix(m, _) => {
                     go(m, f);
                 }
-                Meta::Skip => {} // => f.write_str("no parameters expected", Style::Text),
+                #[cfg(feature = "autocomplete")]
+                Meta::Complete(m, _) => go(m, f),
+                Meta::Skip | Meta::Hidden(_) => {} // => f.write_str("no parameters expected", Style::Text),
                 Meta::CustomUsage(_, u) => {
                     f.doc(u);
                 }
@@ -485,6 +500,7 @@ fn extract_sections<'a>(
             help: _,
             meta,
             info,
+            deprecated: _,
         } = item
         {
             path.push((*name).to_string());
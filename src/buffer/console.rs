@@ -41,6 +41,147 @@ use super::Style;
 const MAX_TAB: usize = 24;
 const MAX_WIDTH: usize = 100;
 
+/// Width settings for console rendering, see
+/// [`help_width`](crate::OptionParser::help_width) and [`max_tab`](crate::OptionParser::max_tab)
+///
+/// Without explicit width lines are wrapped once they go past [`MAX_WIDTH`], with explicit
+/// width words that don't fit move to the next line.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub(crate) struct Layout {
+    width: Option<u16>,
+    max_tab: Option<u16>,
+}
+
+fn columns(n: usize) -> u16 {
+    u16::try_from(n).unwrap_or(u16::MAX)
+}
+
+impl Layout {
+    pub(crate) fn set_width(&mut self, width: usize) {
+        self.width = Some(columns(width));
+    }
+
+    pub(crate) fn set_max_tab(&mut self, max_tab: usize) {
+        self.max_tab = Some(columns(max_tab));
+    }
+
+    /// Use terminal width for output unless width is already set
+    pub(crate) fn or_terminal(self, stream: Stream) -> Self {
+        Self {
+            width: self.width.or_else(|| terminal_width(stream)),
+            max_tab: self.max_tab,
+        }
+    }
+
+    fn max_tab(self) -> usize {
+        self.max_tab.map_or(MAX_TAB, usize::from)
+    }
+
+    fn width(self) -> Option<usize> {
+        self.width.map(usize::from)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum Stream {
+    Stdin,
+    Stdout,
+    Stderr,
+}
+
+/// Width of a terminal connected to the stream, falls back to `COLUMNS` environment variable
+fn terminal_width(stream: Stream) -> Option<u16> {
+    ioctl_width(stream).or_else(|| {
+        std::env::var("COLUMNS")
+            .ok()?
+            .trim()
+            .parse::<u16>()
+            .ok()
+            .filter(|w| *w > 0)
+    })
+}
+
+/// Check if the stream is connected to a terminal
+#[cfg(unix)]
+pub(crate) fn is_terminal(stream: Stream) -> bool {
+    extern "C" {
+        fn isatty(fd: std::os::raw::c_int) -> std::os::raw::c_int;
+    }
+    let fd = match stream {
+        Stream::Stdin => 0,
+        Stream::Stdout => 1,
+        Stream::Stderr => 2,
+    };
+    // SAFETY: isatty only looks at the file descriptor
+    unsafe { isatty(fd) == 1 }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn is_terminal(_stream: Stream) -> bool {
+    false
+}
+
+#[cfg(all(
+    target_os = "linux",
+    not(any(
+        target_arch = "mips",
+        target_arch = "mips64",
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "sparc64"
+    ))
+))]
+fn ioctl_width(stream: Stream) -> Option<u16> {
+    #[repr(C)]
+    struct Winsize {
+        ws_row: u16,
+        ws_col: u16,
+        ws_xpixel: u16,
+        ws_ypixel: u16,
+    }
+    const TIOCGWINSZ: std::os::raw::c_ulong = 0x5413;
+    extern "C" {
+        fn ioctl(
+            fd: std::os::raw::c_int,
+            request: std::os::raw::c_ulong,
+            ...
+        ) -> std::os::raw::c_int;
+    }
+
+    let fd = match stream {
+        Stream::Stdin => 0,
+        Stream::Stdout => 1,
+        Stream::Stderr => 2,
+    };
+    let mut size = Winsize {
+        ws_row: 0,
+        ws_col: 0,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    // SAFETY: TIOCGWINSZ only writes a `winsize` struct into the pointer it gets
+    let res = unsafe { ioctl(fd, TIOCGWINSZ, &mut size as *mut Winsize) };
+    if res == 0 && size.ws_col > 0 {
+        Some(size.ws_col)
+    } else {
+        None
+    }
+}
+
+#[cfg(not(all(
+    target_os = "linux",
+    not(any(
+        target_arch = "mips",
+        target_arch = "mips64",
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "sparc64"
+    ))
+)))]
+fn ioctl_width(_stream: Stream) -> Option<u16> {
+    None
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 /// Default to dull color if colors are enabled,
 #[allow(dead_code)] // not fully used in without colors
@@ -133,18 +274,20 @@ impl Doc {
     /// difference for rendered help message, otherwise you can pass `true`.
     #[must_use]
     pub fn monochrome(&self, full: bool) -> String {
-        self.render_console(full, Color::Monochrome)
+        self.render_console(full, Color::Monochrome, Layout::default())
     }
 
     #[allow(clippy::too_many_lines)] // it's a big ass match statement
-    pub(crate) fn render_console(&self, full: bool, color: Color) -> String {
+    pub(crate) fn render_console(&self, full: bool, color: Color, layout: Layout) -> String {
+        let max_tab = layout.max_tab();
+        let max_width = layout.width().unwrap_or(MAX_WIDTH);
         let mut res = String::new();
         let mut tabstop = 0;
         let mut byte_pos = 0;
         {
             let mut current = 0;
             let mut in_term = false;
-            // looking for widest term below MAX_TAB
+            // looking for widest term below max_tab
             for token in self.tokens.iter().copied() {
                 match token {
                     Token::Text { bytes, style: _ } => {
@@ -159,7 +302,7 @@ impl Doc {
                     }
                     Token::BlockEnd(Block::ItemTerm) => {
                         in_term = false;
-                        if current > tabstop && current <= MAX_TAB {
+                        if current > tabstop && current <= max_tab {
                             tabstop = current;
                         }
                     }
@@ -208,7 +351,14 @@ impl Doc {
                                     if pending_blank_line && !res.ends_with("\n\n") {
                                         res.push('\n');
                                     }
-                                    if char_pos > MAX_WIDTH {
+                                    // only break between words, `-o=` and `OUTPUT` are
+                                    // separate chunks
+                                    let overflows = layout.width.is_some()
+                                        && s != " "
+                                        && res.ends_with(' ')
+                                        && char_pos > margin
+                                        && char_pos + w > max_width;
+                                    if char_pos > max_width || overflows {
                                         char_pos = 0;
                                         res.truncate(res.trim_end().len());
                                         res.push('\n');
@@ -224,7 +374,7 @@ impl Doc {
                                     char_pos = margin;
                                     pushed = missing;
                                 }
-                                if pending_margin && char_pos >= MAX_TAB + 4 && pushed < 2 {
+                                if pending_margin && char_pos >= max_tab + 4 && pushed < 2 {
                                     let missing = 2 - pushed;
                                     res.push_str(&PADDING[..missing]);
                                     char_pos += missing;
@@ -519,3 +669,85 @@ mod test {
         );
     }
 }*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Doc {
+        let mut doc = Doc::default();
+        for (term, body) in [
+            (
+                "--hello",
+                "Greet everyone in the room, one by one, loudly and clearly",
+            ),
+            ("-v", "Be verbose"),
+        ] {
+            doc.token(Token::BlockStart(Block::ItemTerm));
+            doc.literal(term);
+            doc.token(Token::BlockEnd(Block::ItemTerm));
+            doc.token(Token::BlockStart(Block::ItemBody));
+            doc.text(body);
+            doc.token(Token::BlockEnd(Block::ItemBody));
+        }
+        doc
+    }
+
+    fn render(width: Option<usize>, max_tab: Option<usize>) -> String {
+        let mut layout = Layout::default();
+        if let Some(width) = width {
+            layout.set_width(width);
+        }
+        if let Some(max_tab) = max_tab {
+            layout.set_max_tab(max_tab);
+        }
+        sample().render_console(true, Color::Monochrome, layout)
+    }
+
+    #[test]
+    fn default_width() {
+        let expected = "    --hello  Greet everyone in the room, one by one, loudly and clearly\n    -v       Be verbose";
+        assert_eq!(render(None, None), expected);
+    }
+
+    #[test]
+    fn narrow_width() {
+        let expected = [
+            "    --hello  Greet everyone in",
+            "             the room, one by",
+            "             one, loudly and",
+            "             clearly",
+            "    -v       Be verbose",
+        ];
+        assert_eq!(render(Some(30), None), expected.join("\n"));
+
+        let expected = [
+            "    --hello  Greet",
+            "             everyone in",
+            "             the room,",
+            "             one by one,",
+            "             loudly and",
+            "             clearly",
+            "    -v       Be verbose",
+        ];
+        assert_eq!(render(Some(24), None), expected.join("\n"));
+    }
+
+    #[test]
+    fn word_wider_than_width() {
+        let r = render(Some(10), None);
+        assert!(r.starts_with("    --hello\n             Greet\n             everyone\n"));
+        assert!(r.ends_with("    -v       Be\n             verbose"));
+    }
+
+    #[test]
+    fn narrow_tab() {
+        let expected = [
+            "    --hello  Greet everyone in",
+            "        the room, one by one,",
+            "        loudly and clearly",
+            "    -v  Be verbose",
+        ];
+        assert_eq!(render(Some(30), Some(4)), expected.join("\n"));
+    }
+}
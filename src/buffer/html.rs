@@ -52,8 +52,10 @@ fn collect_html(app: String, meta: &Meta, info: &Info) -> Doc {
             &section.path,
             section.info,
             section.meta,
-            &section.info.meta(),
+            &section.info.help_meta(None),
             false,
+            None,
+            None,
         );
         buf.doc(&b);
     }
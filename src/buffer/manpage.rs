@@ -124,9 +124,9 @@ impl<T> OptionParser<T> {
 
             let mut items = HelpItems::default();
             items.append_meta(section.meta);
-            let help_meta = section.info.meta();
+            let help_meta = section.info.help_meta(None);
             items.append_meta(&help_meta);
-            buf.write_help_item_groups(items, false);
+            buf.write_help_item_groups(items, false, None, None);
 
             if let Some(footer) = &section.info.footer {
                 buf.token(Token::BlockStart(Block::Block));
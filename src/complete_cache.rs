@@ -0,0 +1,357 @@
+//! Time budget and on-disk cache for dynamic completion, see
+//! [`ParseComp::timeout`](crate::parsers::ParseComp::timeout) and
+//! [`ParseComp::cache`](crate::parsers::ParseComp::cache)
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs::OpenOptions,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::{arg::Arg, item::Item, Meta};
+
+/// Environment variable set for a background process that computes suggestions, contains the
+/// name of the file suggestions are keyed by
+const REFRESH: &str = "BPAF_COMPLETE_REFRESH";
+
+/// Refresh lock older than that belongs to a process that died without removing it
+const LOCK_TTL: Duration = Duration::from_secs(60);
+
+/// Suggestions produced by a completion function: replacement and an optional description
+pub(crate) type Suggestions = Vec<(String, Option<String>)>;
+
+/// How long a completion function is allowed to run and how long its results are kept
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct CompCache {
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) max_age: Option<Duration>,
+}
+
+/// Everything cached suggestions depend on
+pub(crate) struct Context<'a> {
+    /// application name followed by the names of the commands
+    pub(crate) path: &'a [String],
+    pub(crate) group: Option<&'a str>,
+    /// item being completed, see [`item_key`]
+    pub(crate) item: &'a str,
+    pub(crate) meta: &'a str,
+    /// word being completed
+    pub(crate) word: &'a str,
+    /// items before the word, only when the completion function can look at them
+    pub(crate) before: &'a [Arg],
+}
+
+impl CompCache {
+    /// Get suggestions for `ctx`, `None` means the completion function ran out of time
+    pub(crate) fn get<F>(self, ctx: &Context, compute: F) -> Option<Suggestions>
+    where
+        F: FnOnce() -> Suggestions,
+    {
+        if self.timeout.is_none() && self.max_age.is_none() {
+            return Some(compute());
+        }
+        let file = match cache_file(ctx) {
+            Some(file) => file,
+            None => return self.compute_here(None, compute),
+        };
+        let lock = file.with_extension("lock");
+        // without a cache results are only kept until the waiting process reads them
+        let out = match self.max_age {
+            Some(_) => file.clone(),
+            None => file.with_extension("out"),
+        };
+
+        // background process started to compute suggestions for a single item, no time limits
+        // here. Its output is discarded so other items don't need to compute anything
+        if let Some(key) = std::env::var_os(REFRESH) {
+            if file.file_name() != Some(key.as_os_str()) {
+                return Some(Vec::new());
+            }
+            let _lock = LockGuard(&lock);
+            let res = compute();
+            write_cache(&out, &res);
+            return Some(res);
+        }
+
+        let restart = can_restart();
+        if let Some(max_age) = self.max_age {
+            if let Some((age, res)) = read_cache(&file) {
+                if age > max_age {
+                    if restart {
+                        refresh(&file, &lock);
+                    } else {
+                        // nobody to refresh it in the background, next request gets fresh values
+                        write_cache(&file, &compute());
+                    }
+                }
+                return Some(res);
+            }
+        }
+
+        match self.timeout {
+            // let the background process fill the cache and wait for it within the budget,
+            // if it doesn't finish in time - results are ready for the next request
+            Some(budget) if restart => {
+                let child = refresh(&file, &lock);
+                if !wait_for(child, &lock, budget) {
+                    return None;
+                }
+                let res = read_cache(&out).map(|(_, res)| res);
+                if self.max_age.is_none() {
+                    let _ = std::fs::remove_file(&out);
+                }
+                res
+            }
+            _ => self.compute_here(Some(&file), compute),
+        }
+    }
+
+    /// Run the completion function in this process
+    ///
+    /// Without a background process the budget can't stop the function, results that come
+    /// late are dropped
+    fn compute_here<F>(self, file: Option<&Path>, compute: F) -> Option<Suggestions>
+    where
+        F: FnOnce() -> Suggestions,
+    {
+        let start = Instant::now();
+        let res = compute();
+        if let (Some(file), Some(_)) = (file, self.max_age) {
+            write_cache(file, &res);
+        }
+        match self.timeout {
+            Some(budget) if start.elapsed() > budget => None,
+            _ => Some(res),
+        }
+    }
+}
+
+/// `$XDG_CACHE_HOME/bpaf/<app>/<hash of the context>`, `None` if there's no cache directory
+fn cache_file(ctx: &Context) -> Option<PathBuf> {
+    let dir = match std::env::var_os("XDG_CACHE_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".cache"),
+    };
+    let mut hasher = DefaultHasher::new();
+    ctx.path.hash(&mut hasher);
+    ctx.group.hash(&mut hasher);
+    ctx.item.hash(&mut hasher);
+    ctx.meta.hash(&mut hasher);
+    ctx.word.hash(&mut hasher);
+    for arg in ctx.before {
+        arg.os_str().hash(&mut hasher);
+    }
+    let app = ctx.path.first().map_or("bpaf", String::as_str);
+    Some(
+        dir.join("bpaf")
+            .join(app)
+            .join(format!("{:016x}", hasher.finish())),
+    )
+}
+
+/// Names of the items `meta` consists of: `--long` and `-s` for named items, metavariable and
+/// help for positional ones, identifies the item being completed
+pub(crate) fn item_key(meta: &Meta, to: &mut String) {
+    match meta {
+        Meta::And(xs) | Meta::Or(xs) => {
+            for x in xs {
+                item_key(x, to);
+            }
+        }
+        Meta::Item(item) => match &**item {
+            Item::Flag { shorts, longs, .. } | Item::Argument { shorts, longs, .. } => {
+                for long in longs {
+                    to.push_str(&format!(" --{}", long));
+                }
+                for short in shorts {
+                    to.push_str(&format!(" -{}", short));
+                }
+            }
+            Item::Positional { metavar, help } => {
+                to.push_str(&format!(" {}", metavar.0));
+                if let Some(help) = help {
+                    to.push_str(&format!(" {}", help.monochrome(true)));
+                }
+            }
+            Item::Any { metavar, .. } => to.push_str(&format!(" {}", metavar.monochrome(true))),
+            Item::Command { name, .. } => to.push_str(&format!(" {}", name)),
+        },
+        Meta::CustomUsage(m, _)
+        | Meta::Required(m)
+        | Meta::Optional(m)
+        | Meta::Adjacent(m)
+        | Meta::Subsection(m, _)
+        | Meta::Suffix(m, _)
+        | Meta::Many(m)
+        | Meta::Hidden(m)
+        | Meta::Strict(m)
+        | Meta::Complete(m, _) => item_key(m, to),
+        Meta::Skip => {}
+    }
+}
+
+/// Only a process started by a shell completion script can be restarted in the background
+fn can_restart() -> bool {
+    std::env::args_os().skip(1).any(|a| {
+        a.to_str()
+            .map_or(false, |a| a.starts_with("--bpaf-complete-rev="))
+    })
+}
+
+/// Take the refresh lock, only one process at a time refreshes the same suggestions
+fn try_lock(lock: &Path) -> bool {
+    if let Ok(modified) = std::fs::metadata(lock).and_then(|m| m.modified()) {
+        let age = SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or_default();
+        if age < LOCK_TTL {
+            return false;
+        }
+        let _ = std::fs::remove_file(lock);
+    }
+    if let Some(dir) = lock.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(lock)
+        .is_ok()
+}
+
+/// Removes the refresh lock once the process that holds it is done, even if the completion
+/// function panics
+struct LockGuard<'a>(&'a Path);
+
+impl Drop for LockGuard<'_> {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(self.0);
+    }
+}
+
+/// Run the same completion request again in a separate process to compute suggestions for the
+/// item cached in `file`, unless some other process is doing it already. The process removes
+/// the lock once it's done.
+fn refresh(file: &Path, lock: &Path) -> Option<Child> {
+    if !try_lock(lock) {
+        return None;
+    }
+    let spawned = std::env::current_exe().ok().and_then(|exe| {
+        Command::new(exe)
+            .args(std::env::args_os().skip(1))
+            .env(REFRESH, file.file_name()?)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()
+    });
+    if spawned.is_none() {
+        let _ = std::fs::remove_file(lock);
+    }
+    spawned
+}
+
+/// Wait for the process holding the lock to finish within the budget, `false` if it didn't
+///
+/// `child` is the process this request started, if it exits without removing the lock - the
+/// lock is removed here so the next request doesn't wait for it
+fn wait_for(mut child: Option<Child>, lock: &Path, budget: Duration) -> bool {
+    let start = Instant::now();
+    while lock.exists() {
+        if let Some(Ok(Some(status))) = child.as_mut().map(Child::try_wait) {
+            if !status.success() {
+                let _ = std::fs::remove_file(lock);
+            }
+            return true;
+        }
+        if start.elapsed() > budget {
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    true
+}
+
+/// Cached suggestions along with their age
+fn read_cache(file: &Path) -> Option<(Duration, Suggestions)> {
+    let modified = std::fs::metadata(file).ok()?.modified().ok()?;
+    let age = SystemTime::now()
+        .duration_since(modified)
+        .unwrap_or_default();
+    let body = std::fs::read_to_string(file).ok()?;
+    let res = body
+        .lines()
+        .map(|line| {
+            let mut parts = line.splitn(2, '\t');
+            let replacement = unescape(parts.next().unwrap_or(""));
+            (replacement, parts.next().map(unescape))
+        })
+        .collect();
+    Some((age, res))
+}
+
+/// Failing to write the cache is not fatal, values are computed again next time
+fn write_cache(file: &Path, res: &[(String, Option<String>)]) {
+    let mut body = String::new();
+    for (replacement, descr) in res {
+        body.push_str(&escape(replacement));
+        if let Some(descr) = descr {
+            body.push('\t');
+            body.push_str(&escape(descr));
+        }
+        body.push('\n');
+    }
+    let dir = match file.parent() {
+        Some(dir) => dir,
+        None => return,
+    };
+    // write to a temporary file first so concurrent readers never see a partial file
+    let tmp = file.with_extension(std::process::id().to_string());
+    if std::fs::create_dir_all(dir).is_ok() && std::fs::write(&tmp, body).is_ok() {
+        let _ = std::fs::rename(&tmp, file);
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('t') => res.push('\t'),
+                Some('n') => res.push('\n'),
+                Some(c) => res.push(c),
+                None => res.push('\\'),
+            }
+        } else {
+            res.push(c);
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::try_lock;
+
+    #[test]
+    fn one_refresh_at_a_time() {
+        let dir = std::env::temp_dir().join(format!("bpaf-comp-lock-{}", std::process::id()));
+        let lock = dir.join("key.lock");
+        assert!(try_lock(&lock));
+        assert!(!try_lock(&lock));
+        std::fs::remove_file(&lock).unwrap();
+        assert!(try_lock(&lock));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,74 @@
+//! Read-only view of the command line for completion functions, see
+//! [`complete_with_context`](crate::Parser::complete_with_context)
+
+use std::{ffi::OsStr, rc::Rc, str::FromStr};
+
+use crate::{arg::Arg, parsers::NamedArg};
+
+/// Items the user typed before the one being completed
+///
+/// Passed to a function given to [`complete_with_context`](crate::Parser::complete_with_context)
+/// so suggestions can depend on other arguments. Items are looked up by all the names of a
+/// [`NamedArg`], the same way the parser matches them: `long("repo").short('r')` finds values
+/// given to both `--repo` and `-r` and, with
+/// [`allow_abbreviations`](crate::OptionParser::allow_abbreviations), to `--re`. Items are
+/// found anywhere on the command line, whether the parser consumed them already or not.
+#[derive(Debug, Clone)]
+pub struct CompContext {
+    items: Rc<[Arg]>,
+    path: Vec<String>,
+}
+
+impl CompContext {
+    pub(crate) fn new(items: Rc<[Arg]>, path: Vec<String>) -> Self {
+        Self { items, path }
+    }
+
+    /// Items before the one being completed
+    pub(crate) fn before(&self) -> &[Arg] {
+        let len = self.items.len().saturating_sub(1);
+        &self.items[..len]
+    }
+
+    /// Check if a flag or an argument with any of these names is present
+    #[must_use]
+    pub fn is_present(&self, named: &NamedArg) -> bool {
+        self.before()
+            .iter()
+            .any(|arg| named.matches_arg(arg, false))
+    }
+
+    /// Text of the last value given to an argument with any of these names
+    ///
+    /// Both `--repo foo` and `--repo=foo` give `foo`, value must be complete: `--repo` followed
+    /// by the word being completed gives `None`.
+    #[must_use]
+    pub fn raw(&self, named: &NamedArg) -> Option<&OsStr> {
+        self.before().windows(2).rev().find_map(|pair| match pair {
+            [key, Arg::ArgWord(val) | Arg::Word(val)] if named.matches_arg(key, false) => {
+                Some(val.as_os_str())
+            }
+            _ => None,
+        })
+    }
+
+    /// Last value given to an argument with any of these names, parsed with [`FromStr`]
+    ///
+    /// `None` if the value is absent or can't be parsed
+    #[must_use]
+    pub fn value<T: FromStr>(&self, named: &NamedArg) -> Option<T> {
+        self.raw(named)?.to_str()?.parse().ok()
+    }
+
+    /// Application name followed by the names of the commands being completed
+    ///
+    /// Application name is only present if it's known
+    #[must_use]
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+}
+
+/// Completion function that takes [`CompContext`] as a second argument, created with
+/// [`complete_with_context`](crate::Parser::complete_with_context)
+pub struct WithContext<F>(pub(crate) F);
@@ -16,7 +16,10 @@
 
 use crate::{
     args::{Arg, State},
-    complete_shell::{render_bash, render_fish, render_simple, render_test, render_zsh},
+    complete_shell::{
+        render_bash, render_fish, render_nushell, render_powershell, render_simple, render_test,
+        render_zsh,
+    },
     item::ShortLong,
     parsers::NamedArg,
     Doc, ShellComp,
@@ -32,6 +35,9 @@ pub(crate) struct Complete {
     /// don't try to suggest any more positional items after there's a positional item failure
     /// or parsing in progress
     pub(crate) no_pos_ahead: bool,
+
+    /// Elements of a delimited value before the one being completed, `a,b,` for `--tag a,b,c`
+    pub(crate) value_prefix: String,
 }
 
 impl Complete {
@@ -40,13 +46,18 @@ impl Complete {
             comps: Vec::new(),
             output_rev,
             no_pos_ahead: false,
+            value_prefix: String::new(),
         }
     }
 }
 
 impl State {
     /// Add a new completion hint for flag, if needed
-    pub(crate) fn push_flag(&mut self, named: &NamedArg) {
+    pub(crate) fn push_flag(&mut self, named: &NamedArg, negatable: bool) {
+        // deprecated items are still accepted but not offered
+        if named.deprecated.is_some() {
+            return;
+        }
         let depth = self.depth();
         if let Some(comp) = self.comp_mut() {
             if let Ok(name) = ShortLong::try_from(named) {
@@ -57,6 +68,7 @@ impl State {
                         help: named.help.as_ref().and_then(Doc::to_completion),
                     },
                     name,
+                    negatable,
                 });
             }
         }
@@ -64,6 +76,10 @@ impl State {
 
     /// Add a new completion hint for an argument, if needed
     pub(crate) fn push_argument(&mut self, named: &NamedArg, metavar: &'static str) {
+        // deprecated items are still accepted but not offered
+        if named.deprecated.is_some() {
+            return;
+        }
         let depth = self.depth();
         if let Some(comp) = self.comp_mut() {
             if let Ok(name) = ShortLong::try_from(named) {
@@ -227,6 +243,8 @@ pub(crate) enum Comp {
     Flag {
         extra: CompExtra,
         name: ShortLong,
+        /// `--no-` version of the long name should be offered too
+        negatable: bool,
     },
 
     /// argument + metadata
@@ -289,6 +307,15 @@ impl Comp {
         }
     }
 
+    /// name of the metavariable placeholder, if this is one
+    pub(crate) fn metavar(&self) -> Option<&'static str> {
+        if let Comp::Metavariable { meta, .. } = self {
+            Some(meta)
+        } else {
+            None
+        }
+    }
+
     pub(crate) fn set_group(&mut self, group: String) {
         let extra = match self {
             Comp::Flag { extra, .. }
@@ -356,6 +383,17 @@ enum Prefix<'a> {
 }
 
 impl State {
+    /// Text of the item being completed, same one [`check_complete`](State::check_complete) uses
+    pub(crate) fn comp_lit(&self) -> Option<&str> {
+        self.items
+            .iter()
+            .rev()
+            .filter_map(Arg::and_os_string)
+            .filter_map(pair_to_os_string)
+            .map(|(_, lit)| lit)
+            .next()
+    }
+
     /// Generate completion from collected heads
     ///
     /// before calling this method we run parser in "complete" mode and collect live heads inside
@@ -381,11 +419,16 @@ impl State {
         // so to perform full completion we look at the preceeding item
         // and use it's value if it was a composite short/long argument
         let preceeding = items.next();
+        let joined;
         let (pos_only, full_lit) = match preceeding {
             Some((Arg::Short(_, true, _os) | Arg::Long(_, true, _os), full_lit)) => {
                 (false, full_lit)
             }
             Some((Arg::PosWord(_), _)) => (true, lit),
+            _ if !comp.value_prefix.is_empty() => {
+                joined = format!("{}{}", comp.value_prefix, lit);
+                (false, joined.as_str())
+            }
             _ => (false, lit),
         };
 
@@ -395,7 +438,7 @@ impl State {
             _ => Prefix::NA,
         };
 
-        let (items, shell) = comp.complete(lit, pos_only, prefix);
+        let (items, shell) = comp.complete(lit, pos_only, prefix, &comp.value_prefix);
 
         Some(match comp.output_rev {
             0 => render_test(&items, &shell, full_lit),
@@ -403,6 +446,8 @@ impl State {
             7 => render_zsh(&items, &shell, full_lit),
             8 => render_bash(&items, &shell, full_lit),
             9 => render_fish(&items, &shell, full_lit, self.path[0].as_str()),
+            10 => render_powershell(&items, &shell, full_lit),
+            11 => render_nushell(&items, &shell, full_lit),
             unk => {
                 eprintln!("Unsupported output revision {}, you need to genenerate your shell completion files for the app", unk);
                 std::process::exit(1);
@@ -494,6 +539,7 @@ impl Complete {
         arg: &str,
         pos_only: bool,
         prefix: Prefix,
+        value_prefix: &str,
     ) -> (Vec<ShowComp>, Vec<ShellComp>) {
         let mut items: Vec<ShowComp> = Vec::new();
         let mut shell = Vec::new();
@@ -525,7 +571,11 @@ impl Complete {
                     }
                 }
 
-                Comp::Flag { name, extra } => {
+                Comp::Flag {
+                    name,
+                    extra,
+                    negatable,
+                } => {
                     if let Some(long) = arg_matches(arg, *name) {
                         items.push(ShowComp {
                             pretty: long.clone(),
@@ -533,6 +583,16 @@ impl Complete {
                             extra,
                         });
                     }
+                    if let Some(long) = name.as_long().filter(|_| *negatable) {
+                        let negated = format!("--no-{}", long);
+                        if negated.starts_with(arg) {
+                            items.push(ShowComp {
+                                pretty: negated.clone(),
+                                subst: negated,
+                                extra,
+                            });
+                        }
+                    }
                 }
 
                 Comp::Argument {
@@ -558,9 +618,9 @@ impl Complete {
                         pretty: body.clone(),
                         extra,
                         subst: match prefix {
-                            Prefix::NA => body.clone(),
-                            Prefix::Short(s) => format!("-{}={}", s, body),
-                            Prefix::Long(l) => format!("--{}={}", l, body),
+                            Prefix::NA => format!("{}{}", value_prefix, body),
+                            Prefix::Short(s) => format!("-{}={}{}", s, value_prefix, body),
+                            Prefix::Long(l) => format!("--{}={}{}", l, value_prefix, body),
                         },
                     });
                 }
@@ -1,43 +1,42 @@
 use crate::complete_gen::Complete;
 use std::ffi::OsStr;
 
-fn dump_bash_completer(name: &str) {
-    println!(
+pub(crate) fn bash_completer(name: &str) -> String {
+    format!(
         r#"_bpaf_dynamic_completion()
 {{
     source <( "$1" --bpaf-complete-rev=8 "${{COMP_WORDS[@]:1}}" )
 }}
 complete -o nosort -F _bpaf_dynamic_completion {name}"#,
         name = name,
-    );
+    )
 }
 
-fn dump_zsh_completer(name: &str) {
-    println!(
+pub(crate) fn zsh_completer(name: &str) -> String {
+    format!(
         r#"#compdef {name}
 source <( "${{words[1]}}" --bpaf-complete-rev=7 "${{words[@]:1}}" )
 "#,
         name = name
-    );
+    )
 }
 
-fn dump_fish_completer(_name: &str) {
-    println!(
-        r#"set -l current (commandline --tokenize --current-process)
+pub(crate) fn fish_completer(_name: &str) -> String {
+    r#"set -l current (commandline --tokenize --current-process)
 set -l tmpline $current[1] --bpaf-complete-rev=9 $current[2..]
 if test (commandline --current-process) != (string trim (commandline --current-process))
     set tmpline $tmpline ""
 end
 source ( $tmpline | psub )"#
-    );
+        .to_owned()
 }
 
 // I would love to support elvish better but debugger is not a thing
 // and on any error in code it simply replies "no candidates" with no
 // obvious way even to print "you are here"...
 // https://github.com/elves/elvish/issues/803
-fn dump_elvish_completer(name: &str) {
-    println!(
+fn elvish_completer(name: &str) -> String {
+    format!(
         "\
 set edit:completion:arg-completer[{name}] = {{ |@args| var args = $args[1..];
      var @lines = ( {name} --bpaf-complete-rev={rev} $@args );
@@ -53,7 +52,47 @@ set edit:completion:arg-completer[{name}] = {{ |@args| var args = $args[1..];
 }}",
         name = name,
         rev = 1,
-    );
+    )
+}
+
+// PowerShell passes the whole command line as AST, only elements to the left of the cursor are
+// interesting. Empty strings are passed to native commands as is starting from pwsh 7.3
+fn powershell_completer(name: &str) -> String {
+    format!(
+        r#"Register-ArgumentCompleter -Native -CommandName '{name}' -ScriptBlock {{
+    param($wordToComplete, $commandAst, $cursorPosition)
+    $words = @($commandAst.CommandElements |
+        Where-Object {{ $_.Extent.StartOffset -lt $cursorPosition }} |
+        Select-Object -Skip 1 |
+        ForEach-Object {{ $_.ToString() }})
+    if ($wordToComplete -eq '') {{
+        $words += ''
+    }}
+    (& '{name}' --bpaf-complete-rev={rev} @words) -join "`n" | Invoke-Expression
+}}"#,
+        name = name.replace('\'', "''"),
+        rev = 10,
+    )
+}
+
+// Nushell supports a single external completer, new completer handles `name` and passes
+// everything else to whatever completer was configured before
+fn nushell_completer(name: &str) -> String {
+    format!(
+        r#"do --env {{
+    let previous = $env.config.completions.external.completer?
+    $env.config.completions.external.enable = true
+    $env.config.completions.external.completer = {{|spans|
+        if ($spans.0 | path basename) == '{name}' {{
+            ^$spans.0 --bpaf-complete-rev={rev} ...($spans | skip 1) | from json
+        }} else if $previous != null {{
+            do $previous $spans
+        }}
+    }}
+}}"#,
+        name = name,
+        rev = 11,
+    )
 }
 
 #[derive(Debug)]
@@ -72,10 +111,12 @@ impl ArgScanner<'_> {
         if let Some(name) = &self.name {
             let mut matched = true;
             match arg {
-                "--bpaf-complete-style-zsh" => dump_zsh_completer(name),
-                "--bpaf-complete-style-bash" => dump_bash_completer(name),
-                "--bpaf-complete-style-fish" => dump_fish_completer(name),
-                "--bpaf-complete-style-elvish" => dump_elvish_completer(name),
+                "--bpaf-complete-style-zsh" => println!("{}", zsh_completer(name)),
+                "--bpaf-complete-style-bash" => println!("{}", bash_completer(name)),
+                "--bpaf-complete-style-fish" => println!("{}", fish_completer(name)),
+                "--bpaf-complete-style-elvish" => println!("{}", elvish_completer(name)),
+                "--bpaf-complete-style-powershell" => println!("{}", powershell_completer(name)),
+                "--bpaf-complete-style-nushell" => println!("{}", nushell_completer(name)),
                 _ => {
                     matched = false;
                 }
@@ -41,6 +41,12 @@ pub enum ShellComp {
         /// This raw string will be used for `elvish` shell
         /// <https://elv.sh/ref/edit.html#completion-api>
         elvish: &'static str,
+
+        /// This raw string will be used for `PowerShell`, it should produce
+        /// `System.Management.Automation.CompletionResult` objects, current word is available as
+        /// `$wordToComplete`
+        /// <https://learn.microsoft.com/en-us/powershell/module/microsoft.powershell.core/register-argumentcompleter>
+        pwsh: &'static str,
     },
 
     /// Don't produce anything at all from this parser - can be useful if you want to compose
@@ -48,6 +54,19 @@ pub enum ShellComp {
     Nothing,
 }
 
+/// Completion hint attached to [`Meta`] by [`complete`](crate::Parser::complete) and
+/// [`complete_shell`](crate::Parser::complete_shell)
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub enum CompHint {
+    /// Static shell completion
+    Shell(ShellComp),
+    /// Values are produced by a function inside of the app
+    Dynamic,
+    /// Fixed set of values with optional descriptions
+    Values(&'static [(&'static str, Option<&'static str>)]),
+}
+
 /// Parser that inserts static shell completion into bpaf's dynamic shell completion
 #[cfg(feature = "autocomplete")]
 pub struct ParseCompShell<P> {
@@ -89,7 +108,7 @@ where
     }
 
     fn meta(&self) -> Meta {
-        self.inner.meta()
+        Meta::Complete(Box::new(self.inner.meta()), CompHint::Shell(self.op))
     }
 }
 
@@ -146,19 +165,19 @@ pub(crate) fn render_zsh(
     Ok(res)
 }
 
+// Bash is strange when it comes to completion - rather than taking
+// a glob - _filedir takes an extension which it later to include uppercase
+// version as well and to include "*." in front. For compatibility with
+// zsh and other shells - this code strips "*." from the beginning....
+pub(crate) fn bashmask(i: &str) -> &str {
+    i.strip_prefix("*.").unwrap_or(i)
+}
+
 pub(crate) fn render_bash(
     items: &[ShowComp],
     ops: &[ShellComp],
     full_lit: &str,
 ) -> Result<String, std::fmt::Error> {
-    // Bash is strange when it comes to completion - rather than taking
-    // a glob - _filedir takes an extension which it later to include uppercase
-    // version as well and to include "*." in front. For compatibility with
-    // zsh and other shells - this code strips "*." from the beginning....
-    fn bashmask(i: &str) -> &str {
-        i.strip_prefix("*.").unwrap_or(i)
-    }
-
     use std::fmt::Write;
     let mut res = String::new();
 
@@ -268,6 +287,134 @@ pub(crate) fn render_fish(
     Ok(res)
 }
 
+pub(crate) fn render_powershell(
+    items: &[ShowComp],
+    ops: &[ShellComp],
+    full_lit: &str,
+) -> Result<String, std::fmt::Error> {
+    // single quoted strings in PowerShell only need to escape single quotes
+    fn quote(s: &str) -> String {
+        format!("'{}'", s.replace('\'', "''"))
+    }
+
+    fn result(res: &mut String, subst: &str, pretty: &str, tooltip: &str) -> std::fmt::Result {
+        use std::fmt::Write;
+        let ty = if subst.starts_with('-') {
+            "ParameterName"
+        } else {
+            "ParameterValue"
+        };
+        // none of the fields are allowed to be empty
+        writeln!(
+            res,
+            "[System.Management.Automation.CompletionResult]::new({}, {}, '{}', {})",
+            quote(subst),
+            quote(if pretty.is_empty() { subst } else { pretty }),
+            ty,
+            quote(if tooltip.is_empty() { pretty } else { tooltip }),
+        )
+    }
+
+    use std::fmt::Write;
+    let mut res = String::new();
+
+    if items.is_empty() && ops.is_empty() {
+        if !full_lit.is_empty() {
+            result(&mut res, full_lit, full_lit, full_lit)?;
+        }
+        return Ok(res);
+    }
+
+    for op in ops {
+        match op {
+            ShellComp::File { mask } => {
+                write!(res, "Get-ChildItem -Path \"$wordToComplete*\"")?;
+                if let Some(mask) = mask {
+                    write!(
+                        res,
+                        " | Where-Object {{ $_.PSIsContainer -or $_.Name -like {} }}",
+                        quote(mask)
+                    )?;
+                }
+                writeln!(res, " | ForEach-Object {{ [System.Management.Automation.CompletionResult]::new((Resolve-Path -Relative $_.FullName), $_.Name, 'ProviderItem', $_.Name) }}")
+            }
+            ShellComp::Dir { mask } => {
+                write!(res, "Get-ChildItem -Directory -Path \"$wordToComplete*\"")?;
+                if let Some(mask) = mask {
+                    write!(res, " -Filter {}", quote(mask))?;
+                }
+                writeln!(res, " | ForEach-Object {{ [System.Management.Automation.CompletionResult]::new((Resolve-Path -Relative $_.FullName), $_.Name, 'ProviderContainer', $_.Name) }}")
+            }
+            ShellComp::Raw { pwsh, .. } => writeln!(res, "{}", pwsh),
+            ShellComp::Nothing => Ok(()),
+        }?;
+    }
+
+    // PowerShell inserts whatever is selected and can't show a hint without a value,
+    // placeholders like `<FILE>` are skipped
+    for item in items.iter().filter(|i| !i.subst.is_empty()) {
+        let tooltip = item.extra.help.as_deref().unwrap_or("");
+        result(&mut res, &item.subst, &item.pretty, tooltip)?;
+    }
+    Ok(res)
+}
+
+pub(crate) fn render_nushell(
+    items: &[ShowComp],
+    ops: &[ShellComp],
+    full_lit: &str,
+) -> Result<String, std::fmt::Error> {
+    use crate::json::write_str;
+
+    fn record(res: &mut String, value: &str, description: Option<&str>, group: Option<&str>) {
+        res.push_str("{\"value\": ");
+        write_str(res, value);
+        if let Some(description) = description {
+            res.push_str(", \"description\": ");
+            write_str(res, description);
+        }
+        if let Some(group) = group {
+            res.push_str(", \"group\": ");
+            write_str(res, group);
+        }
+        res.push('}');
+    }
+
+    // nushell can't show a hint without a value, placeholders like `<FILE>` are skipped
+    let items = items
+        .iter()
+        .filter(|i| !i.subst.is_empty())
+        .collect::<Vec<_>>();
+
+    // `null` makes nushell fall back to its own file completion
+    if items.is_empty()
+        && ops
+            .iter()
+            .any(|op| matches!(op, ShellComp::File { .. } | ShellComp::Dir { .. }))
+    {
+        return Ok("null\n".to_owned());
+    }
+
+    let mut res = String::from("[");
+    if items.is_empty() && ops.is_empty() {
+        record(&mut res, full_lit, None, None);
+    }
+    for (ix, item) in items.iter().enumerate() {
+        if ix > 0 {
+            res.push(',');
+        }
+        res.push('\n');
+        record(
+            &mut res,
+            &item.subst,
+            item.extra.help.as_deref(),
+            item.extra.group.as_deref(),
+        );
+    }
+    res.push_str("]\n");
+    Ok(res)
+}
+
 pub(crate) fn render_simple(items: &[ShowComp]) -> Result<String, std::fmt::Error> {
     use std::fmt::Write;
     let mut res = String::new();
@@ -0,0 +1,912 @@
+//! Static completion scripts generated from [`Meta`], see [`render_completion_script`]
+//!
+//! Unlike scripts produced by `--bpaf-complete-style-*` static scripts contain all the commands,
+//! flags and arguments known at the time of generation and only call the app to get values
+//! for items with [`complete`](crate::Parser::complete) attached.
+//!
+//! [`render_completion_script`]: OptionParser::render_completion_script
+
+use std::fmt::Write;
+
+use crate::{
+    complete_shell::{bashmask, CompHint, ShellComp},
+    info::Info,
+    item::Item,
+    Doc, Meta, OptionParser, Parser,
+};
+
+/// Shell to generate a static completion script for
+///
+/// See [`render_completion_script`](OptionParser::render_completion_script)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Shell {
+    /// bash, requires `bash-completion` package
+    Bash,
+    /// zsh, script should be placed in a directory listed in `$fpath` as `_app`
+    Zsh,
+    /// fish, script should be placed in `~/.config/fish/completions` as `app.fish`
+    Fish,
+}
+
+impl Shell {
+    /// Script that asks the app for completions every time, same as `--bpaf-complete-style-*`
+    ///
+    /// Unlike a [static script](OptionParser::render_completion_script) it never goes out of
+    /// date, but requires the app to be in `$PATH`. `app` is the name of the binary as users
+    /// invoke it.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let script = Shell::Bash.dynamic_script("app");
+    /// assert!(script.ends_with("complete -o nosort -F _bpaf_dynamic_completion app\n"));
+    /// ```
+    #[must_use]
+    pub fn dynamic_script(self, app: &str) -> String {
+        let mut script = match self {
+            Shell::Bash => crate::complete_run::bash_completer(app),
+            Shell::Zsh => crate::complete_run::zsh_completer(app),
+            Shell::Fish => crate::complete_run::fish_completer(app),
+        };
+        script.push('\n');
+        script
+    }
+}
+
+impl<T> OptionParser<T> {
+    /// Render a static shell completion script for the app
+    ///
+    /// Generated script covers all the commands, flags, arguments and
+    /// [`complete_shell`](crate::Parser::complete_shell) hints without running the app, only
+    /// items with [`complete`](crate::Parser::complete) call the app to get completion values.
+    /// `app` is the name of the binary as users invoke it.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let verbose = short('v').long("verbose").help("Produce more output").switch();
+    /// let file = positional::<std::path::PathBuf>("FILE")
+    ///     .complete_shell(ShellComp::File { mask: None });
+    /// let parser = construct!(verbose, file).to_options();
+    ///
+    /// let script = parser.render_completion_script("app", Shell::Bash);
+    /// assert!(script.contains("complete -F _app app"));
+    /// ```
+    #[must_use]
+    pub fn render_completion_script(&self, app: impl Into<String>, shell: Shell) -> String {
+        let app = app.into();
+        let mut specs = Vec::new();
+        collect_specs(&self.inner.meta(), &self.info, String::new(), &mut specs);
+        match shell {
+            Shell::Bash => render_bash(&specs, &app),
+            Shell::Zsh => render_zsh(&specs, &app),
+            Shell::Fish => render_fish(&specs, &app),
+        }
+        .unwrap()
+    }
+}
+
+/// Everything known about the app itself or one of its subcommands
+#[derive(Debug, Default)]
+struct Spec {
+    /// Names of commands leading to this one separated by spaces, empty for the app itself
+    path: String,
+    named: Vec<Named>,
+    positionals: Vec<Value>,
+    commands: Vec<Command>,
+}
+
+#[derive(Debug)]
+struct Named {
+    short: Option<char>,
+    long: Option<String>,
+    help: Option<String>,
+    /// Arguments take a value, flags don't
+    value: Option<Value>,
+    /// Item can be used more than once
+    many: bool,
+    /// Names of items from alternative branches, they can't be used together with this one
+    conflicts: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Value {
+    metavar: &'static str,
+    hint: Option<CompHint>,
+    many: bool,
+}
+
+#[derive(Debug)]
+struct Command {
+    name: &'static str,
+    short: Option<char>,
+    help: Option<String>,
+}
+
+fn collect_specs(meta: &Meta, info: &Info, path: String, specs: &mut Vec<Spec>) {
+    let mut spec = Spec {
+        path,
+        ..Spec::default()
+    };
+    let mut nested = Vec::new();
+    spec.collect(meta, None, false, &mut nested);
+    spec.collect(&info.meta(), None, false, &mut Vec::new());
+    let path = spec.path.clone();
+    specs.push(spec);
+
+    for (name, meta, info) in nested {
+        let path = if path.is_empty() {
+            name.to_owned()
+        } else {
+            format!("{} {}", path, name)
+        };
+        collect_specs(&meta, &info, path, specs);
+    }
+}
+
+impl Spec {
+    fn collect(
+        &mut self,
+        meta: &Meta,
+        hint: Option<CompHint>,
+        many: bool,
+        nested: &mut Vec<(&'static str, Meta, Info)>,
+    ) {
+        match meta {
+            Meta::And(xs) => {
+                for x in xs {
+                    self.collect(x, hint, many, nested);
+                }
+            }
+            Meta::Or(xs) => {
+                // names from each branch, items from different branches can't be used together
+                let groups = xs
+                    .iter()
+                    .map(|x| {
+                        let mut branch = Spec::default();
+                        branch.collect(x, hint, many, &mut Vec::new());
+                        branch
+                            .named
+                            .iter()
+                            .flat_map(Named::names)
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>();
+                for x in xs {
+                    self.collect(x, hint, many, nested);
+                }
+                for (ix, group) in groups.iter().enumerate() {
+                    let others = groups
+                        .iter()
+                        .enumerate()
+                        .filter(|(other, _)| *other != ix)
+                        .flat_map(|(_, names)| names)
+                        .filter(|name| !group.contains(name))
+                        .collect::<Vec<_>>();
+                    for named in &mut self.named {
+                        if named.names().iter().any(|name| group.contains(name)) {
+                            for name in &others {
+                                if !named.conflicts.contains(name) {
+                                    named.conflicts.push((*name).clone());
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            Meta::Optional(m)
+            | Meta::Required(m)
+            | Meta::Adjacent(m)
+            | Meta::Subsection(m, _)
+            | Meta::Suffix(m, _)
+            | Meta::CustomUsage(m, _)
+            | Meta::Strict(m) => self.collect(m, hint, many, nested),
+            Meta::Many(m) => self.collect(m, hint, true, nested),
+            Meta::Complete(m, hint) => self.collect(m, Some(*hint), many, nested),
+            Meta::Skip | Meta::Hidden(_) => {}
+            Meta::Item(item) => match item.as_ref() {
+                // there's no way to tell what `any` is going to accept
+                Item::Any { .. } => {}
+                Item::Positional { metavar, .. } => self.positionals.push(Value {
+                    metavar: metavar.0,
+                    hint,
+                    many,
+                }),
+                Item::Command {
+                    name,
+                    short,
+                    help,
+                    meta,
+                    info,
+                    deprecated,
+                } => {
+                    if *deprecated || self.commands.iter().any(|c| c.name == *name) {
+                        return;
+                    }
+                    self.commands.push(Command {
+                        name,
+                        short: *short,
+                        help: help.as_ref().and_then(Doc::to_completion),
+                    });
+                    nested.push((name, meta.as_ref().clone(), info.as_ref().clone()));
+                }
+                // deprecated items are still accepted but not offered
+                Item::Flag {
+                    deprecated: true, ..
+                }
+                | Item::Argument {
+                    deprecated: true, ..
+                } => {}
+                Item::Flag {
+                    name,
+                    help,
+                    negatable,
+                    ..
+                } => {
+                    let help = help.as_ref().and_then(Doc::to_completion);
+                    self.push_named(Named {
+                        short: name.as_short(),
+                        long: name.as_long().map(str::to_owned),
+                        help: help.clone(),
+                        value: None,
+                        many,
+                        conflicts: Vec::new(),
+                    });
+                    if let Some(long) = name.as_long().filter(|_| *negatable) {
+                        self.push_named(Named {
+                            short: None,
+                            long: Some(format!("no-{}", long)),
+                            help,
+                            value: None,
+                            many,
+                            conflicts: Vec::new(),
+                        });
+                    }
+                }
+                Item::Argument {
+                    name,
+                    metavar,
+                    help,
+                    opt_value,
+                    ..
+                } => self.push_named(Named {
+                    short: name.as_short(),
+                    long: name.as_long().map(str::to_owned),
+                    help: help.as_ref().and_then(Doc::to_completion),
+                    // optional value must be attached, next word is not a value
+                    value: if *opt_value {
+                        None
+                    } else {
+                        Some(Value {
+                            metavar: metavar.0,
+                            hint,
+                            many,
+                        })
+                    },
+                    many,
+                    conflicts: Vec::new(),
+                }),
+            },
+        }
+    }
+
+    /// Same name can be used in several alternative branches, keep only the first one
+    fn push_named(&mut self, named: Named) {
+        if !self
+            .named
+            .iter()
+            .any(|n| n.short == named.short && n.long == named.long)
+        {
+            self.named.push(named);
+        }
+    }
+
+    /// Named items that take a value
+    fn arguments(&self) -> impl Iterator<Item = (&Named, Value)> {
+        self.named.iter().filter_map(|n| Some((n, n.value?)))
+    }
+}
+
+impl Named {
+    /// All the names of an item with dashes
+    fn names(&self) -> Vec<String> {
+        let mut res = Vec::new();
+        if let Some(long) = &self.long {
+            res.push(format!("--{}", long));
+        }
+        if let Some(short) = self.short {
+            res.push(format!("-{}", short));
+        }
+        res
+    }
+}
+
+impl Command {
+    fn names(&self) -> Vec<String> {
+        let mut res = vec![self.name.to_owned()];
+        res.extend(self.short.map(|s| s.to_string()));
+        res
+    }
+}
+
+/// Case pattern matching any of the words for a command at `path`: `"path:a"|"path:b"`
+fn case_pattern(path: &str, words: &[String]) -> String {
+    words
+        .iter()
+        .map(|w| format!("\"{}:{}\"", path, w))
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+/// Body of a `case "$state:$word"` statement used by bash and zsh scripts to figure out
+/// which command is being completed and how many positional items it already has
+fn write_state_cases(res: &mut String, specs: &[Spec], indent: &str) -> std::fmt::Result {
+    for spec in specs {
+        for cmd in &spec.commands {
+            let path = if spec.path.is_empty() {
+                cmd.name.to_owned()
+            } else {
+                format!("{} {}", spec.path, cmd.name)
+            };
+            writeln!(
+                res,
+                "{}{}) state=\"{}\"; npos=0 ;;",
+                indent,
+                case_pattern(&spec.path, &cmd.names()),
+                path
+            )?;
+        }
+        for (named, _) in spec.arguments() {
+            writeln!(
+                res,
+                "{}{}) i=$((i + 1)) ;;",
+                indent,
+                case_pattern(&spec.path, &named.names())
+            )?;
+        }
+    }
+    writeln!(res, "{}*:-*) ;;", indent)?;
+    writeln!(res, "{}*) npos=$((npos + 1)) ;;", indent)?;
+    Ok(())
+}
+
+/// Case pattern for a positional item number `ix`, last positional with `many` takes the rest
+fn positional_pattern(path: &str, ix: usize, value: &Value) -> String {
+    if value.many {
+        format!("\"{}:\"*", path)
+    } else {
+        format!("\"{}:{}\"", path, ix)
+    }
+}
+
+/// Check if any of the values need to ask the app for completions
+fn has_dynamic(specs: &[Spec]) -> bool {
+    specs
+        .iter()
+        .flat_map(|s| {
+            s.named
+                .iter()
+                .filter_map(|n| n.value)
+                .chain(s.positionals.iter().copied())
+        })
+        .any(|v| matches!(v.hint, Some(CompHint::Dynamic)))
+}
+
+/// Quote a string for bash, zsh or fish by wrapping it in single quotes
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+/// Turn app name into something usable as a shell function name
+fn func_name(app: &str) -> String {
+    app.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn bash_hint(value: &Value) -> String {
+    match value.hint {
+        None | Some(CompHint::Shell(ShellComp::Nothing)) => String::new(),
+        Some(CompHint::Shell(ShellComp::File { mask: None })) => "_filedir".to_owned(),
+        Some(CompHint::Shell(ShellComp::File { mask: Some(mask) })) => {
+            format!("_filedir '{}'", bashmask(mask))
+        }
+        Some(CompHint::Shell(ShellComp::Dir { mask: None })) => "_filedir -d".to_owned(),
+        Some(CompHint::Shell(ShellComp::Dir { mask: Some(mask) })) => {
+            format!("_filedir -d '{}'", bashmask(mask))
+        }
+        Some(CompHint::Shell(ShellComp::Raw { bash, .. })) => bash.to_owned(),
+        Some(CompHint::Dynamic) => {
+            "source <( \"${words[0]}\" --bpaf-complete-rev=8 \"${words[@]:1}\" )".to_owned()
+        }
+        Some(CompHint::Values(values)) => {
+            let names = values.iter().map(|v| v.0).collect::<Vec<_>>();
+            format!(
+                "COMPREPLY+=( $(compgen -W {} -- \"$cur\") )",
+                quote(&names.join(" "))
+            )
+        }
+    }
+}
+
+fn render_bash(specs: &[Spec], app: &str) -> Result<String, std::fmt::Error> {
+    let mut res = String::new();
+    let func = func_name(app);
+    writeln!(res, "_{}() {{", func)?;
+    writeln!(res, "    local cur prev words cword split")?;
+    writeln!(res, "    _init_completion -s || return")?;
+    writeln!(res)?;
+    writeln!(res, "    local state=\"\" npos=0 i=1")?;
+    writeln!(res, "    while [ \"$i\" -lt \"$cword\" ]; do")?;
+    writeln!(res, "        case \"$state:${{words[i]}}\" in")?;
+    write_state_cases(&mut res, specs, "            ")?;
+    writeln!(res, "        esac")?;
+    writeln!(res, "        i=$((i + 1))")?;
+    writeln!(res, "    done")?;
+    writeln!(res)?;
+
+    writeln!(res, "    case \"$state:$prev\" in")?;
+    for spec in specs {
+        for (named, value) in spec.arguments() {
+            let hint = bash_hint(&value);
+            let sep = if hint.is_empty() { "" } else { "; " };
+            writeln!(
+                res,
+                "        {}) {}{}return ;;",
+                case_pattern(&spec.path, &named.names()),
+                hint,
+                sep
+            )?;
+        }
+    }
+    writeln!(res, "    esac")?;
+    writeln!(res)?;
+
+    writeln!(res, "    local opts=\"\"")?;
+    writeln!(res, "    if [[ \"$cur\" == -* ]]; then")?;
+    writeln!(res, "        case \"$state\" in")?;
+    for spec in specs {
+        let names = spec.named.iter().flat_map(Named::names).collect::<Vec<_>>();
+        if !names.is_empty() {
+            writeln!(
+                res,
+                "            \"{}\") opts=\"{}\" ;;",
+                spec.path,
+                names.join(" ")
+            )?;
+        }
+    }
+    writeln!(res, "        esac")?;
+    writeln!(res, "    else")?;
+    writeln!(res, "        case \"$state\" in")?;
+    for spec in specs.iter().filter(|s| !s.commands.is_empty()) {
+        let names = spec.commands.iter().map(|c| c.name).collect::<Vec<_>>();
+        writeln!(
+            res,
+            "            \"{}\") opts=\"{}\" ;;",
+            spec.path,
+            names.join(" ")
+        )?;
+    }
+    writeln!(res, "        esac")?;
+    writeln!(res, "        case \"$state:$npos\" in")?;
+    for spec in specs {
+        for (ix, value) in spec.positionals.iter().enumerate() {
+            let hint = bash_hint(value);
+            if !hint.is_empty() {
+                writeln!(
+                    res,
+                    "            {}) {} ;;",
+                    positional_pattern(&spec.path, ix, value),
+                    hint
+                )?;
+            }
+        }
+    }
+    writeln!(res, "        esac")?;
+    writeln!(res, "    fi")?;
+    writeln!(
+        res,
+        "    COMPREPLY+=( $(compgen -W \"$opts\" -- \"$cur\") )"
+    )?;
+    writeln!(res, "}}")?;
+    writeln!(res, "complete -F _{} {}", func, app)?;
+    Ok(res)
+}
+
+/// Command to complete a value, used for positional items next to commands
+fn zsh_hint(value: &Value) -> String {
+    match value.hint {
+        None => format!("_message {}", quote(value.metavar)),
+        Some(CompHint::Shell(ShellComp::Nothing)) => String::new(),
+        Some(CompHint::Shell(ShellComp::File { mask: None })) => "_files".to_owned(),
+        Some(CompHint::Shell(ShellComp::File { mask: Some(mask) })) => {
+            format!("_files -g '{}'", mask)
+        }
+        Some(CompHint::Shell(ShellComp::Dir { mask: None })) => "_files -/".to_owned(),
+        Some(CompHint::Shell(ShellComp::Dir { mask: Some(mask) })) => {
+            format!("_files -/ -g '{}'", mask)
+        }
+        Some(CompHint::Shell(ShellComp::Raw { zsh, .. })) => zsh.to_owned(),
+        Some(CompHint::Dynamic) => ZSH_DYNAMIC.to_owned(),
+        Some(CompHint::Values(values)) => {
+            let entries = values
+                .iter()
+                .map(|(name, help)| zsh_describe(name, *help))
+                .collect::<Vec<_>>();
+            format!(
+                "local -a values; values=({}); _describe -t values {} values",
+                entries.join(" "),
+                quote(value.metavar)
+            )
+        }
+    }
+}
+
+/// Ask the app for values, `bpaf_words` is a copy of the command line made by the top level
+/// function since nested ones only see words that belong to their command
+const ZSH_DYNAMIC: &str =
+    "source <( \"${bpaf_words[1]}\" --bpaf-complete-rev=7 \"${bpaf_words[@]:1}\" )";
+
+/// Action part of an `_arguments` spec
+fn zsh_action(value: &Value) -> String {
+    match value.hint {
+        // a space means no completion, only the message
+        None | Some(CompHint::Shell(ShellComp::Nothing)) => " ".to_owned(),
+        Some(CompHint::Shell(ShellComp::File { mask: None })) => "_files".to_owned(),
+        Some(CompHint::Shell(ShellComp::File { mask: Some(mask) })) => {
+            format!("_files -g \"{}\"", mask)
+        }
+        Some(CompHint::Shell(ShellComp::Dir { mask: None })) => "_files -/".to_owned(),
+        Some(CompHint::Shell(ShellComp::Dir { mask: Some(mask) })) => {
+            format!("_files -/ -g \"{}\"", mask)
+        }
+        Some(CompHint::Shell(ShellComp::Raw { zsh, .. })) => {
+            format!("{{{}}}", zsh)
+        }
+        Some(CompHint::Dynamic) => format!("{{{}}}", ZSH_DYNAMIC),
+        Some(CompHint::Values(values)) => {
+            let entries = values
+                .iter()
+                .map(|(name, help)| {
+                    let name = zsh_escape(name, "\\:() \t");
+                    match help {
+                        Some(help) => format!("{}\\:{}", name, zsh_escape(help, "\\() \t")),
+                        None => name,
+                    }
+                })
+                .collect::<Vec<_>>();
+            format!("(({}))", entries.join(" "))
+        }
+    }
+}
+
+/// Put a backslash in front of any of the `special` characters
+fn zsh_escape(s: &str, special: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    for c in s.chars() {
+        if special.contains(c) {
+            res.push('\\');
+        }
+        res.push(c);
+    }
+    res
+}
+
+/// Description part of an `_arguments` spec: `[help]`
+fn zsh_help(help: Option<&str>) -> String {
+    match help {
+        Some(help) => format!("[{}]", zsh_escape(help, "\\[]")),
+        None => String::new(),
+    }
+}
+
+/// Message part of an `_arguments` spec, colons separate parts of the spec
+fn zsh_message(metavar: &str) -> String {
+    if metavar.is_empty() {
+        " ".to_owned()
+    } else {
+        zsh_escape(metavar, "\\:")
+    }
+}
+
+/// `_arguments` specs for all the names of a named item: `'(-v --verbose)--verbose[help]'`
+///
+/// Item excludes items from alternative branches and itself unless it can be used many times
+fn zsh_named_specs(named: &Named) -> Vec<String> {
+    let names = named.names();
+    let mut exclude = Vec::new();
+    if !named.many {
+        exclude.extend(names.iter().cloned());
+    }
+    exclude.extend(named.conflicts.iter().cloned());
+    let exclude = if exclude.is_empty() {
+        String::new()
+    } else {
+        format!("({})", exclude.join(" "))
+    };
+    let star = if named.many { "*" } else { "" };
+    let help = zsh_help(named.help.as_deref());
+
+    names
+        .iter()
+        .map(|name| {
+            let mut spec = format!("{}{}{}", exclude, star, name);
+            if let Some(value) = &named.value {
+                // value can be attached or go into the next word
+                spec.push_str(if name.starts_with("--") { "=" } else { "+" });
+                spec.push_str(&help);
+                spec.push(':');
+                spec.push_str(&zsh_message(value.metavar));
+                spec.push(':');
+                spec.push_str(&zsh_action(value));
+            } else {
+                spec.push_str(&help);
+            }
+            quote(&spec)
+        })
+        .collect()
+}
+
+/// Entry for zsh `_describe`: `'name:help'` with colons in name escaped
+fn zsh_describe(name: &str, help: Option<&str>) -> String {
+    let name = name.replace(':', "\\:");
+    match help {
+        Some(help) => quote(&format!("{}:{}", name, help)),
+        None => quote(&name),
+    }
+}
+
+/// Name of the zsh function that completes command at `path`: `_app__remote__add`
+fn zsh_func(func: &str, path: &str) -> String {
+    let mut res = format!("_{}", func);
+    for name in path.split(' ').filter(|n| !n.is_empty()) {
+        res.push_str("__");
+        res.push_str(&func_name(name));
+    }
+    res
+}
+
+fn render_zsh_function(
+    res: &mut String,
+    spec: &Spec,
+    func: &str,
+    app: &str,
+    dynamic: bool,
+) -> std::fmt::Result {
+    let mut args = spec
+        .named
+        .iter()
+        .flat_map(zsh_named_specs)
+        .collect::<Vec<_>>();
+    if spec.commands.is_empty() {
+        for value in &spec.positionals {
+            let pos = if value.many { "*" } else { "" };
+            let arg = format!(
+                "{}:{}:{}",
+                pos,
+                zsh_message(value.metavar),
+                zsh_action(value)
+            );
+            args.push(quote(&arg));
+        }
+    } else {
+        args.push(quote(": :->command"));
+        args.push(quote("*:: :->args"));
+    }
+
+    writeln!(res, "{}() {{", zsh_func(func, &spec.path))?;
+    writeln!(res, "    local curcontext=\"$curcontext\" state line")?;
+    writeln!(res, "    typeset -A opt_args")?;
+    if dynamic && spec.path.is_empty() {
+        writeln!(res, "    local -a bpaf_words")?;
+        writeln!(res, "    bpaf_words=(\"${{words[@]}}\")")?;
+    }
+    write!(res, "    _arguments -s -S -C")?;
+    for arg in &args {
+        write!(res, " \\\n        {}", arg)?;
+    }
+    writeln!(res)?;
+
+    if !spec.commands.is_empty() {
+        let entries = spec
+            .commands
+            .iter()
+            .map(|c| zsh_describe(c.name, c.help.as_deref()))
+            .collect::<Vec<_>>();
+        writeln!(res, "    case $state in")?;
+        writeln!(res, "        command)")?;
+        writeln!(res, "            local -a commands")?;
+        writeln!(res, "            commands=({})", entries.join(" "))?;
+        writeln!(res, "            _describe -t commands 'command' commands")?;
+        // positional item can be used instead of a command
+        if let Some(hint) = spec.positionals.first().map(zsh_hint) {
+            if !hint.is_empty() {
+                writeln!(res, "            {}", hint)?;
+            }
+        }
+        writeln!(res, "            ;;")?;
+        writeln!(res, "        args)")?;
+        let context = if spec.path.is_empty() {
+            app.to_owned()
+        } else {
+            format!("{}-{}", app, spec.path.replace(' ', "-"))
+        };
+        writeln!(
+            res,
+            "            curcontext=\"${{curcontext%:*:*}}:{}-$line[1]:\"",
+            context
+        )?;
+        writeln!(res, "            case $line[1] in")?;
+        for cmd in &spec.commands {
+            let path = if spec.path.is_empty() {
+                cmd.name.to_owned()
+            } else {
+                format!("{} {}", spec.path, cmd.name)
+            };
+            writeln!(
+                res,
+                "                {}) {} ;;",
+                cmd.names().join("|"),
+                zsh_func(func, &path)
+            )?;
+        }
+        writeln!(res, "            esac")?;
+        writeln!(res, "            ;;")?;
+        writeln!(res, "    esac")?;
+    }
+    writeln!(res, "}}")?;
+    Ok(())
+}
+
+fn render_zsh(specs: &[Spec], app: &str) -> Result<String, std::fmt::Error> {
+    let mut res = String::new();
+    let func = func_name(app);
+    let dynamic = has_dynamic(specs);
+
+    writeln!(res, "#compdef {}", app)?;
+    for spec in specs {
+        writeln!(res)?;
+        render_zsh_function(&mut res, spec, &func, app, dynamic)?;
+    }
+    writeln!(res)?;
+    writeln!(res, "if [ \"$funcstack[1]\" = \"_{}\" ]; then", func)?;
+    writeln!(res, "    _{} \"$@\"", func)?;
+    writeln!(res, "else")?;
+    writeln!(res, "    compdef _{} {}", func, app)?;
+    writeln!(res, "fi")?;
+    Ok(res)
+}
+
+/// Extra arguments for fish `complete` to complete a value
+fn fish_hint(value: &Value, func: &str) -> String {
+    match value.hint {
+        None | Some(CompHint::Shell(ShellComp::Nothing)) => String::new(),
+        Some(CompHint::Shell(ShellComp::File { mask: None })) => " -F".to_owned(),
+        Some(CompHint::Shell(ShellComp::File { mask: Some(mask) })) => format!(
+            " -a {}",
+            quote(&format!(
+                "(__fish_complete_suffix {})",
+                mask.trim_start_matches('*')
+            ))
+        ),
+        Some(CompHint::Shell(ShellComp::Dir { .. })) => {
+            " -a '(__fish_complete_directories)'".to_owned()
+        }
+        Some(CompHint::Shell(ShellComp::Raw { fish, .. })) => {
+            format!(" {}", fish)
+        }
+        Some(CompHint::Dynamic) => format!(" -a '(__{}_dynamic)'", func),
+        Some(CompHint::Values(values)) => {
+            // fish takes descriptions for values after a tab
+            let mut printf = String::from("(printf \"%s\\t%s\\n\"");
+            for (name, help) in values {
+                for s in [name, help.unwrap_or_default()] {
+                    printf.push_str(" \"");
+                    for c in s.chars() {
+                        if matches!(c, '\\' | '"' | '$') {
+                            printf.push('\\');
+                        }
+                        printf.push(c);
+                    }
+                    printf.push('"');
+                }
+            }
+            printf.push(')');
+            format!(" -a {}", quote(&printf))
+        }
+    }
+}
+
+/// Condition for fish `complete -n` that holds when command at `spec` is being completed
+///
+/// Each command leading to `spec` must be present on the command line and none of the
+/// commands `spec` itself has, for the app itself without commands there's no condition
+fn fish_condition(specs: &[Spec], spec: &Spec) -> Option<String> {
+    let mut parts = Vec::new();
+    let mut path = String::new();
+    for name in spec.path.split(' ').filter(|n| !n.is_empty()) {
+        let parent = specs.iter().find(|s| s.path == path)?;
+        let cmd = parent.commands.iter().find(|c| c.name == name)?;
+        parts.push(format!(
+            "__fish_seen_subcommand_from {}",
+            cmd.names().join(" ")
+        ));
+        if !path.is_empty() {
+            path.push(' ');
+        }
+        path.push_str(name);
+    }
+    if !spec.commands.is_empty() {
+        let names = spec
+            .commands
+            .iter()
+            .flat_map(Command::names)
+            .collect::<Vec<_>>();
+        parts.push(format!(
+            "not __fish_seen_subcommand_from {}",
+            names.join(" ")
+        ));
+    }
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join("; and "))
+    }
+}
+
+fn render_fish(specs: &[Spec], app: &str) -> Result<String, std::fmt::Error> {
+    let mut res = String::new();
+    let func = func_name(app);
+
+    if has_dynamic(specs) {
+        writeln!(res, "function __{}_dynamic", func)?;
+        writeln!(
+            res,
+            "    set -l tokens (commandline -opc) (commandline -ct)"
+        )?;
+        writeln!(res, "    $tokens[1] --bpaf-complete-rev=1 $tokens[2..-1]")?;
+        writeln!(res, "end")?;
+        writeln!(res)?;
+    }
+
+    writeln!(res, "complete -c {} -f", app)?;
+    for spec in specs {
+        let prefix = match fish_condition(specs, spec) {
+            Some(cond) => format!("complete -c {} -n {}", app, quote(&cond)),
+            None => format!("complete -c {}", app),
+        };
+        for cmd in &spec.commands {
+            write!(res, "{} -a {}", prefix, cmd.name)?;
+            if let Some(help) = &cmd.help {
+                write!(res, " -d {}", quote(help))?;
+            }
+            writeln!(res)?;
+        }
+        for named in &spec.named {
+            res.push_str(&prefix);
+            if let Some(short) = named.short {
+                write!(res, " -s {}", short)?;
+            }
+            if let Some(long) = &named.long {
+                write!(res, " -l {}", long)?;
+            }
+            if let Some(value) = &named.value {
+                write!(res, " -r{}", fish_hint(value, &func))?;
+            }
+            if let Some(help) = &named.help {
+                write!(res, " -d {}", quote(help))?;
+            }
+            writeln!(res)?;
+        }
+        // fish can't tell positional items apart, all of them get the same completions
+        let mut hints = Vec::new();
+        for value in &spec.positionals {
+            let hint = fish_hint(value, &func);
+            if !hint.is_empty() && !hints.contains(&hint) {
+                writeln!(res, "{}{}", prefix, hint)?;
+                hints.push(hint);
+            }
+        }
+    }
+    Ok(res)
+}
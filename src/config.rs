@@ -0,0 +1,93 @@
+//! Layered configuration sources, see [`ConfigSource`]
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    rc::Rc,
+};
+
+/// A source of values for named items that weren't given on a command line
+///
+/// Attach it to a parser with [`OptionParser::config_source`](crate::OptionParser::config_source).
+/// Once attached every [`argument`](crate::parsers::NamedArg::argument),
+/// [`switch`](crate::parsers::NamedArg::switch) and [`flag`](crate::parsers::NamedArg::flag)
+/// resolves its value in this order: command line, environment variable (see
+/// [`env`](crate::parsers::NamedArg::env)), config source, fallback value.
+///
+/// Values are looked up by the first long name of an item, items without a long name are
+/// never looked up. For flags and switches value `""`, `"0"`, `"false"`, `"no"` and `"off"`
+/// mean "not present", anything else means "present".
+///
+/// `bpaf` doesn't read or parse files by itself, implement this trait for your favorite config
+/// format or use one of the provided implementations: closures, [`HashMap`] and [`BTreeMap`].
+///
+/// ```rust
+/// # use bpaf::*;
+/// # use std::collections::HashMap;
+/// let mut config = HashMap::new();
+/// config.insert("threads".to_owned(), "4".to_owned());
+///
+/// let parser = long("threads")
+///     .argument::<usize>("N")
+///     .fallback(1)
+///     .to_options()
+///     .config_source(config);
+///
+/// // value from the command line wins
+/// let r = parser.run_inner(&["--threads", "8"]).unwrap();
+/// assert_eq!(r, 8);
+///
+/// // config source is used when value is missing on the command line
+/// let r = parser.run_inner(&[]).unwrap();
+/// assert_eq!(r, 4);
+/// ```
+pub trait ConfigSource {
+    /// Get a value for a given key, key is the first long name of an item
+    fn get(&self, key: &str) -> Option<String>;
+}
+
+impl<F> ConfigSource for F
+where
+    F: Fn(&str) -> Option<String>,
+{
+    fn get(&self, key: &str) -> Option<String> {
+        self(key)
+    }
+}
+
+impl<S: std::hash::BuildHasher> ConfigSource for HashMap<String, String, S> {
+    fn get(&self, key: &str) -> Option<String> {
+        HashMap::get(self, key).cloned()
+    }
+}
+
+impl ConfigSource for BTreeMap<String, String> {
+    fn get(&self, key: &str) -> Option<String> {
+        BTreeMap::get(self, key).cloned()
+    }
+}
+
+/// Shared handle to a user provided [`ConfigSource`]
+#[derive(Clone)]
+pub(crate) struct Config(pub(crate) Rc<dyn ConfigSource>);
+
+impl std::fmt::Debug for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Config")
+    }
+}
+
+impl Config {
+    pub(crate) fn get(&self, key: &str) -> Option<String> {
+        self.0.get(key)
+    }
+
+    /// Interpret config value as a flag
+    pub(crate) fn get_flag(&self, key: &str) -> bool {
+        self.get(key).map_or(false, |val| {
+            !matches!(
+                val.trim().to_ascii_lowercase().as_str(),
+                "" | "0" | "false" | "no" | "off"
+            )
+        })
+    }
+}
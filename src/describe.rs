@@ -0,0 +1,451 @@
+//! Machine readable description of a parser, see [`describe`](crate::OptionParser::describe)
+//!
+//! Description mirrors the structure of the parser: which items must be present together,
+//! which are alternatives, which are optional or can be repeated, and it covers everything
+//! that goes into the help message. It can be used to generate wrappers or documentation
+//! in other formats and can be exported as JSON with [`CliSpec::to_json`].
+
+use crate::{
+    info::Info,
+    item::{Item, ShortLong},
+    json::Object,
+    Doc, Meta,
+};
+
+/// Description of a parser created with [`to_options`](crate::Parser::to_options)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CliSpec {
+    /// Program or command description, see [`descr`](crate::OptionParser::descr)
+    pub descr: Option<String>,
+    /// Text after the usage line, see [`header`](crate::OptionParser::header)
+    pub header: Option<String>,
+    /// Text at the end of the help, see [`footer`](crate::OptionParser::footer)
+    pub footer: Option<String>,
+    /// Custom usage line, see [`usage`](crate::OptionParser::usage)
+    pub usage: Option<String>,
+    /// Program version, see [`version`](crate::OptionParser::version)
+    pub version: Option<String>,
+    /// Help is shown when there are no arguments, see
+    /// [`fallback_to_usage`](crate::OptionParser::fallback_to_usage)
+    pub fallback_to_usage: bool,
+    /// Items the parser consumes
+    pub args: Node,
+    /// Built-in `--help` and, if version is set, `--version` flags
+    pub builtins: Node,
+}
+
+/// A part of the parser, see [`CliSpec`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Node {
+    /// All of the nodes must be present
+    And(Vec<Node>),
+    /// Exactly one of the nodes must be present
+    Or(Vec<Node>),
+    /// Node can be absent
+    Optional(Box<Node>),
+    /// Node can be present multiple times
+    Many(Box<Node>),
+    /// Items inside must be adjacent to each other on the command line
+    Adjacent(Box<Node>),
+    /// Positional items inside must be present after `--`
+    Strict(Box<Node>),
+    /// Node is not shown in help or usage but can still be parsed
+    Hidden(Box<Node>),
+    /// Items shown as a separate group in the help message
+    Group {
+        /// Group header
+        header: String,
+        /// Grouped items
+        inner: Box<Node>,
+    },
+    /// Items with a custom usage string, empty if items are not shown in usage
+    Usage {
+        /// Custom usage
+        usage: String,
+        /// Items
+        inner: Box<Node>,
+    },
+    /// Items followed by extra text in the help message, such as default value
+    Suffix {
+        /// Extra text
+        text: String,
+        /// Items
+        inner: Box<Node>,
+    },
+    /// Items with values offered by shell completion
+    Complete {
+        /// Completion hint
+        hint: Completion,
+        /// Items
+        inner: Box<Node>,
+    },
+    /// Flag or switch: `-v`, `--verbose`
+    Flag(Named),
+    /// Named argument with a value: `--output FILE`
+    Argument(Named),
+    /// Positional item: `FILE`
+    Positional(Positional),
+    /// Item consumed by [`any`](crate::any), can contain anything
+    Any(Positional),
+    /// Subcommand
+    Command(Command),
+    /// Nothing to consume, for example [`pure`](crate::pure)
+    Empty,
+}
+
+/// Values shell completion offers for an item, see [`Node::Complete`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Completion {
+    /// Values are produced by a function inside of the app, see
+    /// [`complete`](crate::Parser::complete)
+    Dynamic,
+    /// File names, optionally limited by a mask
+    File {
+        /// File mask, such as `*.rs`
+        mask: Option<String>,
+    },
+    /// Directory names, optionally limited by a mask
+    Dir {
+        /// Directory mask
+        mask: Option<String>,
+    },
+    /// Values are produced by a shell specific script
+    Raw,
+    /// Shell completion is disabled
+    Nothing,
+    /// Fixed set of values with optional descriptions, see
+    /// [`possible_values`](crate::Parser::possible_values)
+    Values(Vec<(String, Option<String>)>),
+}
+
+/// Flag or named argument
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Named {
+    /// Short name, without the dash
+    pub short: Option<char>,
+    /// Long name, without dashes
+    pub long: Option<String>,
+    /// Environment variables used as a fallback, in order they are checked
+    pub env: Vec<String>,
+    /// Placeholder for the value, only present for arguments
+    pub metavar: Option<String>,
+    /// Help message
+    pub help: Option<String>,
+    /// Item is [`deprecated`](crate::parsers::NamedArg::deprecated)
+    pub deprecated: bool,
+    /// Flag also accepts `--no-` versions of long names, see
+    /// [`negatable`](crate::parsers::NamedArg::negatable)
+    pub negatable: bool,
+    /// Value of the argument can be omitted and can only be attached to the name, see
+    /// [`argument_opt_value`](crate::parsers::NamedArg::argument_opt_value)
+    pub opt_value: bool,
+}
+
+/// Positional item
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Positional {
+    /// Placeholder for the value
+    pub metavar: String,
+    /// Help message
+    pub help: Option<String>,
+}
+
+/// Subcommand
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Command {
+    /// Command name
+    pub name: String,
+    /// Short alias, if any
+    pub short: Option<char>,
+    /// Help message shown in the parent's list of commands
+    pub help: Option<String>,
+    /// Command is [`deprecated`](crate::parsers::ParseCommand::deprecated)
+    pub deprecated: bool,
+    /// Description of the command's own parser
+    pub spec: Box<CliSpec>,
+}
+
+fn text(doc: &Doc) -> String {
+    doc.monochrome(true).trim_end().to_owned()
+}
+
+fn opt_text(doc: &Option<Doc>) -> Option<String> {
+    doc.as_ref().map(text)
+}
+
+impl CliSpec {
+    pub(crate) fn new(info: &Info, meta: &Meta) -> Self {
+        Self {
+            descr: opt_text(&info.descr),
+            header: opt_text(&info.header),
+            footer: opt_text(&info.footer),
+            usage: opt_text(&info.usage),
+            version: opt_text(&info.version),
+            fallback_to_usage: info.help_if_no_args,
+            args: Node::from(meta),
+            builtins: Node::from(&info.help_meta(info.locale.as_ref())),
+        }
+    }
+
+    /// Render description as JSON
+    ///
+    /// Each node is an object with `kind` field: `and`, `or`, `optional`, `many`, `adjacent`,
+    /// `strict`, `hidden`, `group`, `usage`, `suffix`, `complete`, `flag`, `argument`,
+    /// `positional`, `any`, `command` or `empty` and fields of the corresponding variant. Nested
+    /// nodes are in `items` for `and` and `or` and in `item` for the rest, command's own
+    /// description is in `spec`. Completion hint is in `hint`: `dynamic`, `file`, `dir`, `raw`,
+    /// `nothing` or `values`, with `mask` for files and directories and `values` with `name`
+    /// and `help` of each value. Missing values are `null`.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut obj = Object::default();
+        obj.opt_str("descr", self.descr.as_deref());
+        obj.opt_str("header", self.header.as_deref());
+        obj.opt_str("footer", self.footer.as_deref());
+        obj.opt_str("usage", self.usage.as_deref());
+        obj.opt_str("version", self.version.as_deref());
+        obj.bool("fallback_to_usage", self.fallback_to_usage);
+        obj.raw("args", &self.args.to_json());
+        obj.raw("builtins", &self.builtins.to_json());
+        obj.finish()
+    }
+}
+
+impl Node {
+    /// Render node as JSON, see [`CliSpec::to_json`]
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut obj = Object::default();
+        let mut wrapped = |kind: &str, inner: &Node| {
+            obj.str("kind", kind);
+            obj.raw("item", &inner.to_json());
+        };
+        match self {
+            Node::And(xs) | Node::Or(xs) => {
+                let kind = if matches!(self, Node::And(_)) {
+                    "and"
+                } else {
+                    "or"
+                };
+                obj.str("kind", kind);
+                let items = xs.iter().map(Node::to_json).collect::<Vec<_>>();
+                obj.raw_list("items", &items);
+            }
+            Node::Optional(x) => wrapped("optional", x),
+            Node::Many(x) => wrapped("many", x),
+            Node::Adjacent(x) => wrapped("adjacent", x),
+            Node::Strict(x) => wrapped("strict", x),
+            Node::Hidden(x) => wrapped("hidden", x),
+            Node::Group { header, inner } => {
+                obj.str("kind", "group");
+                obj.str("header", header);
+                obj.raw("item", &inner.to_json());
+            }
+            Node::Usage { usage, inner } => {
+                obj.str("kind", "usage");
+                obj.str("usage", usage);
+                obj.raw("item", &inner.to_json());
+            }
+            Node::Suffix { text, inner } => {
+                obj.str("kind", "suffix");
+                obj.str("text", text);
+                obj.raw("item", &inner.to_json());
+            }
+            Node::Complete { hint, inner } => {
+                obj.str("kind", "complete");
+                match hint {
+                    Completion::Dynamic => obj.str("hint", "dynamic"),
+                    Completion::File { mask } | Completion::Dir { mask } => {
+                        let kind = if matches!(hint, Completion::File { .. }) {
+                            "file"
+                        } else {
+                            "dir"
+                        };
+                        obj.str("hint", kind);
+                        obj.opt_str("mask", mask.as_deref());
+                    }
+                    Completion::Raw => obj.str("hint", "raw"),
+                    Completion::Nothing => obj.str("hint", "nothing"),
+                    Completion::Values(values) => {
+                        obj.str("hint", "values");
+                        let values = values
+                            .iter()
+                            .map(|(name, help)| {
+                                let mut value = Object::default();
+                                value.str("name", name);
+                                value.opt_str("help", help.as_deref());
+                                value.finish()
+                            })
+                            .collect::<Vec<_>>();
+                        obj.raw_list("values", &values);
+                    }
+                }
+                obj.raw("item", &inner.to_json());
+            }
+            Node::Flag(named) | Node::Argument(named) => {
+                let kind = if matches!(self, Node::Flag(_)) {
+                    "flag"
+                } else {
+                    "argument"
+                };
+                obj.str("kind", kind);
+                obj.opt_str("short", named.short.map(String::from).as_deref());
+                obj.opt_str("long", named.long.as_deref());
+                obj.list("env", &named.env);
+                obj.opt_str("metavar", named.metavar.as_deref());
+                obj.opt_str("help", named.help.as_deref());
+                obj.bool("deprecated", named.deprecated);
+                obj.bool("negatable", named.negatable);
+                obj.bool("opt_value", named.opt_value);
+            }
+            Node::Positional(pos) | Node::Any(pos) => {
+                let kind = if matches!(self, Node::Positional(_)) {
+                    "positional"
+                } else {
+                    "any"
+                };
+                obj.str("kind", kind);
+                obj.str("metavar", &pos.metavar);
+                obj.opt_str("help", pos.help.as_deref());
+            }
+            Node::Command(cmd) => {
+                obj.str("kind", "command");
+                obj.str("name", &cmd.name);
+                obj.opt_str("short", cmd.short.map(String::from).as_deref());
+                obj.opt_str("help", cmd.help.as_deref());
+                obj.bool("deprecated", cmd.deprecated);
+                obj.raw("spec", &cmd.spec.to_json());
+            }
+            Node::Empty => obj.str("kind", "empty"),
+        }
+        obj.finish()
+    }
+}
+
+impl From<&Meta> for Node {
+    fn from(meta: &Meta) -> Self {
+        let boxed = |meta: &Meta| Box::new(Node::from(meta));
+        match meta {
+            Meta::And(xs) => Node::And(xs.iter().map(Node::from).collect()),
+            Meta::Or(xs) => Node::Or(xs.iter().map(Node::from).collect()),
+            Meta::Optional(x) => Node::Optional(boxed(x)),
+            Meta::Required(x) => Node::from(x.as_ref()),
+            Meta::Adjacent(x) => Node::Adjacent(boxed(x)),
+            Meta::Item(item) => Node::from(item.as_ref()),
+            Meta::Many(x) => Node::Many(boxed(x)),
+            Meta::Subsection(x, header) => Node::Group {
+                header: text(header),
+                inner: boxed(x),
+            },
+            Meta::Suffix(x, suffix) => Node::Suffix {
+                text: text(&suffix.render(None)),
+                inner: boxed(x),
+            },
+            Meta::Skip => Node::Empty,
+            Meta::Hidden(x) => Node::Hidden(boxed(x)),
+            Meta::CustomUsage(x, usage) => Node::Usage {
+                usage: text(usage),
+                inner: boxed(x),
+            },
+            Meta::Strict(x) => Node::Strict(boxed(x)),
+            #[cfg(feature = "autocomplete")]
+            Meta::Complete(x, hint) => Node::Complete {
+                hint: Completion::from(hint),
+                inner: boxed(x),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "autocomplete")]
+impl From<&crate::complete_shell::CompHint> for Completion {
+    fn from(hint: &crate::complete_shell::CompHint) -> Self {
+        use crate::complete_shell::{CompHint, ShellComp};
+        let mask = |mask: &Option<&'static str>| mask.map(String::from);
+        match hint {
+            CompHint::Dynamic => Completion::Dynamic,
+            CompHint::Values(values) => Completion::Values(
+                values
+                    .iter()
+                    .map(|(name, help)| ((*name).to_owned(), help.map(String::from)))
+                    .collect(),
+            ),
+            CompHint::Shell(ShellComp::File { mask: m }) => Completion::File { mask: mask(m) },
+            CompHint::Shell(ShellComp::Dir { mask: m }) => Completion::Dir { mask: mask(m) },
+            CompHint::Shell(ShellComp::Raw { .. }) => Completion::Raw,
+            CompHint::Shell(ShellComp::Nothing) => Completion::Nothing,
+        }
+    }
+}
+
+fn named(name: &ShortLong, env: &[&'static str], help: &Option<Doc>, deprecated: bool) -> Named {
+    Named {
+        short: name.as_short(),
+        long: name.as_long().map(String::from),
+        env: env.iter().map(|e| (*e).to_owned()).collect(),
+        metavar: None,
+        help: opt_text(help),
+        deprecated,
+        negatable: false,
+        opt_value: false,
+    }
+}
+
+impl From<&Item> for Node {
+    fn from(item: &Item) -> Self {
+        match item {
+            Item::Any { metavar, help, .. } => Node::Any(Positional {
+                metavar: text(metavar),
+                help: opt_text(help),
+            }),
+            Item::Positional { metavar, help } => Node::Positional(Positional {
+                metavar: metavar.0.to_owned(),
+                help: opt_text(help),
+            }),
+            Item::Command {
+                name,
+                short,
+                help,
+                meta,
+                info,
+                deprecated,
+            } => Node::Command(Command {
+                name: (*name).to_owned(),
+                short: *short,
+                help: opt_text(help),
+                deprecated: *deprecated,
+                spec: Box::new(CliSpec::new(info, meta)),
+            }),
+            Item::Flag {
+                name,
+                env,
+                help,
+                deprecated,
+                negatable,
+                ..
+            } => Node::Flag(Named {
+                negatable: *negatable,
+                ..named(name, env, help, *deprecated)
+            }),
+            Item::Argument {
+                name,
+                metavar,
+                env,
+                help,
+                deprecated,
+                opt_value,
+                ..
+            } => Node::Argument(Named {
+                metavar: Some(metavar.0.to_owned()),
+                opt_value: *opt_value,
+                ..named(name, env, help, *deprecated)
+            }),
+        }
+    }
+}
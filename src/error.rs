@@ -2,11 +2,13 @@ use std::ops::Range;
 
 use crate::{
     args::{Arg, State},
-    buffer::{Block, Color, Doc, Style, Token},
+    buffer::{is_terminal, Block, Color, Doc, Layout, Stream, Style, Token},
     item::Item,
     item::ShortLong,
+    locale::{self, Catalog, Text},
     meta_help::Metavar,
     meta_youmean::{Suggestion, Variant},
+    response::ResponseError,
     Meta,
 };
 
@@ -35,6 +37,9 @@ pub(crate) enum Message {
     /// pure_with failed to parse a value
     PureFailed(String),
 
+    /// Group constraint is not satisfied and none of its items can be named
+    NoneOfGroup,
+
     /// Expected one of those values
     ///
     /// Used internally to generate better error messages
@@ -50,8 +55,11 @@ pub(crate) enum Message {
     /// Parser provided by user failed to parse a value
     ParseFailed(Option<usize>, String),
 
+    /// Value from a config source under this key failed to parse
+    ConfigFailed(&'static str, String),
+
     /// Parser provided by user failed to validate a value
-    GuardFailed(Option<usize>, &'static str),
+    GuardFailed(Option<usize>, String),
 
     /// Argument requres a value but something else was passed,
     /// required: --foo <BAR>
@@ -74,11 +82,20 @@ pub(crate) enum Message {
     /// --release --dev
     Conflict(/* winner */ usize, usize),
 
+    /// Argument is present, but an item it requires is not, contains the item name
+    Requires(usize, String),
+
     /// Expected one or more items in the scope, got someting else if any
     Expected(Vec<Item>, Option<usize>),
 
     /// Parameter is accepted but only once
     OnlyOnce(/* winner */ usize, usize),
+
+    /// Rest of a delimited value is left after the parser took the first element
+    SingleValue(usize),
+
+    /// Response file couldn't be expanded: path, line if known and a reason
+    ResponseFile(String, Option<usize>, ResponseError),
 }
 
 impl Message {
@@ -88,18 +105,23 @@ impl Message {
             | Message::ParseSome(_)
             | Message::ParseFail(_)
             | Message::Missing(_)
-            | Message::PureFailed(_) => true,
+            | Message::PureFailed(_)
+            | Message::NoneOfGroup => true,
             Message::StrictPos(_, _)
             | Message::ParseFailed(_, _)
+            | Message::ConfigFailed(_, _)
             | Message::GuardFailed(_, _)
             | Message::Unconsumed(_)
             | Message::Ambiguity(_, _)
             | Message::Suggestion(_, _)
             | Message::Conflict(_, _)
+            | Message::Requires(_, _)
             | Message::ParseFailure(_)
             | Message::Expected(_, _)
             | Message::OnlyOnce(_, _)
-            | Message::NoArgument(_, _) => false,
+            | Message::SingleValue(_)
+            | Message::NoArgument(_, _)
+            | Message::ResponseFile(_, _, _) => false,
         }
     }
 }
@@ -161,15 +183,340 @@ impl Message {
 /// [`ParseFailure::unwrap_stdout`] and [`ParseFailure::unwrap_stdout`] - both of which produce a
 /// an unformatted `String` that parser might produce if failure type is correct or panics
 /// otherwise.
+///
+/// Errors produced by `bpaf` also carry an [`ErrorKind`] describing the failure for tools that
+/// need to react to it programmatically, see [`ParseFailure::kind`] and [`ParseFailure::to_json`].
 #[derive(Clone, Debug)]
 pub enum ParseFailure {
     /// Print this to stdout and exit with success code
-    Stdout(Doc, bool),
+    Stdout(Output, bool),
     /// This also goes to stdout with exit code of 0,
     /// this cannot be Doc because completion needs more control about rendering
     Completion(String),
     /// Print this to stderr and exit with failure code
-    Stderr(Doc),
+    Stderr(Output),
+}
+
+/// Text of a [`ParseFailure`] along with what the parser knows about it
+///
+/// Dereferences to the [`Doc`] with the text, create one from a [`Doc`] with `into`. Failures
+/// produced by the parser also remember the [`ErrorKind`], see [`ParseFailure::kind`], and
+/// width and pager settings of the parser, see [`ParseFailure::exit_code`].
+#[derive(Clone, Debug)]
+pub struct Output {
+    doc: Doc,
+    pub(crate) kind: Option<Box<ErrorKind>>,
+    pub(crate) layout: Layout,
+    pub(crate) pager: bool,
+    pub(crate) locale: Option<Catalog>,
+}
+
+impl From<Doc> for Output {
+    fn from(doc: Doc) -> Self {
+        Output {
+            doc,
+            kind: None,
+            layout: Layout::default(),
+            pager: false,
+            locale: None,
+        }
+    }
+}
+
+impl From<Output> for Doc {
+    fn from(output: Output) -> Self {
+        output.doc
+    }
+}
+
+impl std::ops::Deref for Output {
+    type Target = Doc;
+
+    fn deref(&self) -> &Doc {
+        &self.doc
+    }
+}
+
+impl std::fmt::Display for Output {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.doc.fmt(f)
+    }
+}
+
+/// Machine readable description of a parsing error
+///
+/// Indices refer to command line arguments passed to the parser, program name is not included,
+/// same as in [`ValueSource::Cli`](crate::ValueSource::Cli). Items are named the way they are
+/// written on the command line (`--release`, `-v`) or by their metavariable for positional
+/// items.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Required items are missing, `index` points to an unexpected argument found instead if any
+    Missing {
+        /// Names of items parser expected to see
+        expected: Vec<String>,
+        /// Argument found in place of expected items
+        index: Option<usize>,
+    },
+    /// Environment variable is not set and there's no fallback
+    NoEnv {
+        /// Variable name
+        name: String,
+    },
+    /// Failure produced by [`fail`](crate::fail), [`some`](crate::Parser::some) or
+    /// [`pure_with`](crate::pure_with)
+    Custom {
+        /// Error message
+        message: String,
+    },
+    /// Strictly positional item is present, but on the left side of `--`
+    StrictPos {
+        /// Argument that was found
+        index: usize,
+        /// Metavariable of the strictly positional item
+        metavar: String,
+    },
+    /// Value was present but failed to parse
+    ParseFailed {
+        /// Argument containing the value, if known
+        index: Option<usize>,
+        /// Value that failed to parse, if known
+        value: Option<String>,
+        /// Error message from the parser
+        message: String,
+    },
+    /// Value from a config source failed to parse
+    ConfigFailed {
+        /// Config key
+        key: String,
+        /// Error message from the parser
+        message: String,
+    },
+    /// Value was parsed but failed a [`guard`](crate::Parser::guard) check
+    GuardFailed {
+        /// Argument containing the value, if known
+        index: Option<usize>,
+        /// Value that failed the check, if known
+        value: Option<String>,
+        /// Error message from the guard
+        message: String,
+    },
+    /// Named argument is present without its value
+    NoArgument {
+        /// Argument that requires a value
+        index: usize,
+        /// Name of the argument
+        name: String,
+        /// Metavariable of the missing value
+        metavar: String,
+    },
+    /// Argument is not expected in this context
+    Unconsumed {
+        /// Argument that was left unconsumed
+        index: usize,
+        /// Argument as it was passed
+        name: String,
+    },
+    /// Short flag can be parsed as both several flags or a flag with a value
+    Ambiguity {
+        /// Ambiguous argument
+        index: usize,
+        /// Argument as it was passed
+        name: String,
+    },
+    /// Argument is not expected, but there's a similar one that is
+    Suggestion {
+        /// Unexpected argument
+        index: usize,
+        /// Argument as it was passed
+        name: String,
+        /// Suggested item, for items that belong to a subcommand - the subcommand name
+        suggestion: String,
+    },
+    /// Abbreviated name matches several names, see
+    /// [`allow_abbreviations`](crate::OptionParser::allow_abbreviations)
+    AmbiguousPrefix {
+        /// Ambiguous argument
+        index: usize,
+        /// Argument as it was passed
+        name: String,
+        /// Names it could stand for
+        candidates: Vec<String>,
+    },
+    /// Two mutually exclusive items are present
+    Conflict {
+        /// Argument that can't be used
+        index: usize,
+        /// Name of the argument that can't be used
+        name: String,
+        /// Argument that was accepted instead
+        winner_index: usize,
+        /// Name of the argument that was accepted instead
+        winner_name: String,
+    },
+    /// Item is present, but an item it [`requires`](crate::requires) is not
+    Requires {
+        /// Argument that requires another item
+        index: usize,
+        /// Name of the argument
+        name: String,
+        /// Name of the required item
+        required: String,
+    },
+    /// Item can be used only once but is present several times
+    OnlyOnce {
+        /// Repeated argument
+        index: usize,
+        /// Argument as it was passed
+        name: String,
+        /// First occurrence of the argument
+        first_index: usize,
+    },
+    /// Argument with a [`delimiter`](crate::parsers::ParseArgument::delimiter) got several
+    /// values but takes only one
+    SingleValue {
+        /// Argument with several values
+        index: usize,
+        /// Argument as it was passed
+        name: String,
+    },
+    /// Response file couldn't be read or parsed, see
+    /// [`response_files`](crate::OptionParser::response_files)
+    ResponseFile {
+        /// Path to the file
+        path: String,
+        /// Line number inside the file, if relevant
+        line: Option<usize>,
+    },
+}
+
+impl ErrorKind {
+    fn write_json(&self, obj: &mut crate::json::Object) {
+        match self {
+            ErrorKind::Missing { expected, index } => {
+                obj.str("kind", "missing");
+                obj.list("expected", expected);
+                obj.opt_num("index", *index);
+            }
+            ErrorKind::NoEnv { name } => {
+                obj.str("kind", "no_env");
+                obj.str("name", name);
+            }
+            ErrorKind::Custom { message: _ } => {
+                obj.str("kind", "custom");
+            }
+            ErrorKind::StrictPos { index, metavar } => {
+                obj.str("kind", "strict_pos");
+                obj.num("index", *index);
+                obj.str("metavar", metavar);
+            }
+            ErrorKind::ParseFailed {
+                index,
+                value,
+                message: _,
+            } => {
+                obj.str("kind", "parse_failed");
+                obj.opt_num("index", *index);
+                obj.opt_str("value", value.as_deref());
+            }
+            ErrorKind::ConfigFailed { key, message: _ } => {
+                obj.str("kind", "config_failed");
+                obj.str("key", key);
+            }
+            ErrorKind::GuardFailed {
+                index,
+                value,
+                message: _,
+            } => {
+                obj.str("kind", "guard_failed");
+                obj.opt_num("index", *index);
+                obj.opt_str("value", value.as_deref());
+            }
+            ErrorKind::NoArgument {
+                index,
+                name,
+                metavar,
+            } => {
+                obj.str("kind", "no_argument");
+                obj.num("index", *index);
+                obj.str("name", name);
+                obj.str("metavar", metavar);
+            }
+            ErrorKind::Unconsumed { index, name } => {
+                obj.str("kind", "unconsumed");
+                obj.num("index", *index);
+                obj.str("name", name);
+            }
+            ErrorKind::Ambiguity { index, name } => {
+                obj.str("kind", "ambiguity");
+                obj.num("index", *index);
+                obj.str("name", name);
+            }
+            ErrorKind::Suggestion {
+                index,
+                name,
+                suggestion,
+            } => {
+                obj.str("kind", "suggestion");
+                obj.num("index", *index);
+                obj.str("name", name);
+                obj.str("suggestion", suggestion);
+            }
+            ErrorKind::AmbiguousPrefix {
+                index,
+                name,
+                candidates,
+            } => {
+                obj.str("kind", "ambiguous_prefix");
+                obj.num("index", *index);
+                obj.str("name", name);
+                obj.list("candidates", candidates);
+            }
+            ErrorKind::Conflict {
+                index,
+                name,
+                winner_index,
+                winner_name,
+            } => {
+                obj.str("kind", "conflict");
+                obj.num("index", *index);
+                obj.str("name", name);
+                obj.num("winner_index", *winner_index);
+                obj.str("winner_name", winner_name);
+            }
+            ErrorKind::Requires {
+                index,
+                name,
+                required,
+            } => {
+                obj.str("kind", "requires");
+                obj.num("index", *index);
+                obj.str("name", name);
+                obj.str("required", required);
+            }
+            ErrorKind::OnlyOnce {
+                index,
+                name,
+                first_index,
+            } => {
+                obj.str("kind", "only_once");
+                obj.num("index", *index);
+                obj.str("name", name);
+                obj.num("first_index", *first_index);
+            }
+            ErrorKind::SingleValue { index, name } => {
+                obj.str("kind", "single_value");
+                obj.num("index", *index);
+                obj.str("name", name);
+            }
+            ErrorKind::ResponseFile { path, line } => {
+                obj.str("kind", "response_file");
+                obj.str("path", path);
+                obj.opt_num("line", *line);
+            }
+        }
+    }
 }
 
 impl ParseFailure {
@@ -202,15 +549,90 @@ impl ParseFailure {
         }
     }
 
+    /// Kind of a parsing error, `None` for `stdout` and completion output
+    #[must_use]
+    pub fn kind(&self) -> Option<&ErrorKind> {
+        match self {
+            Self::Stderr(output) => output.kind.as_deref(),
+            Self::Completion(..) | Self::Stdout(..) => None,
+        }
+    }
+
+    /// Render a parsing error as a single line JSON object, `None` for `stdout` and completion
+    /// output and for errors that don't come from `bpaf`
+    ///
+    /// Object contains `kind` in snake case, rendered error `message` and fields specific to
+    /// the [`ErrorKind`], with `null` for values that are not known.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let intel = long("intel").req_flag(());
+    /// let att = long("att").req_flag(());
+    /// let parser = construct!([intel, att]).to_options();
+    ///
+    /// let r = parser.run_inner(&["--intel", "--att"]).unwrap_err();
+    /// assert_eq!(
+    ///     r.to_json().unwrap(),
+    ///     r#"{"kind":"conflict","index":1,"name":"--att","winner_index":0,"winner_name":"--intel","message":"`--att` cannot be used at the same time as `--intel`"}"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_json(&self) -> Option<String> {
+        match self {
+            Self::Stderr(output) => {
+                let kind = output.kind.as_deref()?;
+                let mut obj = crate::json::Object::default();
+                kind.write_json(&mut obj);
+                obj.str("message", &output.monochrome(true));
+                Some(obj.finish())
+            }
+            Self::Completion(..) | Self::Stdout(..) => None,
+        }
+    }
+
     /// Run an action appropriate to the failure and produce the exit code
     ///
-    /// Prints a message to `stdout` or `stderr` and returns the exit code
+    /// Prints a message to `stdout` or `stderr` and returns the exit code, width,
+    /// [`pager`](crate::OptionParser::pager) and [`locale`](crate::OptionParser::locale)
+    /// settings come from the parser that failed
     #[allow(clippy::must_use_candidate)]
     pub fn exit_code(self) -> i32 {
+        let (layout, pager, locale) = match &self {
+            ParseFailure::Stdout(output, _) | ParseFailure::Stderr(output) => {
+                (output.layout, output.pager, output.locale.clone())
+            }
+            ParseFailure::Completion(_) => (Layout::default(), false, None),
+        };
+        self.print(layout, pager, locale.as_ref())
+    }
+
+    /// Remember width, pager and locale settings of the parser for
+    /// [`exit_code`](Self::exit_code)
+    pub(crate) fn with_output(
+        mut self,
+        layout: Layout,
+        pager: bool,
+        locale: Option<&Catalog>,
+    ) -> Self {
+        if let ParseFailure::Stdout(output, _) | ParseFailure::Stderr(output) = &mut self {
+            output.layout = layout;
+            output.pager = pager;
+            output.locale = locale.cloned();
+        }
+        self
+    }
+
+    /// Print the message using width, pager and locale settings from the parser and produce
+    /// the exit code
+    pub(crate) fn print(self, layout: Layout, pager: bool, locale: Option<&Catalog>) -> i32 {
         let color = Color::default();
         match self {
             ParseFailure::Stdout(msg, full) => {
-                println!("{}", msg.render_console(full, color));
+                let layout = layout.or_terminal(Stream::Stdout);
+                let text = msg.render_console(full, color, layout);
+                if !(pager && is_terminal(Stream::Stdout) && crate::pager::page(&text)) {
+                    println!("{}", text);
+                }
                 0
             }
             ParseFailure::Completion(s) => {
@@ -218,20 +640,22 @@ impl ParseFailure {
                 0
             }
             ParseFailure::Stderr(msg) => {
+                let prefix = locale::get(locale, Text::Error);
                 #[allow(unused_mut)]
                 let mut error;
                 #[cfg(not(feature = "color"))]
                 {
-                    error = "Error: ";
+                    error = prefix;
                 }
 
                 #[cfg(feature = "color")]
                 {
                     error = String::new();
-                    color.push_str(Style::Invalid, &mut error, "Error: ");
+                    color.push_str(Style::Invalid, &mut error, prefix);
                 }
 
-                eprintln!("{}{}", error, msg.render_console(true, color));
+                let layout = layout.or_terminal(Stream::Stderr);
+                eprintln!("{} {}", error, msg.render_console(true, color, layout));
                 1
             }
         }
@@ -243,13 +667,30 @@ fn check_conflicts(args: &State) -> Option<Message> {
     Some(Message::Conflict(winner, loser))
 }
 
-fn textual_part(args: &State, ix: Option<usize>) -> Option<std::borrow::Cow<str>> {
+pub(crate) fn textual_part(args: &State, ix: Option<usize>) -> Option<std::borrow::Cow<str>> {
     match args.items.get(ix?)? {
         Arg::Short(_, _, _) | Arg::Long(_, _, _) => None,
         Arg::ArgWord(s) | Arg::Word(s) | Arg::PosWord(s) => Some(s.to_string_lossy()),
     }
 }
 
+/// Name of an item as it appears on a command line
+pub(crate) fn item_name(item: &Item) -> String {
+    match item {
+        Item::Any { metavar, .. } => metavar.monochrome(false),
+        Item::Command { .. } => "COMMAND".to_owned(),
+        _ => crate::provenance::item_name(item).unwrap_or_default(),
+    }
+}
+
+fn variant_name(variant: &Variant) -> String {
+    match variant {
+        Variant::CommandLong(name) => (*name).to_owned(),
+        Variant::Flag(ShortLong::Long(l) | ShortLong::ShortLong(_, l)) => format!("--{}", l),
+        Variant::Flag(ShortLong::Short(s)) => format!("-{}", s),
+    }
+}
+
 fn only_once(args: &State, cur: usize) -> Option<usize> {
     if cur == 0 {
         return None;
@@ -273,6 +714,9 @@ impl Message {
                     self = conflict;
                 } else if let Some((ix, suggestion)) = crate::meta_youmean::suggest(args, meta) {
                     self = Message::Suggestion(ix, suggestion);
+                } else if args.split_keys.contains(&ix) {
+                    // the rest of a delimited value that was only partially consumed
+                    self = Message::SingleValue(ix);
                 } else if let Some(prev_ix) = only_once(args, ix) {
                     self = Message::OnlyOnce(prev_ix, ix);
                 }
@@ -283,118 +727,170 @@ impl Message {
             _ => {}
         }
 
+        let locale = args.locale.as_ref();
         let mut doc = Doc::default();
-        match self {
+        let kind = match self {
             // already rendered
             Message::ParseFailure(f) => return f,
 
             // this case is handled above
-            Message::Missing(_) => {
+            Message::Missing(xs) => {
                 // this one is unreachable
+                let expected = xs.iter().map(|x| item_name(&x.item)).collect();
+                ErrorKind::Missing {
+                    expected,
+                    index: None,
+                }
             }
 
             // Error: --foo is not expected in this context
             Message::Unconsumed(ix) => {
                 let item = &args.items[ix];
-                doc.token(Token::BlockStart(Block::TermRef));
-                doc.write(item, Style::Invalid);
-                doc.token(Token::BlockEnd(Block::TermRef));
-                doc.text(" is not expected in this context");
+                doc.template(locale::get(locale, Text::NotExpected), |doc, _| {
+                    term_ref(doc, |doc| doc.write(item, Style::Invalid));
+                });
+                ErrorKind::Unconsumed {
+                    index: args.origin(ix),
+                    name: item.to_string(),
+                }
             }
 
             // Error: environment variable FOO is not set
             Message::NoEnv(name) => {
-                doc.text("environment variable ");
-                doc.token(Token::BlockStart(Block::TermRef));
-                doc.invalid(name);
-                doc.token(Token::BlockEnd(Block::TermRef));
-                doc.text(" is not set");
+                doc.template(locale::get(locale, Text::NoEnv), |doc, _| {
+                    term_ref(doc, |doc| doc.invalid(name));
+                });
+                ErrorKind::NoEnv {
+                    name: name.to_owned(),
+                }
             }
 
             // Error: FOO expected to be  in the right side of --
-            Message::StrictPos(_ix, metavar) => {
-                doc.text("expected ");
-                doc.token(Token::BlockStart(Block::TermRef));
-                doc.metavar(metavar);
-                doc.token(Token::BlockEnd(Block::TermRef));
-                doc.text(" to be on the right side of ");
-                doc.token(Token::BlockStart(Block::TermRef));
-                doc.literal("--");
-                doc.token(Token::BlockEnd(Block::TermRef));
+            Message::StrictPos(ix, metavar) => {
+                doc.template(locale::get(locale, Text::StrictPos), |doc, ix| match ix {
+                    0 => term_ref(doc, |doc| doc.metavar(metavar)),
+                    _ => term_ref(doc, |doc| doc.literal("--")),
+                });
+                ErrorKind::StrictPos {
+                    index: args.origin(ix),
+                    metavar: metavar.0.to_owned(),
+                }
             }
 
             // Error: <message from some or fail>
             Message::ParseSome(s) | Message::ParseFail(s) => {
                 doc.text(s);
+                ErrorKind::Custom {
+                    message: s.to_owned(),
+                }
             }
 
             // Error: couldn't parse FIELD: <FromStr message>
             Message::ParseFailed(mix, s) => {
-                doc.text("couldn't parse");
-                if let Some(field) = textual_part(args, mix) {
-                    doc.text(" ");
-                    doc.token(Token::BlockStart(Block::TermRef));
-                    doc.invalid(&field);
-                    doc.token(Token::BlockEnd(Block::TermRef));
+                let value = textual_part(args, mix);
+                match &value {
+                    Some(field) => {
+                        doc.template(locale::get(locale, Text::ParseFailed), |doc, ix| match ix {
+                            0 => term_ref(doc, |doc| doc.invalid(field)),
+                            _ => doc.text(&s),
+                        });
+                    }
+                    None => {
+                        doc.template(locale::get(locale, Text::ParseFailedNoValue), |doc, _| {
+                            doc.text(&s);
+                        });
+                    }
+                }
+                ErrorKind::ParseFailed {
+                    index: mix.map(|ix| args.origin(ix)),
+                    value: value.map(|v| v.into_owned()),
+                    message: s,
+                }
+            }
+
+            // Error: couldn't parse config value `threads`: <FromStr message>
+            Message::ConfigFailed(key, s) => {
+                doc.template(
+                    locale::get(locale, Text::ConfigFailed),
+                    |doc, ix| match ix {
+                        0 => term_ref(doc, |doc| doc.invalid(key)),
+                        _ => doc.text(&s),
+                    },
+                );
+                ErrorKind::ConfigFailed {
+                    key: key.to_owned(),
+                    message: s,
                 }
-                doc.text(": ");
-                doc.text(&s);
             }
 
             // Error: ( FIELD:  | check failed: ) <message from guard>
             Message::GuardFailed(mix, s) => {
-                if let Some(field) = textual_part(args, mix) {
-                    doc.token(Token::BlockStart(Block::TermRef));
-                    doc.invalid(&field);
-                    doc.token(Token::BlockEnd(Block::TermRef));
-                    doc.text(": ");
-                } else {
-                    doc.text("check failed: ");
+                let value = textual_part(args, mix);
+                match &value {
+                    Some(field) => {
+                        doc.template(locale::get(locale, Text::GuardFailed), |doc, ix| match ix {
+                            0 => term_ref(doc, |doc| doc.invalid(field)),
+                            _ => doc.text(&s),
+                        });
+                    }
+                    None => {
+                        doc.template(locale::get(locale, Text::GuardFailedNoValue), |doc, _| {
+                            doc.text(&s);
+                        });
+                    }
+                }
+                ErrorKind::GuardFailed {
+                    index: mix.map(|ix| args.origin(ix)),
+                    value: value.map(|v| v.into_owned()),
+                    message: s,
                 }
-                doc.text(s);
             }
 
             // Error: --foo requires an argument FOO, got a flag --bar, try --foo=-bar to use it as an argument
             // Error: --foo requires an argument FOO
-            Message::NoArgument(x, mv) => match args.get(x + 1) {
-                Some(Arg::Short(_, _, os) | Arg::Long(_, _, os)) => {
-                    let arg = &args.items[x];
-                    let os = &os.to_string_lossy();
-
-                    doc.token(Token::BlockStart(Block::TermRef));
-                    doc.write(arg, Style::Literal);
-                    doc.token(Token::BlockEnd(Block::TermRef));
-                    doc.text(" requires an argument ");
-                    doc.token(Token::BlockStart(Block::TermRef));
-                    doc.metavar(mv);
-                    doc.token(Token::BlockEnd(Block::TermRef));
-                    doc.text(", got a flag ");
-                    doc.token(Token::BlockStart(Block::TermRef));
-                    doc.write(os, Style::Invalid);
-                    doc.token(Token::BlockEnd(Block::TermRef));
-                    doc.text(", try ");
-                    doc.token(Token::BlockStart(Block::TermRef));
-                    doc.write(arg, Style::Literal);
-                    doc.literal("=");
-                    doc.write(os, Style::Literal);
-                    doc.token(Token::BlockEnd(Block::TermRef));
-                    doc.text(" to use it as an argument");
+            Message::NoArgument(x, mv) => {
+                let arg = &args.items[x];
+                match args.get(x + 1) {
+                    Some(Arg::Short(_, _, os) | Arg::Long(_, _, os)) => {
+                        let os = &os.to_string_lossy();
+                        let template = locale::get(locale, Text::NoArgumentGotFlag);
+                        doc.template(template, |doc, ix| match ix {
+                            0 => term_ref(doc, |doc| doc.write(arg, Style::Literal)),
+                            1 => term_ref(doc, |doc| doc.metavar(mv)),
+                            2 => term_ref(doc, |doc| doc.write(os, Style::Invalid)),
+                            _ => term_ref(doc, |doc| {
+                                doc.write(arg, Style::Literal);
+                                doc.literal("=");
+                                doc.write(os, Style::Literal);
+                            }),
+                        });
+                    }
+                    // "Some" part of this branch is actually unreachable
+                    Some(Arg::ArgWord(_) | Arg::Word(_) | Arg::PosWord(_)) | None => {
+                        doc.template(locale::get(locale, Text::NoArgument), |doc, ix| match ix {
+                            0 => term_ref(doc, |doc| doc.write(arg, Style::Literal)),
+                            _ => term_ref(doc, |doc| doc.metavar(mv)),
+                        });
+                    }
                 }
-                // "Some" part of this branch is actually unreachable
-                Some(Arg::ArgWord(_) | Arg::Word(_) | Arg::PosWord(_)) | None => {
-                    let arg = &args.items[x];
-                    doc.token(Token::BlockStart(Block::TermRef));
-                    doc.write(arg, Style::Literal);
-                    doc.token(Token::BlockEnd(Block::TermRef));
-                    doc.text(" requires an argument ");
-                    doc.token(Token::BlockStart(Block::TermRef));
-                    doc.metavar(mv);
-                    doc.token(Token::BlockEnd(Block::TermRef));
+                ErrorKind::NoArgument {
+                    index: args.origin(x),
+                    name: arg.to_string(),
+                    metavar: mv.0.to_owned(),
                 }
-            },
+            }
             // Error: <message from pure_with>
             Message::PureFailed(s) => {
                 doc.text(&s);
+                ErrorKind::Custom { message: s }
+            }
+            // Error: none of the required items are present
+            Message::NoneOfGroup => {
+                let message = locale::get(locale, Text::NoneOfGroup);
+                doc.text(message);
+                ErrorKind::Custom {
+                    message: message.to_owned(),
+                }
             }
             // Error: app supports -f as both an option and an option-argument, try to split -foo
             // into invididual options (-f -o ..) or use -f=oo syntax to disambiguate
@@ -405,193 +901,241 @@ impl Message {
                 let second = chars.next().unwrap();
                 let s = args.items[ix].os_str().to_str().unwrap();
 
-                if let Some(name) = args.path.first() {
-                    doc.literal(name);
-                    doc.text(" supports ");
-                } else {
-                    doc.text("app supports ");
+                doc.template(locale::get(locale, Text::Ambiguity), |doc, ix| match ix {
+                    0 => match args.path.first() {
+                        Some(name) => doc.literal(name),
+                        None => doc.text(locale::get(locale, Text::App)),
+                    },
+                    1 => term_ref(doc, |doc| {
+                        doc.literal("-");
+                        doc.write_char(first, Style::Literal);
+                    }),
+                    2 => term_ref(doc, |doc| doc.write(s, Style::Literal)),
+                    3 => {
+                        doc.literal("-");
+                        doc.write_char(first, Style::Literal);
+                        doc.literal(" -");
+                        doc.write_char(second, Style::Literal);
+                        doc.literal(" ..");
+                    }
+                    _ => term_ref(doc, |doc| {
+                        doc.literal("-");
+                        doc.write_char(first, Style::Literal);
+                        doc.literal("=");
+                        doc.literal(rest);
+                    }),
+                });
+                ErrorKind::Ambiguity {
+                    index: args.origin(ix),
+                    name: s.to_owned(),
+                }
+            }
+            // Error: --ver is ambiguous, it could be --verbose, --version
+            Message::Suggestion(ix, Suggestion::Ambiguous(candidates)) => {
+                let actual = args.items[ix].to_string();
+                doc.template(
+                    locale::get(locale, Text::AmbiguousPrefix),
+                    |doc, i| match i {
+                        0 => term_ref(doc, |doc| doc.write_str(&actual, Style::Invalid)),
+                        _ => {
+                            for (i, candidate) in candidates.iter().enumerate() {
+                                if i > 0 {
+                                    doc.text(", ");
+                                }
+                                term_ref(doc, |doc| doc.literal(candidate));
+                            }
+                        }
+                    },
+                );
+                ErrorKind::AmbiguousPrefix {
+                    index: args.origin(ix),
+                    name: actual,
+                    candidates,
                 }
-
-                doc.token(Token::BlockStart(Block::TermRef));
-                doc.literal("-");
-                doc.write_char(first, Style::Literal);
-                doc.token(Token::BlockEnd(Block::TermRef));
-                doc.text(" as both an option and an option-argument, try to split ");
-                doc.token(Token::BlockStart(Block::TermRef));
-                doc.write(s, Style::Literal);
-                doc.token(Token::BlockEnd(Block::TermRef));
-                doc.text(" into individual options (");
-                doc.literal("-");
-                doc.write_char(first, Style::Literal);
-                doc.literal(" -");
-                doc.write_char(second, Style::Literal);
-                doc.literal(" ..");
-                doc.text(") or use ");
-                doc.token(Token::BlockStart(Block::TermRef));
-                doc.literal("-");
-                doc.write_char(first, Style::Literal);
-                doc.literal("=");
-                doc.literal(rest);
-                doc.token(Token::BlockEnd(Block::TermRef));
-                doc.text(" syntax to disambiguate");
             }
             // Error: No such (flag|argument|command), did you mean  ...
             Message::Suggestion(ix, suggestion) => {
                 let actual = &args.items[ix].to_string();
-                match suggestion {
+                // MissingDash and ExtraDash refer to what user likely meant to type rather than
+                // to a split item
+                let mut shown = None;
+                let (text, suggested) = match suggestion {
                     Suggestion::Variant(v) => {
-                        let ty = match &args.items[ix] {
-                            _ if actual.starts_with('-') => "flag",
-                            Arg::Short(_, _, _) | Arg::Long(_, _, _) => "flag",
-                            Arg::ArgWord(_) => "argument value",
-                            Arg::Word(_) | Arg::PosWord(_) => "command or positional",
-                        };
-
-                        doc.text("no such ");
-                        doc.text(ty);
-                        doc.text(": ");
-                        doc.token(Token::BlockStart(Block::TermRef));
-                        doc.invalid(actual);
-                        doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text(", did you mean ");
-                        doc.token(Token::BlockStart(Block::TermRef));
-
-                        match v {
-                            Variant::CommandLong(name) => doc.literal(name),
-                            Variant::Flag(ShortLong::Long(l) | ShortLong::ShortLong(_, l)) => {
-                                doc.literal("--");
-                                doc.literal(l);
-                            }
-                            Variant::Flag(ShortLong::Short(s)) => {
-                                doc.literal("-");
-                                doc.write_char(s, Style::Literal);
-                            }
+                        let text = match &args.items[ix] {
+                            _ if actual.starts_with('-') => Text::NoSuchFlag,
+                            Arg::Short(_, _, _) | Arg::Long(_, _, _) => Text::NoSuchFlag,
+                            Arg::ArgWord(_) => Text::NoSuchValue,
+                            Arg::Word(_) | Arg::PosWord(_) => Text::NoSuchCommand,
                         };
-
-                        doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text("?");
+                        (text, variant_name(&v))
                     }
                     Suggestion::MissingDash(name) => {
-                        doc.text("no such flag: ");
-                        doc.token(Token::BlockStart(Block::TermRef));
-                        doc.literal("-");
-                        doc.literal(name);
-                        doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text(" (with one dash), did you mean ");
-                        doc.token(Token::BlockStart(Block::TermRef));
-                        doc.literal("--");
-                        doc.literal(name);
-                        doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text("?");
+                        shown = Some(format!("-{}", name));
+                        (Text::MissingDash, format!("--{}", name))
                     }
                     Suggestion::ExtraDash(name) => {
-                        doc.text("no such flag: ");
-                        doc.token(Token::BlockStart(Block::TermRef));
-                        doc.literal("--");
-                        doc.write_char(name, Style::Literal);
-                        doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text(" (with two dashes), did you mean ");
-                        doc.token(Token::BlockStart(Block::TermRef));
-                        doc.literal("-");
-                        doc.write_char(name, Style::Literal);
-                        doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text("?");
+                        shown = Some(format!("--{}", name));
+                        (Text::ExtraDash, format!("-{}", name))
                     }
                     Suggestion::Nested(x, v) => {
-                        let ty = match v {
-                            Variant::CommandLong(_) => "subcommand",
-                            Variant::Flag(_) => "flag",
+                        let text = match v {
+                            Variant::CommandLong(_) => Text::NestedCommand,
+                            Variant::Flag(_) => Text::NestedFlag,
                         };
-                        doc.text(ty);
-                        doc.text(" ");
-                        doc.token(Token::BlockStart(Block::TermRef));
-                        doc.literal(actual);
-                        doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text(
-                            " is not valid in this context, did you mean to pass it to command ",
-                        );
-                        doc.token(Token::BlockStart(Block::TermRef));
-                        doc.literal(&x);
-                        doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text("?");
+                        (text, x)
                     }
+                    Suggestion::Ambiguous(_) => unreachable!("handled above"),
+                };
+                let invalid = match text {
+                    Text::NoSuchFlag | Text::NoSuchValue | Text::NoSuchCommand => Style::Invalid,
+                    _ => Style::Literal,
+                };
+                doc.template(locale::get(locale, text), |doc, ix| match ix {
+                    0 => term_ref(doc, |doc| {
+                        doc.write_str(shown.as_deref().unwrap_or(actual), invalid);
+                    }),
+                    _ => term_ref(doc, |doc| doc.literal(&suggested)),
+                });
+                ErrorKind::Suggestion {
+                    index: args.origin(ix),
+                    name: actual.clone(),
+                    suggestion: suggested,
                 }
             }
             // Error: Expected (no arguments|--foo), got ..., pass --help
             Message::Expected(exp, actual) => {
-                doc.text("expected ");
-                match exp.len() {
-                    0 => {
-                        doc.text("no arguments");
-                    }
-                    1 => {
-                        doc.token(Token::BlockStart(Block::TermRef));
-                        doc.write_item(&exp[0]);
-                        doc.token(Token::BlockEnd(Block::TermRef));
-                    }
-                    2 => {
-                        doc.token(Token::BlockStart(Block::TermRef));
-                        doc.write_item(&exp[0]);
-                        doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text(" or ");
-                        doc.token(Token::BlockStart(Block::TermRef));
-                        doc.write_item(&exp[1]);
-                        doc.token(Token::BlockEnd(Block::TermRef));
-                    }
-                    _ => {
-                        doc.token(Token::BlockStart(Block::TermRef));
-                        doc.write_item(&exp[0]);
-                        doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text(", ");
-                        doc.token(Token::BlockStart(Block::TermRef));
-                        doc.write_item(&exp[1]);
-                        doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text(", or more");
+                let write_expected = |doc: &mut Doc| match exp.len() {
+                    0 => doc.text(locale::get(locale, Text::NoArguments)),
+                    1 => term_ref(doc, |doc| doc.write_item(&exp[0])),
+                    len => {
+                        let text = if len == 2 {
+                            Text::OneOfTwo
+                        } else {
+                            Text::OneOfMany
+                        };
+                        doc.template(locale::get(locale, text), |doc, ix| {
+                            if let Some(item) = exp.get(ix) {
+                                term_ref(doc, |doc| doc.write_item(item));
+                            }
+                        });
                     }
-                }
+                };
+                let write_help = |doc: &mut Doc| term_ref(doc, |doc| doc.literal("--help"));
                 match actual {
                     Some(actual) => {
-                        doc.text(", got ");
-                        doc.token(Token::BlockStart(Block::TermRef));
-                        doc.write(&args.items[actual], Style::Invalid);
-                        doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text(". Pass ");
+                        doc.template(locale::get(locale, Text::ExpectedGot), |doc, ix| match ix {
+                            0 => write_expected(doc),
+                            1 => {
+                                term_ref(doc, |doc| doc.write(&args.items[actual], Style::Invalid))
+                            }
+                            _ => write_help(doc),
+                        });
                     }
                     None => {
-                        doc.text(", pass ");
+                        doc.template(locale::get(locale, Text::Expected), |doc, ix| match ix {
+                            0 => write_expected(doc),
+                            _ => write_help(doc),
+                        });
                     }
                 }
-                doc.token(Token::BlockStart(Block::TermRef));
-                doc.literal("--help");
-                doc.token(Token::BlockEnd(Block::TermRef));
-                doc.text(" for usage information");
+                ErrorKind::Missing {
+                    expected: exp.iter().map(item_name).collect(),
+                    index: actual.map(|ix| args.origin(ix)),
+                }
             }
 
             // Error: --intel cannot be used at the same time as --att
             Message::Conflict(winner, loser) => {
-                doc.token(Token::BlockStart(Block::TermRef));
-                doc.write(&args.items[loser], Style::Literal);
-                doc.token(Token::BlockEnd(Block::TermRef));
-                doc.text(" cannot be used at the same time as ");
-                doc.token(Token::BlockStart(Block::TermRef));
-                doc.write(&args.items[winner], Style::Literal);
-                doc.token(Token::BlockEnd(Block::TermRef));
+                doc.template(locale::get(locale, Text::Conflict), |doc, ix| {
+                    let item = if ix == 0 { loser } else { winner };
+                    term_ref(doc, |doc| doc.write(&args.items[item], Style::Literal));
+                });
+                ErrorKind::Conflict {
+                    index: args.origin(loser),
+                    name: args.items[loser].to_string(),
+                    winner_index: args.origin(winner),
+                    winner_name: args.items[winner].to_string(),
+                }
+            }
+
+            // Error: --cert requires --key
+            Message::Requires(ix, required) => {
+                doc.template(locale::get(locale, Text::Requires), |doc, i| {
+                    if i == 0 {
+                        term_ref(doc, |doc| doc.write(&args.items[ix], Style::Literal));
+                    } else {
+                        term_ref(doc, |doc| doc.literal(&required));
+                    }
+                });
+                ErrorKind::Requires {
+                    index: args.origin(ix),
+                    name: args.items[ix].to_string(),
+                    required,
+                }
             }
 
             // Error: argument FOO cannot be used multiple times in this context
-            Message::OnlyOnce(_winner, loser) => {
-                doc.text("argument ");
-                doc.token(Token::BlockStart(Block::TermRef));
-                doc.write(&args.items[loser], Style::Literal);
-                doc.token(Token::BlockEnd(Block::TermRef));
-                doc.text(" cannot be used multiple times in this context");
+            Message::OnlyOnce(winner, loser) => {
+                doc.template(locale::get(locale, Text::OnlyOnce), |doc, _| {
+                    term_ref(doc, |doc| doc.write(&args.items[loser], Style::Literal));
+                });
+                ErrorKind::OnlyOnce {
+                    index: args.origin(loser),
+                    name: args.items[loser].to_string(),
+                    first_index: args.origin(winner),
+                }
+            }
+
+            // Error: argument --tag expects a single value
+            Message::SingleValue(ix) => {
+                doc.template(locale::get(locale, Text::SingleValue), |doc, _| {
+                    term_ref(doc, |doc| doc.write(&args.items[ix], Style::Literal));
+                });
+                ErrorKind::SingleValue {
+                    index: args.origin(ix),
+                    name: args.items[ix].to_string(),
+                }
+            }
+
+            // Error: response file `args.txt`, line 3: unterminated quote
+            Message::ResponseFile(path, line, err) => {
+                let reason = match &err {
+                    ResponseError::Io(msg) => msg.as_str(),
+                    ResponseError::UnterminatedQuote => {
+                        locale::get(locale, Text::UnterminatedQuote)
+                    }
+                    ResponseError::Cycle => locale::get(locale, Text::ResponseFileCycle),
+                    ResponseError::TooDeep => locale::get(locale, Text::ResponseFileDepth),
+                };
+                let text = match line {
+                    Some(_) => Text::ResponseFileLine,
+                    None => Text::ResponseFile,
+                };
+                doc.template(locale::get(locale, text), |doc, ix| match (ix, line) {
+                    (0, _) => term_ref(doc, |doc| doc.invalid(&path)),
+                    (1, Some(line)) => doc.text(&line.to_string()),
+                    _ => doc.text(reason),
+                });
+                ErrorKind::ResponseFile { path, line }
             }
         };
 
-        ParseFailure::Stderr(doc)
+        let mut output = Output::from(doc);
+        output.kind = Some(Box::new(kind));
+        ParseFailure::Stderr(output)
     }
 }
 
+/// Write a reference to a command line item, rendered in backticks in plain text
+fn term_ref<F>(doc: &mut Doc, f: F)
+where
+    F: FnOnce(&mut Doc),
+{
+    doc.token(Token::BlockStart(Block::TermRef));
+    f(doc);
+    doc.token(Token::BlockEnd(Block::TermRef));
+}
+
 /// go over all the missing items, pick the left most scope
 pub(crate) fn summarize_missing(items: &[MissingItem], inner: &Meta, args: &State) -> Message {
     // missing items can belong to different scopes, pick the best scope to work with
@@ -0,0 +1,380 @@
+//! Constraints between several parsers: [`requires`](crate::requires),
+//! [`conflicts`](crate::conflicts), [`exactly_one_of`](crate::exactly_one_of) and
+//! [`at_least_one_of`](crate::at_least_one_of)
+//!
+//! An item is present if its parser consumed something from the command line, values that come
+//! from environment variables, config sources or fallbacks don't count.
+
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    buffer::Doc,
+    error::{item_name, Message, MissingItem},
+    locale::Text,
+    meta::Note,
+    Error, Meta, Parser, State,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Constraint {
+    /// If the first item is present - second one must be present too
+    Requires,
+    /// At most one of the items can be present
+    Conflicts,
+    /// Exactly one of the items must be present
+    ExactlyOne,
+    /// At least one of the items must be present
+    AtLeastOne,
+}
+
+/// A tuple of parsers that can be used with group constraints such as [`conflicts`](crate::conflicts)
+///
+/// Implemented for tuples of 2 to 8 parsers, produces a tuple of their results.
+pub trait Group<T> {
+    /// Evaluate all the members in order, for each member record the first consumed item if any
+    #[doc(hidden)]
+    fn eval_members(&self, args: &mut State, present: &mut Vec<Option<usize>>) -> Result<T, Error>;
+
+    #[doc(hidden)]
+    fn member_metas(&self) -> Vec<Meta>;
+}
+
+fn eval_member<P, T>(
+    parser: &P,
+    args: &mut State,
+    present: &mut Vec<Option<usize>>,
+) -> Result<T, Error>
+where
+    P: Parser<T>,
+{
+    let before = args.clone();
+    let res = parser.eval(args)?;
+    present.push(args.first_consumed_since(&before));
+    Ok(res)
+}
+
+macro_rules! group_tuple {
+    ($($p:ident $t:ident),+) => {
+        impl<$($p, $t),+> Group<($($t,)+)> for ($($p,)+)
+        where
+            $($p: Parser<$t>),+
+        {
+            #[allow(non_snake_case)]
+            fn eval_members(
+                &self,
+                args: &mut State,
+                present: &mut Vec<Option<usize>>,
+            ) -> Result<($($t,)+), Error> {
+                let ($($p,)+) = self;
+                Ok(($(eval_member($p, args, present)?,)+))
+            }
+
+            #[allow(non_snake_case)]
+            fn member_metas(&self) -> Vec<Meta> {
+                let ($($p,)+) = self;
+                vec![$($p.meta()),+]
+            }
+        }
+    };
+}
+
+group_tuple!(P1 T1, P2 T2);
+group_tuple!(P1 T1, P2 T2, P3 T3);
+group_tuple!(P1 T1, P2 T2, P3 T3, P4 T4);
+group_tuple!(P1 T1, P2 T2, P3 T3, P4 T4, P5 T5);
+group_tuple!(P1 T1, P2 T2, P3 T3, P4 T4, P5 T5, P6 T6);
+group_tuple!(P1 T1, P2 T2, P3 T3, P4 T4, P5 T5, P6 T6, P7 T7);
+group_tuple!(P1 T1, P2 T2, P3 T3, P4 T4, P5 T5, P6 T6, P7 T7, P8 T8);
+
+/// Parser that checks a constraint between several items, created with
+/// [`requires`](crate::requires), [`conflicts`](crate::conflicts),
+/// [`exactly_one_of`](crate::exactly_one_of) or [`at_least_one_of`](crate::at_least_one_of)
+pub struct ParseGroup<G> {
+    pub(crate) members: G,
+    pub(crate) constraint: Constraint,
+}
+
+impl<T, G> Parser<T> for ParseGroup<G>
+where
+    G: Group<T>,
+{
+    fn eval(&self, args: &mut State) -> Result<T, Error> {
+        let mut present = Vec::new();
+        let res = self.members.eval_members(args, &mut present)?;
+
+        // partial command line can't satisfy the constraints yet, but completion should still
+        // work for the items inside
+        #[cfg(feature = "autocomplete")]
+        if args.comp_ref().is_some() {
+            return Ok(res);
+        }
+
+        match check(self.constraint, &present, args, || {
+            self.members.member_metas()
+        }) {
+            Some(err) => Err(Error(err)),
+            None => Ok(res),
+        }
+    }
+
+    fn meta(&self) -> Meta {
+        let metas = self.members.member_metas();
+        let help = describe(self.constraint, &metas);
+        let usage = match self.constraint {
+            Constraint::Requires => {
+                // [[--cert=CERT] --key=KEY] - second item is optional only if the first one
+                // is missing
+                let mut metas = metas.into_iter();
+                let first = metas.next().unwrap_or_default();
+                match metas.next().unwrap_or_default() {
+                    Meta::Optional(second) => {
+                        Meta::Optional(Box::new(Meta::And(vec![first, *second])))
+                    }
+                    second => Meta::And(vec![first, second]),
+                }
+            }
+            Constraint::Conflicts | Constraint::ExactlyOne | Constraint::AtLeastOne => {
+                let alts = Meta::Or(metas.into_iter().map(required).collect());
+                match self.constraint {
+                    Constraint::Conflicts => Meta::Optional(Box::new(alts)),
+                    Constraint::AtLeastOne => Meta::Many(Box::new(Meta::Required(Box::new(alts)))),
+                    _ => alts,
+                }
+            }
+        };
+        Meta::Suffix(Box::new(usage), Box::new(help))
+    }
+}
+
+/// Group constraint checked on a bigger parser that contains the members in any order, used by
+/// the derive macro to keep the fields in the order they are declared
+#[doc(hidden)]
+pub struct GroupCheck(Rc<Members>);
+
+struct Members {
+    constraint: Constraint,
+    metas: RefCell<Vec<Meta>>,
+    /// first item consumed by each member during the last run
+    present: RefCell<Vec<Option<usize>>>,
+}
+
+impl GroupCheck {
+    fn new(constraint: Constraint) -> Self {
+        Self(Rc::new(Members {
+            constraint,
+            metas: RefCell::new(Vec::new()),
+            present: RefCell::new(Vec::new()),
+        }))
+    }
+
+    #[must_use]
+    pub fn requires() -> Self {
+        Self::new(Constraint::Requires)
+    }
+
+    #[must_use]
+    pub fn conflicts() -> Self {
+        Self::new(Constraint::Conflicts)
+    }
+
+    #[must_use]
+    pub fn exactly_one_of() -> Self {
+        Self::new(Constraint::ExactlyOne)
+    }
+
+    #[must_use]
+    pub fn at_least_one_of() -> Self {
+        Self::new(Constraint::AtLeastOne)
+    }
+
+    /// Add a parser to the group, members are added in the order of the constraint arguments
+    pub fn member<T, P>(&self, parser: P) -> ParseMember<P>
+    where
+        P: Parser<T>,
+    {
+        let mut metas = self.0.metas.borrow_mut();
+        metas.push(parser.meta());
+        ParseMember {
+            inner: parser,
+            group: self.0.clone(),
+            ix: metas.len() - 1,
+        }
+    }
+}
+
+/// Parser that records if it consumed anything for a [`GroupCheck`]
+#[doc(hidden)]
+pub struct ParseMember<P> {
+    inner: P,
+    group: Rc<Members>,
+    ix: usize,
+}
+
+impl<T, P> Parser<T> for ParseMember<P>
+where
+    P: Parser<T>,
+{
+    fn eval(&self, args: &mut State) -> Result<T, Error> {
+        let before = args.clone();
+        let res = self.inner.eval(args)?;
+        if let Some(present) = self.group.present.borrow_mut().get_mut(self.ix) {
+            *present = args.first_consumed_since(&before);
+        }
+        Ok(res)
+    }
+
+    fn meta(&self) -> Meta {
+        let meta = self.inner.meta();
+        let metas = self.group.metas.borrow();
+        // help mentions the constraint after the last member
+        if self.ix + 1 == metas.len() {
+            let help = describe(self.group.constraint, &metas);
+            Meta::Suffix(Box::new(meta), Box::new(help))
+        } else {
+            meta
+        }
+    }
+}
+
+/// Check group constraints after `inner` parser succeeds, created by the derive macro
+#[doc(hidden)]
+pub fn check_groups<T, P>(inner: P, groups: Vec<GroupCheck>) -> ParseGroupChecks<P>
+where
+    P: Parser<T>,
+{
+    ParseGroupChecks { inner, groups }
+}
+
+/// Parser that checks [`GroupCheck`] constraints on the result of the inner parser
+#[doc(hidden)]
+pub struct ParseGroupChecks<P> {
+    inner: P,
+    groups: Vec<GroupCheck>,
+}
+
+impl<T, P> Parser<T> for ParseGroupChecks<P>
+where
+    P: Parser<T>,
+{
+    fn eval(&self, args: &mut State) -> Result<T, Error> {
+        for GroupCheck(group) in &self.groups {
+            let len = group.metas.borrow().len();
+            *group.present.borrow_mut() = vec![None; len];
+        }
+        let res = self.inner.eval(args)?;
+
+        // partial command line can't satisfy the constraints yet
+        #[cfg(feature = "autocomplete")]
+        if args.comp_ref().is_some() {
+            return Ok(res);
+        }
+
+        for GroupCheck(group) in &self.groups {
+            let present = group.present.borrow();
+            if let Some(err) = check(group.constraint, &present, args, || {
+                group.metas.borrow().clone()
+            }) {
+                return Err(Error(err));
+            }
+        }
+        Ok(res)
+    }
+
+    fn meta(&self) -> Meta {
+        self.inner.meta()
+    }
+}
+
+/// Help message describing the constraint: `[--cert requires --key]`
+fn describe(constraint: Constraint, metas: &[Meta]) -> Note {
+    let literal = |meta: &Meta| {
+        let mut doc = Doc::default();
+        doc.literal(&member_name(meta));
+        doc
+    };
+    match constraint {
+        Constraint::Requires => {
+            Note::Text(Text::GroupRequires, metas.iter().map(literal).collect())
+        }
+        Constraint::Conflicts | Constraint::ExactlyOne | Constraint::AtLeastOne => {
+            let text = match constraint {
+                Constraint::Conflicts => Text::GroupAtMostOne,
+                Constraint::ExactlyOne => Text::GroupExactlyOne,
+                _ => Text::GroupAtLeastOne,
+            };
+            let mut list = Doc::default();
+            for (ix, meta) in metas.iter().enumerate() {
+                if ix > 0 {
+                    list.text(", ");
+                }
+                list.literal(&member_name(meta));
+            }
+            Note::Text(text, vec![list])
+        }
+    }
+}
+
+/// Check presence of group members against the constraint, `metas` are only used for errors
+fn check<F>(
+    constraint: Constraint,
+    present: &[Option<usize>],
+    args: &State,
+    metas: F,
+) -> Option<Message>
+where
+    F: FnOnce() -> Vec<Meta>,
+{
+    // present items in order they are given on the command line
+    let mut used = present.iter().copied().flatten().collect::<Vec<_>>();
+    used.sort_unstable();
+
+    match constraint {
+        Constraint::Requires => match present {
+            [Some(ix), None] => Some(Message::Requires(*ix, member_name(metas().get(1)?))),
+            _ => None,
+        },
+        Constraint::Conflicts | Constraint::ExactlyOne if used.len() > 1 => {
+            Some(Message::Conflict(used[0], used[1]))
+        }
+        Constraint::ExactlyOne | Constraint::AtLeastOne if used.is_empty() => {
+            let scope = args.scope();
+            let missing = metas()
+                .iter()
+                .filter_map(Meta::first_item)
+                .map(|item| MissingItem {
+                    item,
+                    position: scope.start,
+                    scope: scope.clone(),
+                })
+                .collect::<Vec<_>>();
+            if missing.is_empty() {
+                Some(Message::NoneOfGroup)
+            } else {
+                Some(Message::Missing(missing))
+            }
+        }
+        Constraint::Conflicts | Constraint::ExactlyOne | Constraint::AtLeastOne => None,
+    }
+}
+
+/// Strip `[]` from an item that is optional on its own but not as part of a group
+fn required(meta: Meta) -> Meta {
+    match meta {
+        Meta::Optional(inner) => *inner,
+        Meta::Suffix(inner, suffix) => Meta::Suffix(Box::new(required(*inner)), suffix),
+        Meta::Many(inner) => Meta::Many(Box::new(required(*inner))),
+        meta => meta,
+    }
+}
+
+/// Name of a group member for help and error messages
+fn member_name(meta: &Meta) -> String {
+    match Meta::first_item(meta) {
+        Some(item) => item_name(&item),
+        None => {
+            let mut doc = Doc::default();
+            doc.write_meta(meta, true);
+            doc.monochrome(false)
+        }
+    }
+}
@@ -1,13 +1,20 @@
 //! Help message generation and rendering
 
 use crate::{
-    args::{Args, State},
+    args::{Args, LongMatching, State},
+    buffer::Layout,
+    config::{Config, ConfigSource},
+    describe::CliSpec,
     error::Message,
+    locale::{self, Catalog, Locale, Text},
     meta_help::render_help,
     parsers::NamedArg,
-    short, Doc, Error, Meta, ParseFailure, Parser,
+    short, Doc, Error, Meta, ParseFailure, Parser, Provenance, Warning,
 };
 
+#[cfg(doc)]
+use crate::ValueSource;
+
 /// Information about the parser
 ///
 /// No longer public, users are only interacting with it via [`OptionParser`]
@@ -27,6 +34,21 @@ pub struct Info {
     pub help_arg: NamedArg,
     pub version_arg: NamedArg,
     pub help_if_no_args: bool,
+    /// Source of values for items missing from the command line, see
+    /// [`config_source`][OptionParser::config_source]
+    pub(crate) config: Option<Config>,
+    /// Catalog of built-in messages, see [`locale`][OptionParser::locale]
+    pub(crate) locale: Option<Catalog>,
+    /// Expand `@path` arguments, see [`response_files`][OptionParser::response_files]
+    pub(crate) response_files: bool,
+    /// Accept partial and differently cased long names, see
+    /// [`allow_abbreviations`][OptionParser::allow_abbreviations] and
+    /// [`case_insensitive_longs`][OptionParser::case_insensitive_longs]
+    pub(crate) long_matching: LongMatching,
+    /// Help width settings, see [`help_width`][OptionParser::help_width]
+    pub(crate) layout: Layout,
+    /// Show help with a pager, see [`pager`][OptionParser::pager]
+    pub(crate) pager: bool,
 }
 
 impl Default for Info {
@@ -37,11 +59,16 @@ impl Default for Info {
             header: None,
             footer: None,
             usage: None,
-            help_arg: short('h').long("help").help("Prints help information"),
-            version_arg: short('V')
-                .long("version")
-                .help("Prints version information"),
+            // descriptions come from the message catalog, see `mk_help_parser`
+            help_arg: short('h').long("help"),
+            version_arg: short('V').long("version"),
             help_if_no_args: false,
+            config: None,
+            locale: None,
+            response_files: false,
+            long_matching: LongMatching::default(),
+            layout: Layout::default(),
+            pager: false,
         }
     }
 }
@@ -85,9 +112,17 @@ impl<T> OptionParser<T> {
     where
         Self: Sized,
     {
-        match self.run_inner(Args::current_args()) {
-            Ok(t) => t,
-            Err(err) => std::process::exit(err.exit_code()),
+        match self.run_inner_with_warnings(Args::current_args()) {
+            Ok((t, warnings)) => {
+                for warning in &warnings {
+                    warning.print(self.info.layout, self.info.locale.as_ref());
+                }
+                t
+            }
+            Err(err) => {
+                let locale = self.info.locale.as_ref();
+                std::process::exit(err.print(self.info.layout, self.info.pager, locale))
+            }
         }
     }
 
@@ -193,28 +228,180 @@ impl<T> OptionParser<T> {
     where
         Self: Sized,
     {
+        Ok(self.run_top(args.into(), false)?.0)
+    }
+
+    /// Execute the [`OptionParser`] and report where each consumed value came from
+    ///
+    /// Works the same way as [`run_inner`](OptionParser::run_inner) but in addition to the
+    /// parsed value returns [`Provenance`] - a list of consumed items along with their
+    /// [`ValueSource`]: command line (with an index of the argument), environment variable,
+    /// config source, [`fallback`](Parser::fallback) or [`pure`](crate::pure).
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let threads = long("threads").argument::<usize>("N").fallback(4);
+    /// let verbose = short('v').switch();
+    /// let parser = construct!(threads, verbose).to_options();
+    ///
+    /// let (r, provenance) = parser.run_inner_with_provenance(&["-v"]).unwrap();
+    /// assert_eq!(r, (4, true));
+    /// assert_eq!(provenance.get("--threads"), Some(ValueSource::Fallback));
+    /// assert_eq!(provenance.get("-v"), Some(ValueSource::Cli(0)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`run_inner`](OptionParser::run_inner)
+    pub fn run_inner_with_provenance<'a>(
+        &self,
+        args: impl Into<Args<'a>>,
+    ) -> Result<(T, Provenance), ParseFailure>
+    where
+        Self: Sized,
+    {
+        let (res, mut state) = self.run_top(args.into(), true)?;
+        let entries = state.provenance.take().unwrap_or_default();
+        Ok((res, Provenance { entries }))
+    }
+
+    /// Execute the [`OptionParser`] and collect warnings produced while parsing
+    ///
+    /// Works the same way as [`run_inner`](OptionParser::run_inner) but in addition to the
+    /// parsed value returns a list of [`Warning`]s, for example about
+    /// [`deprecated`](crate::parsers::NamedArg::deprecated) items being used.
+    /// [`run`](OptionParser::run) prints those to `stderr`.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let quiet = short('q').long("quiet").deprecated("use --verbosity=0").switch();
+    /// let parser = quiet.to_options();
+    ///
+    /// let (r, warnings) = parser.run_inner_with_warnings(&["-q"]).unwrap();
+    /// assert!(r);
+    /// assert_eq!(warnings[0].to_string(), "`--quiet` is deprecated: use --verbosity=0");
+    ///
+    /// let (r, warnings) = parser.run_inner_with_warnings(&[]).unwrap();
+    /// assert!(!r);
+    /// assert!(warnings.is_empty());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`run_inner`](OptionParser::run_inner)
+    pub fn run_inner_with_warnings<'a>(
+        &self,
+        args: impl Into<Args<'a>>,
+    ) -> Result<(T, Vec<Warning>), ParseFailure>
+    where
+        Self: Sized,
+    {
+        let (res, mut state) = self.run_top(args.into(), false)?;
+        Ok((res, std::mem::take(&mut state.warnings)))
+    }
+
+    /// Parse arguments with a fresh state
+    ///
+    /// Failures carry width and pager settings so [`ParseFailure::exit_code`] can use them
+    fn run_top(&self, args: Args, provenance: bool) -> Result<(T, State), ParseFailure> {
+        let res = self.prepare_state(args).and_then(|mut state| {
+            if provenance {
+                state.provenance = Some(Vec::new());
+            }
+            let res = self.run_subparser(&mut state)?;
+            Ok((res, state))
+        });
+        res.map_err(|err| {
+            err.with_output(self.info.layout, self.info.pager, self.info.locale.as_ref())
+        })
+    }
+
+    fn prepare_state(&self, mut args: Args) -> Result<State, ParseFailure> {
+        let mut err = None;
+        if self.info.response_files {
+            args = match args.expand_response_files() {
+                Ok(args) => args,
+                Err(msg) => {
+                    err = Some(msg);
+                    Args::from(&[])
+                }
+            };
+        }
+
         // prepare available short flags and arguments for disambiguation
         let mut short_flags = Vec::new();
         let mut short_args = Vec::new();
-        self.inner
-            .meta()
-            .collect_shorts(&mut short_flags, &mut short_args);
+        let meta = self.inner.meta();
+        meta.collect_shorts(&mut short_flags, &mut short_args);
         short_flags.extend(&self.info.help_arg.short);
         short_flags.extend(&self.info.version_arg.short);
-        let args = args.into();
-        let mut err = None;
-        let mut state = State::construct(args, &short_flags, &short_args, &mut err);
+        let mut state = State::construct(
+            args,
+            &short_flags,
+            &short_args,
+            self.info.long_matching,
+            &mut err,
+        );
 
-        // this only handles disambiguation failure in construct
+        // this only handles response file and disambiguation failures in construct
         if let Some(msg) = err {
-            return Err(msg.render(&state, &self.inner.meta()));
+            state.locale = self.info.locale.clone();
+            return Err(msg.render(&state, &meta));
         }
-
-        self.run_subparser(&mut state)
+        Ok(state)
     }
 
     /// Run subparser, implementation detail
+    ///
+    /// Subparser's own config source and message catalog, if present, replace the parent's for
+    /// the duration, same goes for commands and long names available for abbreviated matching
     pub(crate) fn run_subparser(&self, args: &mut State) -> Result<T, ParseFailure> {
+        let parent_config = match &self.info.config {
+            Some(config) => Some(args.config.replace(config.clone())),
+            None => None,
+        };
+        let parent_locale = match &self.info.locale {
+            Some(locale) => Some(args.locale.replace(locale.clone())),
+            None => None,
+        };
+        let parent_names = if args.long_matching.enabled() {
+            let mut commands = Vec::new();
+            self.inner.meta().collect_commands(&mut commands);
+            let commands = std::mem::replace(&mut args.commands, commands.into());
+            let longs = std::mem::replace(&mut args.longs, self.collect_longs().into());
+            args.resolve_longs();
+            Some((commands, longs))
+        } else {
+            None
+        };
+        let res = self.run_subparser_inner(args);
+        if let Some((commands, longs)) = parent_names {
+            args.commands = commands;
+            args.longs = longs;
+        }
+        if let Some(config) = parent_config {
+            args.config = config;
+        }
+        if let Some(locale) = parent_locale {
+            args.locale = locale;
+        }
+        res
+    }
+
+    /// Long names available at this level, help and version included, but not the ones that
+    /// belong to commands
+    fn collect_longs(&self) -> Vec<String> {
+        let mut longs = Vec::new();
+        self.inner.meta().collect_longs(&mut longs);
+        let mut extra = self.info.help_arg.long.clone();
+        if self.info.version.is_some() {
+            extra.extend(&self.info.version_arg.long);
+        }
+        longs.extend(extra.iter().map(|l| (*l).to_owned()));
+        longs
+    }
+
+    fn run_subparser_inner(&self, args: &mut State) -> Result<T, ParseFailure> {
         // process should work like this:
         // - inner parser is evaluated, it returns Error
         // - if error is finalized (ParseFailure) - it is simply propagated outwards,
@@ -232,12 +419,18 @@ impl<T> OptionParser<T> {
                 &args.path,
                 &self.info,
                 &self.inner.meta(),
-                &self.info.meta(),
+                &self.info.help_meta(args.locale.as_ref()),
                 true,
+                args.config.as_ref(),
+                args.locale.as_ref(),
             );
-            return Err(ParseFailure::Stdout(buffer, false));
+            return Err(ParseFailure::Stdout(buffer.into(), false));
         };
 
+        // with prompts enabled parser might need to run a second time, asking for values that
+        // turned out to be missing
+        let original = args.prompts.as_ref().map(|_| args.clone());
+
         let res = self.inner.eval(args);
         if let Err(Error(Message::ParseFailure(failure))) = res {
             return Err(failure);
@@ -268,21 +461,30 @@ impl<T> OptionParser<T> {
                         &args.path,
                         &self.info,
                         &self.inner.meta(),
-                        &self.info.meta(),
+                        &self.info.help_meta(args.locale.as_ref()),
                         true,
+                        args.config.as_ref(),
+                        args.locale.as_ref(),
                     )
                 }
                 ExtraParams::Version(v) => {
                     use crate::buffer::{Block, Token};
                     let mut buffer = Doc::default();
                     buffer.token(Token::BlockStart(Block::Block));
-                    buffer.text("Version: ");
-                    buffer.doc(&v);
+                    let template = locale::get(args.locale.as_ref(), Text::Version);
+                    buffer.template(template, |buffer, _| buffer.doc(&v));
                     buffer.token(Token::BlockEnd(Block::Block));
                     buffer
                 }
             };
-            return Err(ParseFailure::Stdout(buffer, detailed));
+            return Err(ParseFailure::Stdout(buffer.into(), detailed));
+        }
+
+        if let Some(original) = original {
+            if args.prompts.as_ref().map_or(false, |p| p.request(&err)) {
+                *args = original;
+                return self.run_subparser_inner(args);
+            }
         }
         Err(err.render(args, &self.inner.meta()))
     }
@@ -647,28 +849,276 @@ impl<T> OptionParser<T> {
         self.info.help_if_no_args = true;
         self
     }
+
+    /// Read values missing from the command line from a config source
+    ///
+    /// With config source attached, named [`argument`](NamedArg::argument),
+    /// [`switch`](NamedArg::switch) and [`flag`](NamedArg::flag) parsers try the command line
+    /// first, then environment variables, then the config source keyed by the first long name
+    /// and only after that fall back to [`fallback`](crate::Parser::fallback) values.
+    ///
+    /// `--help` output mentions config keys that have values but not the values themselves
+    /// since those can be secrets, parse failures mention the config key. Subcommands use config source of the parent unless they have their own.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let config = |key: &str| match key {
+    ///     "threads" => Some("4".to_owned()),
+    ///     _ => None,
+    /// };
+    ///
+    /// let parser = long("threads")
+    ///     .argument::<usize>("N")
+    ///     .fallback(1)
+    ///     .to_options()
+    ///     .config_source(config);
+    ///
+    /// assert_eq!(parser.run_inner(&[]).unwrap(), 4);
+    /// ```
+    #[must_use]
+    pub fn config_source<C>(mut self, config: C) -> Self
+    where
+        C: ConfigSource + 'static,
+    {
+        self.info.config = Some(Config(std::rc::Rc::new(config)));
+        self
+    }
+
+    /// Replace built-in messages with a message catalog
+    ///
+    /// Catalog covers help section headers, default descriptions for `--help` and `--version`
+    /// and error messages, see [`Locale`] for details. Subcommands use the catalog of the parent
+    /// unless they have their own.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = short('v')
+    ///     .long("verbose")
+    ///     .switch()
+    ///     .to_options()
+    ///     .locale(locale::German);
+    ///
+    /// let r = parser.run_inner(&["--verbsoe"]).unwrap_err().unwrap_stderr();
+    /// assert_eq!(r, "unbekannte Option: `--verbsoe`, meinten Sie `--verbose`?");
+    /// ```
+    #[must_use]
+    pub fn locale<L>(mut self, locale: L) -> Self
+    where
+        L: Locale + 'static,
+    {
+        self.info.locale = Some(Catalog(std::rc::Rc::new(locale)));
+        self
+    }
+
+    /// Read arguments from response files given as `@path`
+    ///
+    /// With response files enabled every argument that looks like `@path` is replaced with
+    /// arguments read from that file. Arguments in the file are separated by whitespace and can
+    /// use shell-like quoting: `'single quotes'`, `"double \" quotes"` and backslash escapes,
+    /// lines starting with `#` are ignored. Response files can mention other response files,
+    /// nested paths are relative to the file that mentions them.
+    ///
+    /// Arguments after `--` are not expanded, including `--` that comes from a response file,
+    /// neither are arguments during shell completion.
+    /// Indices in [`ErrorKind`](crate::ErrorKind) and [`ValueSource::Cli`] refer to the
+    /// expanded list of arguments. Disabled by default.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// # let dir = std::env::temp_dir();
+    /// # let path = dir.join(format!("bpaf-doc-{}.rsp", std::process::id()));
+    /// # std::fs::write(&path, "--name 'Bob Smith'\n-v").unwrap();
+    /// let name = long("name").argument::<String>("NAME");
+    /// let verbose = short('v').switch();
+    /// let parser = construct!(name, verbose).to_options().response_files(true);
+    ///
+    /// let args = [format!("@{}", path.display())];
+    /// let r = parser.run_inner(&args[..]).unwrap();
+    /// assert_eq!(r, ("Bob Smith".to_owned(), true));
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    #[must_use]
+    pub fn response_files(mut self, enabled: bool) -> Self {
+        self.info.response_files = enabled;
+        self
+    }
+
+    /// Accept unambiguous prefixes of long names and commands
+    ///
+    /// With abbreviations enabled `--verb` is accepted as `--verbose` and `b` as `build`, as long
+    /// as there's no other long name or command that starts the same way. Exact matches always
+    /// win, ambiguous prefixes produce an error listing all the candidates. Both long names and
+    /// commands are resolved against the ones available at the same level: inside a subcommand
+    /// only its own long names count.
+    /// Shell completion is not affected. Set it on the top level parser, it applies to all the
+    /// subcommands.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let verbose = long("verbose").switch();
+    /// let verify = long("verify").switch();
+    /// let parser = construct!(verbose, verify).to_options().allow_abbreviations();
+    ///
+    /// let r = parser.run_inner(&["--verb"]).unwrap();
+    /// assert_eq!(r, (true, false));
+    ///
+    /// let r = parser.run_inner(&["--ver"]).unwrap_err().unwrap_stderr();
+    /// assert_eq!(r, "`--ver` is ambiguous, it could be `--verbose`, `--verify`");
+    /// ```
+    #[must_use]
+    pub fn allow_abbreviations(mut self) -> Self {
+        self.info.long_matching.abbreviations = true;
+        self
+    }
+
+    /// Match long names and commands ignoring their case
+    ///
+    /// With this setting `--VERBOSE` and `--Verbose` are accepted as `--verbose` and `BUILD` as
+    /// `build`. Exact matches always win. Combined with
+    /// [`allow_abbreviations`](OptionParser::allow_abbreviations) prefixes are also matched
+    /// ignoring case. Set it on the top level parser, it applies to all the subcommands.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let verbose = long("verbose").switch();
+    /// let parser = verbose.to_options().case_insensitive_longs();
+    ///
+    /// let r = parser.run_inner(&["--Verbose"]).unwrap();
+    /// assert!(r);
+    /// ```
+    #[must_use]
+    pub fn case_insensitive_longs(mut self) -> Self {
+        self.info.long_matching.case_insensitive = true;
+        self
+    }
+
+    /// Wrap help and error messages to fit into this many columns
+    ///
+    /// By default messages printed by [`run`](OptionParser::run) and
+    /// [`exit_code`](ParseFailure::exit_code) fit into the terminal width, as reported by the
+    /// terminal or `COLUMNS` environment variable, or wrap after 100 columns when width is not
+    /// known. This setting overrides the terminal width for messages printed by
+    /// [`run`](OptionParser::run), lines are wrapped so they fit into `width` columns.
+    /// Set it on the top level parser, it applies to all the subcommands.
+    ///
+    /// ```no_run
+    /// # use bpaf::*;
+    /// let name = long("name")
+    ///     .help("Name of the person to greet")
+    ///     .argument::<String>("NAME")
+    ///     .to_options()
+    ///     .help_width(60)
+    ///     .max_tab(16)
+    ///     .run();
+    /// ```
+    #[must_use]
+    pub fn help_width(mut self, width: usize) -> Self {
+        self.info.layout.set_width(width);
+        self
+    }
+
+    /// Align descriptions of options and commands at most this many columns from the names
+    ///
+    /// Names wider than this get descriptions right after them instead. Defaults to 24,
+    /// smaller values leave more space for descriptions on narrow terminals. Same as with
+    /// [`help_width`](OptionParser::help_width) only applies to messages printed by
+    /// [`run`](OptionParser::run).
+    #[must_use]
+    pub fn max_tab(mut self, width: usize) -> Self {
+        self.info.layout.set_max_tab(width);
+        self
+    }
+
+    /// Describe the parser in a machine readable format
+    ///
+    /// Description covers all the items the parser consumes along with their help messages,
+    /// environment variables and subcommands, including hidden ones, see [`CliSpec`] for
+    /// details. [`CliSpec::to_json`] renders it as JSON.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// use bpaf::describe::Node;
+    /// let verbose = short('v').help("Print more details").switch();
+    /// let name = positional::<String>("NAME").optional();
+    /// let parser = construct!(verbose, name).to_options().descr("Greeter");
+    ///
+    /// let spec = parser.describe();
+    /// assert_eq!(spec.descr.as_deref(), Some("Greeter"));
+    /// assert!(matches!(&spec.args, Node::And(xs) if xs.len() == 2));
+    /// assert_eq!(
+    ///     spec.args.to_json(),
+    ///     r#"{"kind":"and","items":[{"kind":"optional","item":{"kind":"flag","short":"v","long":null,"env":[],"metavar":null,"help":"Print more details","deprecated":false,"negatable":false,"opt_value":false}},{"kind":"optional","item":{"kind":"positional","metavar":"NAME","help":null}}]}"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn describe(&self) -> CliSpec {
+        CliSpec::new(&self.info, &self.inner.meta())
+    }
+
+    /// Show help messages with a pager
+    ///
+    /// When enabled and `stdout` is a terminal [`run`](OptionParser::run) sends help messages
+    /// through a program set in `PAGER` environment variable, `less -R` by default. Colors are
+    /// kept, so the pager needs to understand them. Help is printed as usual if pager can't be
+    /// started or `PAGER` is set to an empty string. Disabled by default.
+    ///
+    /// ```no_run
+    /// # use bpaf::*;
+    /// let verbose = short('v')
+    ///     .help("Print more details")
+    ///     .switch()
+    ///     .to_options()
+    ///     .pager(true)
+    ///     .run();
+    /// ```
+    #[must_use]
+    pub fn pager(mut self, enabled: bool) -> Self {
+        self.info.pager = enabled;
+        self
+    }
 }
 
 impl Info {
+    /// `--help` parser, default description comes from the message catalog
     #[inline(never)]
-    fn mk_help_parser(&self) -> impl Parser<()> {
-        self.help_arg.clone().req_flag(())
+    fn mk_help_parser(&self, locale: Option<&Catalog>) -> impl Parser<()> {
+        let mut arg = self.help_arg.clone();
+        if arg.help.is_none() {
+            arg = arg.help(locale::get(locale, Text::HelpDescr));
+        }
+        arg.req_flag(())
     }
+
+    /// `--version` parser, default description comes from the message catalog
     #[inline(never)]
-    fn mk_version_parser(&self) -> impl Parser<()> {
-        self.version_arg.clone().req_flag(())
+    fn mk_version_parser(&self, locale: Option<&Catalog>) -> impl Parser<()> {
+        let mut arg = self.version_arg.clone();
+        if arg.help.is_none() {
+            arg = arg.help(locale::get(locale, Text::VersionDescr));
+        }
+        arg.req_flag(())
+    }
+
+    /// Meta for `--help` and `--version` parsers
+    pub(crate) fn help_meta(&self, locale: Option<&Catalog>) -> Meta {
+        let help = self.mk_help_parser(locale).meta();
+        match &self.version {
+            Some(_) => Meta::And(vec![help, self.mk_version_parser(locale).meta()]),
+            None => help,
+        }
     }
 }
 
 impl Parser<ExtraParams> for Info {
     fn eval(&self, args: &mut State) -> Result<ExtraParams, Error> {
-        let help = self.mk_help_parser();
+        let locale = args.locale.clone();
+        let help = self.mk_help_parser(locale.as_ref());
         if help.eval(args).is_ok() {
             return Ok(ExtraParams::Help(help.eval(args).is_ok()));
         }
 
         if let Some(version) = &self.version {
-            if self.mk_version_parser().eval(args).is_ok() {
+            if self.mk_version_parser(locale.as_ref()).eval(args).is_ok() {
                 return Ok(ExtraParams::Version(version.clone()));
             }
         }
@@ -678,11 +1128,7 @@ impl Parser<ExtraParams> for Info {
     }
 
     fn meta(&self) -> Meta {
-        let help = self.mk_help_parser().meta();
-        match &self.version {
-            Some(_) => Meta::And(vec![help, self.mk_version_parser().meta()]),
-            None => help,
-        }
+        self.help_meta(self.locale.as_ref())
     }
 }
 
@@ -18,6 +18,7 @@ pub enum Item {
         help: Option<Doc>,
         meta: Box<Meta>,
         info: Box<Info>,
+        deprecated: bool,
     },
     /// short or long name, consumed anywhere
     /// -f
@@ -26,8 +27,14 @@ pub enum Item {
         name: ShortLong,
         /// used for disambiguation
         shorts: Vec<char>,
-        env: Option<&'static str>,
+        /// used for long name matching
+        longs: Vec<&'static str>,
+        /// environment variables in order they are checked
+        env: Vec<&'static str>,
         help: Option<Doc>,
+        deprecated: bool,
+        /// accepts `--no-` versions of long names
+        negatable: bool,
     },
     /// Short or long name followed by a value, consumed anywhere
     /// -f <VAL>
@@ -36,9 +43,15 @@ pub enum Item {
         name: ShortLong,
         /// used for disambiguation
         shorts: Vec<char>,
+        /// used for long name matching
+        longs: Vec<&'static str>,
         metavar: Metavar,
-        env: Option<&'static str>,
+        /// environment variables in order they are checked
+        env: Vec<&'static str>,
         help: Option<Doc>,
+        deprecated: bool,
+        /// value is optional and can only be attached to the name: `--color[=WHEN]`
+        opt_value: bool,
     },
 }
 
@@ -54,6 +67,12 @@ impl Item {
     pub(crate) fn normalize(&mut self, short: bool) {
         match self {
             Item::Positional { .. } | Item::Command { .. } | Item::Any { .. } => {}
+            // `--[no-]flag` shows both spellings, short name can't do that
+            Item::Flag {
+                name,
+                negatable: true,
+                ..
+            } => name.normalize(false),
             Item::Flag { name, .. } | Item::Argument { name, .. } => name.normalize(short),
         }
     }
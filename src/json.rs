@@ -0,0 +1,133 @@
+//! Minimal JSON writer used for machine readable output, `bpaf` has no dependencies
+
+use std::fmt::Write;
+
+/// Write a string as a JSON string literal, with quotes
+pub(crate) fn write_str(res: &mut String, s: &str) {
+    res.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(res, "\\u{:04x}", c as u32);
+            }
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+}
+
+/// JSON object writer, keys are written in the order they are added
+#[derive(Default)]
+pub(crate) struct Object(String);
+
+impl Object {
+    fn key(&mut self, key: &str) {
+        self.0.push(if self.0.is_empty() { '{' } else { ',' });
+        write_str(&mut self.0, key);
+        self.0.push(':');
+    }
+
+    pub(crate) fn str(&mut self, key: &str, val: &str) {
+        self.key(key);
+        write_str(&mut self.0, val);
+    }
+
+    pub(crate) fn opt_str(&mut self, key: &str, val: Option<&str>) {
+        match val {
+            Some(val) => self.str(key, val),
+            None => {
+                self.key(key);
+                self.0.push_str("null");
+            }
+        }
+    }
+
+    pub(crate) fn num(&mut self, key: &str, val: usize) {
+        self.key(key);
+        let _ = write!(self.0, "{}", val);
+    }
+
+    pub(crate) fn opt_num(&mut self, key: &str, val: Option<usize>) {
+        match val {
+            Some(val) => self.num(key, val),
+            None => {
+                self.key(key);
+                self.0.push_str("null");
+            }
+        }
+    }
+
+    pub(crate) fn bool(&mut self, key: &str, val: bool) {
+        self.key(key);
+        self.0.push_str(if val { "true" } else { "false" });
+    }
+
+    /// Value that is already rendered as JSON
+    pub(crate) fn raw(&mut self, key: &str, val: &str) {
+        self.key(key);
+        self.0.push_str(val);
+    }
+
+    /// List of values that are already rendered as JSON
+    pub(crate) fn raw_list(&mut self, key: &str, vals: &[String]) {
+        self.key(key);
+        self.0.push('[');
+        for (ix, val) in vals.iter().enumerate() {
+            if ix > 0 {
+                self.0.push(',');
+            }
+            self.0.push_str(val);
+        }
+        self.0.push(']');
+    }
+
+    pub(crate) fn list(&mut self, key: &str, vals: &[String]) {
+        self.key(key);
+        self.0.push('[');
+        for (ix, val) in vals.iter().enumerate() {
+            if ix > 0 {
+                self.0.push(',');
+            }
+            write_str(&mut self.0, val);
+        }
+        self.0.push(']');
+    }
+
+    pub(crate) fn finish(mut self) -> String {
+        if self.0.is_empty() {
+            self.0.push('{');
+        }
+        self.0.push('}');
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_str, Object};
+
+    #[test]
+    fn escapes() {
+        let mut res = String::new();
+        write_str(&mut res, "a \"b\"\\\n\u{1}ü");
+        assert_eq!(res, r#""a \"b\"\\\n\u0001ü""#);
+    }
+
+    #[test]
+    fn object() {
+        let mut obj = Object::default();
+        obj.str("kind", "missing");
+        obj.opt_num("index", None);
+        obj.list("expected", &["--a".to_owned(), "B".to_owned()]);
+        assert_eq!(
+            obj.finish(),
+            r#"{"kind":"missing","index":null,"expected":["--a","B"]}"#
+        );
+        assert_eq!(Object::default().finish(), "{}");
+    }
+}
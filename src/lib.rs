@@ -170,23 +170,41 @@ mod args;
 pub mod batteries;
 mod buffer;
 #[cfg(feature = "autocomplete")]
+mod complete_cache;
+#[cfg(feature = "autocomplete")]
+mod complete_context;
+#[cfg(feature = "autocomplete")]
 mod complete_gen;
 #[cfg(feature = "autocomplete")]
 mod complete_run;
 #[cfg(feature = "autocomplete")]
 mod complete_shell;
+#[cfg(feature = "autocomplete")]
+mod complete_static;
+mod config;
+pub mod describe;
 pub mod doc;
 mod error;
 mod from_os_str;
+mod group;
 mod info;
 mod item;
+mod json;
+pub mod locale;
 mod meta;
 mod meta_help;
 mod meta_youmean;
+mod pager;
 pub mod params;
+mod prompt;
+mod provenance;
+mod response;
 mod structs;
 #[cfg(test)]
 mod tests;
+mod validate;
+mod value_enum;
+mod warning;
 
 pub mod parsers {
     //! This module exposes parsers that accept further configuration with builder pattern
@@ -195,44 +213,78 @@ pub mod parsers {
     //! access to documentation
     #[cfg(feature = "autocomplete")]
     #[doc(inline)]
+    pub use crate::complete_context::WithContext;
+    #[cfg(feature = "autocomplete")]
+    #[doc(inline)]
     pub use crate::complete_shell::ParseCompShell;
     #[doc(inline)]
+    pub use crate::group::{Group, ParseGroup};
+    #[doc(inline)]
     pub use crate::params::{
         NamedArg, ParseAny, ParseArgument, ParseCommand, ParseFlag, ParsePositional,
     };
+    #[cfg(feature = "autocomplete")]
+    #[doc(inline)]
+    pub use crate::structs::ParseComp;
     #[doc(inline)]
     pub use crate::structs::{
         ParseCollect, ParseCon, ParseCount, ParseFallback, ParseFallbackWith, ParseLast, ParseMany,
         ParseOptional, ParseSome,
     };
+    #[doc(inline)]
+    pub use crate::value_enum::ParsePossibleValues;
 }
 
 // -------------------------------------------------------------------
 
 #[doc(inline)]
-pub use crate::{args::Args, buffer::Doc, error::ParseFailure, info::OptionParser};
+pub use crate::{
+    args::Args,
+    buffer::Doc,
+    config::ConfigSource,
+    error::{ErrorKind, Output, ParseFailure},
+    info::OptionParser,
+    locale::Locale,
+    provenance::{Provenance, ValueSource},
+    value_enum::ValueEnum,
+    warning::Warning,
+};
 
 #[doc(hidden)]
-// used by construct macro, not part of public API
-pub use crate::{args::State, error::Error, meta::Meta, structs::ParseCon};
+// used by construct macro and derive, not part of public API
+pub use crate::{
+    args::State,
+    error::Error,
+    group::{check_groups, GroupCheck},
+    meta::Meta,
+    structs::ParseCon,
+    value_enum::unknown_value,
+};
 
 use std::{marker::PhantomData, str::FromStr};
 
 use crate::{
     buffer::{MetaInfo, Style},
+    group::Constraint,
     item::Item,
     params::build_positional,
-    parsers::{NamedArg, ParseAny, ParseCommand, ParsePositional},
+    parsers::{Group, NamedArg, ParseAny, ParseCommand, ParseGroup, ParsePositional},
     structs::{
         ParseCollect, ParseCount, ParseFail, ParseFallback, ParseFallbackWith, ParseGroupHelp,
-        ParseGuard, ParseHide, ParseLast, ParseMany, ParseMap, ParseOptional, ParseOrElse,
-        ParsePure, ParsePureWith, ParseSome, ParseUsage, ParseWith, ParseWithGroupHelp,
+        ParseGuard, ParseGuardWith, ParseHide, ParseLast, ParseMany, ParseMap, ParseOptional,
+        ParseOrElse, ParsePure, ParsePureWith, ParseSome, ParseUsage, ParseWith,
+        ParseWithGroupHelp,
     },
+    value_enum::ParsePossibleValues,
 };
 
+#[cfg(feature = "autocomplete")]
+pub use crate::complete_context::CompContext;
 #[cfg(feature = "autocomplete")]
 pub use crate::complete_shell::ShellComp;
 #[cfg(feature = "autocomplete")]
+pub use crate::complete_static::Shell;
+#[cfg(feature = "autocomplete")]
 use structs::ParseComp;
 
 #[doc(inline)]
@@ -869,6 +921,40 @@ pub trait Parser<T> {
             message,
         }
     }
+
+    /// Validate or fail with a message produced by the check
+    ///
+    /// Same as [`guard`](Parser::guard), but the check returns the error message so it can
+    /// mention the value. For common checks on arguments and positionals see
+    /// [`range`](crate::parsers::ParseArgument::range),
+    /// [`min_len`](crate::parsers::ParseArgument::min_len) and
+    /// [`matches`](crate::parsers::ParseArgument::matches).
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = long("jobs")
+    ///     .argument::<u32>("N")
+    ///     .guard_with(|n| {
+    ///         if *n <= 64 {
+    ///             Ok(())
+    ///         } else {
+    ///             Err(format!("at most 64 jobs are supported, got {}", n))
+    ///         }
+    ///     })
+    ///     .to_options();
+    ///
+    /// let r = parser.run_inner(&["--jobs", "100"]).unwrap_err().unwrap_stderr();
+    /// assert_eq!(r, "`100`: at most 64 jobs are supported, got 100");
+    /// ```
+    #[must_use]
+    fn guard_with<F, E>(self, check: F) -> ParseGuardWith<Self, F>
+    where
+        Self: Sized + Parser<T>,
+        F: Fn(&T) -> Result<(), E>,
+        E: ToString,
+    {
+        ParseGuardWith { inner: self, check }
+    }
     // }}}
 
     // combine
@@ -1095,6 +1181,40 @@ pub trait Parser<T> {
         ParseWithGroupHelp { inner: self, f }
     }
 
+    /// List accepted values of a [`ValueEnum`] in help and offer them in shell completion
+    ///
+    /// Values are listed after the help for the item as `[possible values: fast, slow]`, with
+    /// `autocomplete` feature enabled they are also offered as completion candidates with
+    /// variant doc comments as descriptions. See [`ValueEnum`] for an example.
+    ///
+    /// # Derive usage
+    /// ```rust
+    /// # use bpaf::*;
+    /// #[derive(Debug, Clone, Copy, Bpaf)]
+    /// #[bpaf(value_enum)]
+    /// enum Mode {
+    ///     Fast,
+    ///     Slow,
+    /// }
+    ///
+    /// #[derive(Debug, Clone, Bpaf)]
+    /// struct Options {
+    ///     /// Processing mode
+    ///     #[bpaf(argument("MODE"), possible_values)]
+    ///     mode: Mode,
+    /// }
+    /// ```
+    fn possible_values(self) -> ParsePossibleValues<Self, T>
+    where
+        Self: Sized + Parser<T>,
+        T: ValueEnum,
+    {
+        ParsePossibleValues {
+            inner: self,
+            ty: PhantomData,
+        }
+    }
+
     // {{{ comp
     /// Dynamic shell completion
     ///
@@ -1134,6 +1254,56 @@ pub trait Parser<T> {
             inner: self,
             op,
             group: None,
+            cache: crate::complete_cache::CompCache::default(),
+        }
+    }
+
+    /// Dynamic shell completion that depends on other arguments
+    ///
+    /// Works like [`complete`](Parser::complete), but the completion function also gets a
+    /// [`CompContext`] with the rest of the command line, so suggestions for `--branch`
+    /// can depend on `--repo` the user already typed.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use bpaf::*;
+    /// fn branches(input: &String, ctx: &CompContext) -> Vec<(String, Option<String>)> {
+    ///     let repo = ctx.value::<String>(&long("repo")).unwrap_or_else(|| ".".to_owned());
+    ///     let known: &[&str] = if repo == "bpaf" { &["master", "docs"] } else { &["main"] };
+    ///     known
+    ///         .iter()
+    ///         .filter(|b| b.starts_with(input.as_str()))
+    ///         .map(|b| (b.to_string(), None))
+    ///         .collect()
+    /// }
+    ///
+    /// let repo = long("repo").argument::<String>("REPO");
+    /// let branch = long("branch")
+    ///     .argument::<String>("BRANCH")
+    ///     .complete_with_context(branches);
+    /// let parser = construct!(repo, branch).to_options();
+    ///
+    /// let r = parser
+    ///     .run_inner(Args::from(&["--repo", "bpaf", "--branch", "d"]).set_comp(0))
+    ///     .unwrap_err()
+    ///     .unwrap_stdout();
+    /// assert_eq!(r, "docs");
+    /// ```
+    #[cfg(feature = "autocomplete")]
+    fn complete_with_context<M, F>(
+        self,
+        op: F,
+    ) -> ParseComp<Self, crate::complete_context::WithContext<F>>
+    where
+        M: Into<String>,
+        F: Fn(&T, &CompContext) -> Vec<(M, Option<M>)>,
+        Self: Sized + Parser<T>,
+    {
+        ParseComp {
+            inner: self,
+            op: crate::complete_context::WithContext(op),
+            group: None,
+            cache: crate::complete_cache::CompCache::default(),
         }
     }
     // }}}
@@ -1333,6 +1503,7 @@ pub fn short(short: char) -> NamedArg {
         env: Vec::new(),
         long: Vec::new(),
         help: None,
+        deprecated: None,
     }
 }
 
@@ -1350,6 +1521,7 @@ pub fn long(long: &'static str) -> NamedArg {
         long: vec![long],
         env: Vec::new(),
         help: None,
+        deprecated: None,
     }
 }
 
@@ -1379,6 +1551,7 @@ pub fn env(variable: &'static str) -> NamedArg {
         long: Vec::new(),
         help: None,
         env: vec![variable],
+        deprecated: None,
     }
 }
 
@@ -1442,6 +1615,7 @@ where
         help: subparser.short_descr().map(Into::into),
         subparser,
         adjacent: false,
+        deprecated: None,
     }
 }
 
@@ -1552,3 +1726,167 @@ pub fn choice<T: 'static>(parsers: impl IntoIterator<Item = Box<dyn Parser<T>>>)
     }
     this
 }
+
+/// Require one item to be used together with another one
+///
+/// Produces values of both parsers, fails if `item` is present on the command line but
+/// `required` is not. Values from environment variables, config sources or fallbacks don't make
+/// an item present. Usage shows the items as `[[--cert=CERT] --key=KEY]`, help message mentions
+/// the constraint.
+///
+/// ```rust
+/// # use bpaf::*;
+/// # use std::path::PathBuf;
+/// let cert = long("cert").argument::<PathBuf>("CERT").optional();
+/// let key = long("key").argument::<PathBuf>("KEY").optional();
+/// let parser = requires(cert, key).to_options();
+///
+/// let r = parser.run_inner(&["--cert", "a.pem", "--key", "a.key"]).unwrap();
+/// assert_eq!(r, (Some(PathBuf::from("a.pem")), Some(PathBuf::from("a.key"))));
+///
+/// let r = parser.run_inner(&["--key", "a.key"]).unwrap();
+/// assert_eq!(r, (None, Some(PathBuf::from("a.key"))));
+///
+/// let r = parser.run_inner(&["--cert", "a.pem"]).unwrap_err().unwrap_stderr();
+/// assert_eq!(r, "`--cert` requires `--key`");
+/// ```
+#[must_use]
+pub fn requires<A, B, PA, PB>(item: PA, required: PB) -> ParseGroup<(PA, PB)>
+where
+    PA: Parser<A>,
+    PB: Parser<B>,
+{
+    ParseGroup {
+        members: (item, required),
+        constraint: Constraint::Requires,
+    }
+}
+
+/// Allow at most one of several items to be used
+///
+/// Takes a tuple of 2 to 8 parsers and produces a tuple of their values, fails if more than one
+/// item is present on the command line. Values from environment variables, config sources or
+/// fallbacks don't make an item present. Usage shows the items as `[--json | --yaml]`, help
+/// message mentions the constraint.
+///
+/// To pick a single value out of several alternatives use [`construct!`] with `[]` instead.
+///
+/// ```rust
+/// # use bpaf::*;
+/// let json = long("json").help("Output JSON").switch();
+/// let yaml = long("yaml").help("Output YAML").switch();
+/// let toml = long("toml").help("Output TOML").switch();
+/// let parser = conflicts((json, yaml, toml)).to_options();
+///
+/// let r = parser.run_inner(&["--yaml"]).unwrap();
+/// assert_eq!(r, (false, true, false));
+///
+/// let r = parser.run_inner(&[]).unwrap();
+/// assert_eq!(r, (false, false, false));
+///
+/// let r = parser.run_inner(&["--toml", "--json"]).unwrap_err().unwrap_stderr();
+/// assert_eq!(r, "`--json` cannot be used at the same time as `--toml`");
+///
+/// let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+/// let expected = "\
+/// Usage: [--json | --yaml | --toml]
+///
+/// Available options:
+///         --json  Output JSON
+///         --yaml  Output YAML
+///         --toml  Output TOML
+///                 [at most one of --json, --yaml, --toml]
+///     -h, --help  Prints help information
+/// ";
+/// assert_eq!(r, expected);
+/// ```
+///
+/// # Derive usage
+///
+/// `requires`, `conflicts`, `exactly_one_of` and `at_least_one_of` annotations on a struct take
+/// names of the fields to group, each field can be a part of only one group. Fields keep their
+/// order in usage and help, help mentions the constraint after the last field of the group.
+///
+/// ```rust
+/// # use bpaf::*;
+/// #[derive(Debug, Clone, Bpaf)]
+/// #[bpaf(options, conflicts(json, yaml), requires(cert, key))]
+/// struct Options {
+///     json: bool,
+///     yaml: bool,
+///     #[bpaf(argument("CERT"))]
+///     cert: Option<String>,
+///     #[bpaf(argument("KEY"))]
+///     key: Option<String>,
+/// }
+///
+/// let r = options().run_inner(&["--json", "--yaml"]).unwrap_err().unwrap_stderr();
+/// assert_eq!(r, "`--yaml` cannot be used at the same time as `--json`");
+/// ```
+#[must_use]
+pub fn conflicts<T, G>(members: G) -> ParseGroup<G>
+where
+    G: Group<T>,
+{
+    ParseGroup {
+        members,
+        constraint: Constraint::Conflicts,
+    }
+}
+
+/// Require exactly one of several items to be used
+///
+/// Same as [`conflicts`], but also fails if none of the items are present. Usage shows the items
+/// as `(--json | --yaml)`.
+///
+/// ```rust
+/// # use bpaf::*;
+/// let json = long("json").switch();
+/// let yaml = long("yaml").switch();
+/// let parser = exactly_one_of((json, yaml)).to_options();
+///
+/// let r = parser.run_inner(&["--json"]).unwrap();
+/// assert_eq!(r, (true, false));
+///
+/// let r = parser.run_inner(&[]).unwrap_err().unwrap_stderr();
+/// assert_eq!(r, "expected `--json` or `--yaml`, pass `--help` for usage information");
+/// ```
+#[must_use]
+pub fn exactly_one_of<T, G>(members: G) -> ParseGroup<G>
+where
+    G: Group<T>,
+{
+    ParseGroup {
+        members,
+        constraint: Constraint::ExactlyOne,
+    }
+}
+
+/// Require at least one of several items to be used
+///
+/// Takes a tuple of 2 to 8 parsers and produces a tuple of their values, fails if none of the
+/// items are present on the command line. Values from environment variables, config sources or
+/// fallbacks don't make an item present. Usage shows the items as `(--user=USER | --all)...`.
+///
+/// ```rust
+/// # use bpaf::*;
+/// let user = long("user").argument::<String>("USER").many();
+/// let all = long("all").switch();
+/// let parser = at_least_one_of((user, all)).to_options();
+///
+/// let r = parser.run_inner(&["--user", "alice", "--all"]).unwrap();
+/// assert_eq!(r, (vec!["alice".to_owned()], true));
+///
+/// let r = parser.run_inner(&[]).unwrap_err().unwrap_stderr();
+/// assert_eq!(r, "expected `--user=USER` or `--all`, pass `--help` for usage information");
+/// ```
+#[must_use]
+pub fn at_least_one_of<T, G>(members: G) -> ParseGroup<G>
+where
+    G: Group<T>,
+{
+    ParseGroup {
+        members,
+        constraint: Constraint::AtLeastOne,
+    }
+}
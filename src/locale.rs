@@ -0,0 +1,453 @@
+//! Localization of built-in messages, see [`Locale`]
+//!
+//! Contains message identifiers and bundled message catalogs
+
+use std::rc::Rc;
+
+use crate::buffer::Doc;
+
+/// Built-in message that can be replaced by a [`Locale`]
+///
+/// Messages are templates, placeholders `{0}`, `{1}`, ... are replaced with message specific
+/// values such as names of command line items, English version of each template is listed
+/// next to the variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Text {
+    /// `Usage`, help message section header
+    Usage,
+    /// `Available positional items:`, help message section header
+    AvailablePositional,
+    /// `Available options:`, help message section header
+    AvailableOptions,
+    /// `Available commands:`, help message section header
+    AvailableCommands,
+    /// `Prints help information`, description of `--help`
+    HelpDescr,
+    /// `Prints version information`, description of `--version`
+    VersionDescr,
+    /// `Version: {0}`, output of `--version`
+    Version,
+    /// `[env:{0}: set]`, help for a flag with environment variable set
+    EnvSet,
+    /// `[env:{0}: not set]`, help for a flag with environment variable not set
+    EnvNotSet,
+    /// `[env:{0} = {1}]`, help for an argument with environment variable set
+    EnvValue,
+    /// `[env:{0}: N/A]`, help for an argument with environment variable not set
+    EnvMissing,
+    /// `Uses environment variable {0}`, environment variable in generated documentation
+    UsesEnv,
+    /// `[config:{0}]`, help for an argument with a value in a config source
+    ConfigKey,
+    /// `[{0} requires {1}]`, help for a group where one item requires another
+    GroupRequires,
+    /// `[at most one of {0}]`, help for a group of conflicting items
+    GroupAtMostOne,
+    /// `[exactly one of {0}]`, help for a group where exactly one item is required
+    GroupExactlyOne,
+    /// `[at least one of {0}]`, help for a group where at least one item is required
+    GroupAtLeastOne,
+    /// `[possible values: {0}]`, help for an argument with a fixed set of values
+    PossibleValues,
+
+    /// `{0} is not expected in this context`
+    NotExpected,
+    /// `environment variable {0} is not set`
+    NoEnv,
+    /// `expected {0} to be on the right side of {1}`
+    StrictPos,
+    /// `couldn't parse {0}: {1}`, value and error message
+    ParseFailed,
+    /// `couldn't parse: {0}`, error message when value is not known
+    ParseFailedNoValue,
+    /// `couldn't parse config value {0}: {1}`, config key and error message
+    ConfigFailed,
+    /// `{0}: {1}`, value and guard message
+    GuardFailed,
+    /// `check failed: {0}`, guard message when value is not known
+    GuardFailedNoValue,
+    /// `{0} requires an argument {1}`
+    NoArgument,
+    /// `{0} requires an argument {1}, got a flag {2}, try {3} to use it as an argument`
+    NoArgumentGotFlag,
+    /// `{0} supports {1} as both an option and an option-argument, try to split {2} into
+    /// individual options ({3}) or use {4} syntax to disambiguate`
+    Ambiguity,
+    /// `app`, program name used when it is not known
+    App,
+    /// `no such flag: {0}, did you mean {1}?`
+    NoSuchFlag,
+    /// `no such argument value: {0}, did you mean {1}?`
+    NoSuchValue,
+    /// `no such command or positional: {0}, did you mean {1}?`
+    NoSuchCommand,
+    /// `no such flag: {0} (with one dash), did you mean {1}?`
+    MissingDash,
+    /// `no such flag: {0} (with two dashes), did you mean {1}?`
+    ExtraDash,
+    /// `subcommand {0} is not valid in this context, did you mean to pass it to command {1}?`
+    NestedCommand,
+    /// `flag {0} is not valid in this context, did you mean to pass it to command {1}?`
+    NestedFlag,
+    /// `{0} is ambiguous, it could be {1}`, abbreviated name and a list of names it matches
+    AmbiguousPrefix,
+    /// `expected {0}, pass {1} for usage information`
+    Expected,
+    /// `expected {0}, got {1}. Pass {2} for usage information`
+    ExpectedGot,
+    /// `no arguments`, used as `{0}` in [`Expected`](Text::Expected)
+    NoArguments,
+    /// `{0} or {1}`, used as `{0}` in [`Expected`](Text::Expected)
+    OneOfTwo,
+    /// `{0}, {1}, or more`, used as `{0}` in [`Expected`](Text::Expected)
+    OneOfMany,
+    /// `{0} cannot be used at the same time as {1}`
+    Conflict,
+    /// `{0} requires {1}`
+    Requires,
+    /// `argument {0} cannot be used multiple times in this context`
+    OnlyOnce,
+    /// `argument {0} expects a single value`, delimited value has elements nobody consumed
+    SingleValue,
+    /// `response file {0}: {1}`, path and reason
+    ResponseFile,
+    /// `response file {0}, line {1}: {2}`, path, line and reason
+    ResponseFileLine,
+    /// `unterminated quote`, used as a reason in [`ResponseFileLine`](Text::ResponseFileLine)
+    UnterminatedQuote,
+    /// `file includes itself`, used as a reason in [`ResponseFile`](Text::ResponseFile)
+    ResponseFileCycle,
+    /// `files are nested too deep`, used as a reason in [`ResponseFile`](Text::ResponseFile)
+    ResponseFileDepth,
+    /// `(deprecated)`, help for a deprecated item
+    Deprecated,
+    /// `{0} is deprecated: {1}`, warning about a deprecated item being used
+    DeprecatedUsed,
+    /// `must be in range {0}`, used as a reason in [`GuardFailed`](Text::GuardFailed)
+    OutOfRange,
+    /// `must be at least {0} characters long`, used as a reason in
+    /// [`GuardFailed`](Text::GuardFailed)
+    TooShort,
+    /// `not a valid {0}`, used as a reason in [`GuardFailed`](Text::GuardFailed)
+    NotValid,
+    /// `none of the required items are present`, group with no known items is not satisfied
+    NoneOfGroup,
+    /// `expected one of {0}`, used as a reason in [`ParseFailed`](Text::ParseFailed)
+    ExpectedOneOf,
+    /// `expected one of {0}, did you mean {1}?`, used as a reason in
+    /// [`ParseFailed`](Text::ParseFailed)
+    ExpectedOneOfSuggest,
+    /// `Error:`, printed before an error message
+    Error,
+    /// `Warning:`, printed before a warning
+    Warning,
+}
+
+impl Text {
+    /// English version of the message
+    #[must_use]
+    pub fn english(self) -> &'static str {
+        match self {
+            Text::Usage => "Usage",
+            Text::AvailablePositional => "Available positional items:",
+            Text::AvailableOptions => "Available options:",
+            Text::AvailableCommands => "Available commands:",
+            Text::HelpDescr => "Prints help information",
+            Text::VersionDescr => "Prints version information",
+            Text::Version => "Version: {0}",
+            Text::EnvSet => "[env:{0}: set]",
+            Text::EnvNotSet => "[env:{0}: not set]",
+            Text::EnvValue => "[env:{0} = {1}]",
+            Text::EnvMissing => "[env:{0}: N/A]",
+            Text::UsesEnv => "Uses environment variable {0}",
+            Text::ConfigKey => "[config:{0}]",
+            Text::GroupRequires => "[{0} requires {1}]",
+            Text::GroupAtMostOne => "[at most one of {0}]",
+            Text::GroupExactlyOne => "[exactly one of {0}]",
+            Text::GroupAtLeastOne => "[at least one of {0}]",
+            Text::PossibleValues => "[possible values: {0}]",
+            Text::NotExpected => "{0} is not expected in this context",
+            Text::NoEnv => "environment variable {0} is not set",
+            Text::StrictPos => "expected {0} to be on the right side of {1}",
+            Text::ParseFailed => "couldn't parse {0}: {1}",
+            Text::ParseFailedNoValue => "couldn't parse: {0}",
+            Text::ConfigFailed => "couldn't parse config value {0}: {1}",
+            Text::GuardFailed => "{0}: {1}",
+            Text::GuardFailedNoValue => "check failed: {0}",
+            Text::NoArgument => "{0} requires an argument {1}",
+            Text::NoArgumentGotFlag => {
+                "{0} requires an argument {1}, got a flag {2}, try {3} to use it as an argument"
+            }
+            Text::Ambiguity => {
+                "{0} supports {1} as both an option and an option-argument, try to split {2} \
+                 into individual options ({3}) or use {4} syntax to disambiguate"
+            }
+            Text::App => "app",
+            Text::NoSuchFlag => "no such flag: {0}, did you mean {1}?",
+            Text::NoSuchValue => "no such argument value: {0}, did you mean {1}?",
+            Text::NoSuchCommand => "no such command or positional: {0}, did you mean {1}?",
+            Text::MissingDash => "no such flag: {0} (with one dash), did you mean {1}?",
+            Text::ExtraDash => "no such flag: {0} (with two dashes), did you mean {1}?",
+            Text::NestedCommand => {
+                "subcommand {0} is not valid in this context, did you mean to pass it to command {1}?"
+            }
+            Text::NestedFlag => {
+                "flag {0} is not valid in this context, did you mean to pass it to command {1}?"
+            }
+            Text::AmbiguousPrefix => "{0} is ambiguous, it could be {1}",
+            Text::Expected => "expected {0}, pass {1} for usage information",
+            Text::ExpectedGot => "expected {0}, got {1}. Pass {2} for usage information",
+            Text::NoArguments => "no arguments",
+            Text::OneOfTwo => "{0} or {1}",
+            Text::OneOfMany => "{0}, {1}, or more",
+            Text::Conflict => "{0} cannot be used at the same time as {1}",
+            Text::Requires => "{0} requires {1}",
+            Text::OnlyOnce => "argument {0} cannot be used multiple times in this context",
+            Text::SingleValue => "argument {0} expects a single value",
+            Text::ResponseFile => "response file {0}: {1}",
+            Text::ResponseFileLine => "response file {0}, line {1}: {2}",
+            Text::UnterminatedQuote => "unterminated quote",
+            Text::ResponseFileCycle => "file includes itself",
+            Text::ResponseFileDepth => "files are nested too deep",
+            Text::Deprecated => "(deprecated)",
+            Text::DeprecatedUsed => "{0} is deprecated: {1}",
+            Text::OutOfRange => "must be in range {0}",
+            Text::TooShort => "must be at least {0} characters long",
+            Text::NotValid => "not a valid {0}",
+            Text::NoneOfGroup => "none of the required items are present",
+            Text::ExpectedOneOf => "expected one of {0}",
+            Text::ExpectedOneOfSuggest => "expected one of {0}, did you mean {1}?",
+            Text::Error => "Error:",
+            Text::Warning => "Warning:",
+        }
+    }
+}
+
+/// Catalog of built-in messages: help section headers, `--help` and `--version` descriptions
+/// and error messages
+///
+/// Attach it to a parser with [`OptionParser::locale`](crate::OptionParser::locale), subcommands
+/// use the catalog of the parent unless they have their own. Messages that are not provided
+/// fall back to [`English`]. Messages produced by your code - help, `FromStr` errors, guard
+/// messages and so on are used as is.
+///
+/// ```rust
+/// # use bpaf::{*, locale::Text};
+/// struct Pirate;
+/// impl Locale for Pirate {
+///     fn text(&self, text: Text) -> Option<&str> {
+///         match text {
+///             Text::NotExpected => Some("arr, {0} be not welcome here"),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let parser = short('v').switch().to_options().locale(Pirate);
+/// let r = parser.run_inner(&["-x"]).unwrap_err().unwrap_stderr();
+/// assert_eq!(r, "arr, `-x` be not welcome here");
+/// ```
+pub trait Locale {
+    /// Template for a built-in message, see [`Text`] for placeholders each message uses
+    ///
+    /// Return `None` to use the English version
+    fn text(&self, text: Text) -> Option<&str>;
+}
+
+/// Built-in English messages, used by default
+#[derive(Debug, Clone, Copy, Default)]
+pub struct English;
+
+impl Locale for English {
+    fn text(&self, text: Text) -> Option<&str> {
+        Some(text.english())
+    }
+}
+
+/// Built-in German messages
+#[derive(Debug, Clone, Copy, Default)]
+pub struct German;
+
+impl Locale for German {
+    fn text(&self, text: Text) -> Option<&str> {
+        Some(match text {
+            Text::Usage => "Aufruf",
+            Text::AvailablePositional => "Verfügbare Positionsargumente:",
+            Text::AvailableOptions => "Verfügbare Optionen:",
+            Text::AvailableCommands => "Verfügbare Befehle:",
+            Text::HelpDescr => "Zeigt Hilfeinformationen an",
+            Text::VersionDescr => "Zeigt Versionsinformationen an",
+            Text::Version => "Version: {0}",
+            Text::EnvSet => "[env:{0}: gesetzt]",
+            Text::EnvNotSet => "[env:{0}: nicht gesetzt]",
+            Text::EnvValue => "[env:{0} = {1}]",
+            Text::EnvMissing => "[env:{0}: nicht verfügbar]",
+            Text::UsesEnv => "Verwendet die Umgebungsvariable {0}",
+            Text::ConfigKey => "[config:{0}]",
+            Text::GroupRequires => "[{0} erfordert {1}]",
+            Text::GroupAtMostOne => "[höchstens eines von {0}]",
+            Text::GroupExactlyOne => "[genau eines von {0}]",
+            Text::GroupAtLeastOne => "[mindestens eines von {0}]",
+            Text::PossibleValues => "[mögliche Werte: {0}]",
+            Text::NotExpected => "{0} wird in diesem Kontext nicht erwartet",
+            Text::NoEnv => "Umgebungsvariable {0} ist nicht gesetzt",
+            Text::StrictPos => "{0} muss rechts von {1} stehen",
+            Text::ParseFailed => "{0} konnte nicht verarbeitet werden: {1}",
+            Text::ParseFailedNoValue => "Wert konnte nicht verarbeitet werden: {0}",
+            Text::ConfigFailed => "Konfigurationswert {0} konnte nicht verarbeitet werden: {1}",
+            Text::GuardFailed => "{0}: {1}",
+            Text::GuardFailedNoValue => "Prüfung fehlgeschlagen: {0}",
+            Text::NoArgument => "{0} benötigt ein Argument {1}",
+            Text::NoArgumentGotFlag => {
+                "{0} benötigt ein Argument {1}, stattdessen wurde die Option {2} angegeben, \
+                 verwenden Sie {3}, um sie als Argument zu übergeben"
+            }
+            Text::Ambiguity => {
+                "{0} akzeptiert {1} sowohl als Option als auch als Option mit Argument, teilen \
+                 Sie {2} in einzelne Optionen auf ({3}) oder verwenden Sie {4} zur Unterscheidung"
+            }
+            Text::App => "Programm",
+            Text::NoSuchFlag => "unbekannte Option: {0}, meinten Sie {1}?",
+            Text::NoSuchValue => "unbekannter Argumentwert: {0}, meinten Sie {1}?",
+            Text::NoSuchCommand => {
+                "unbekannter Befehl oder Positionsargument: {0}, meinten Sie {1}?"
+            }
+            Text::MissingDash => "unbekannte Option: {0} (mit einem Bindestrich), meinten Sie {1}?",
+            Text::ExtraDash => "unbekannte Option: {0} (mit zwei Bindestrichen), meinten Sie {1}?",
+            Text::NestedCommand => {
+                "Unterbefehl {0} ist in diesem Kontext ungültig, wollten Sie ihn an den Befehl \
+                 {1} übergeben?"
+            }
+            Text::NestedFlag => {
+                "Option {0} ist in diesem Kontext ungültig, wollten Sie sie an den Befehl {1} \
+                 übergeben?"
+            }
+            Text::AmbiguousPrefix => "{0} ist mehrdeutig, in Frage kommen {1}",
+            Text::Expected => "{0} erwartet, {1} zeigt die Verwendung an",
+            Text::ExpectedGot => "{0} erwartet, {1} erhalten. {2} zeigt die Verwendung an",
+            Text::NoArguments => "keine Argumente",
+            Text::OneOfTwo => "{0} oder {1}",
+            Text::OneOfMany => "{0}, {1} oder weitere",
+            Text::Conflict => "{0} kann nicht zusammen mit {1} verwendet werden",
+            Text::Requires => "{0} erfordert {1}",
+            Text::OnlyOnce => "Argument {0} kann in diesem Kontext nur einmal verwendet werden",
+            Text::SingleValue => "Argument {0} erwartet einen einzelnen Wert",
+            Text::ResponseFile => "Antwortdatei {0}: {1}",
+            Text::ResponseFileLine => "Antwortdatei {0}, Zeile {1}: {2}",
+            Text::UnterminatedQuote => "Anführungszeichen wird nicht geschlossen",
+            Text::ResponseFileCycle => "Datei bindet sich selbst ein",
+            Text::ResponseFileDepth => "Dateien sind zu tief verschachtelt",
+            Text::Deprecated => "(veraltet)",
+            Text::DeprecatedUsed => "{0} ist veraltet: {1}",
+            Text::OutOfRange => "muss im Bereich {0} liegen",
+            Text::TooShort => "muss mindestens {0} Zeichen lang sein",
+            Text::NotValid => "kein gültiger Wert für {0}",
+            Text::NoneOfGroup => "keines der erforderlichen Elemente ist vorhanden",
+            Text::ExpectedOneOf => "erwartet wird einer der Werte {0}",
+            Text::ExpectedOneOfSuggest => "erwartet wird einer der Werte {0}, meinten Sie {1}?",
+            Text::Error => "Fehler:",
+            Text::Warning => "Warnung:",
+        })
+    }
+}
+
+/// Shared handle to a user provided [`Locale`]
+#[derive(Clone)]
+pub(crate) struct Catalog(pub(crate) Rc<dyn Locale>);
+
+impl std::fmt::Debug for Catalog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Catalog")
+    }
+}
+
+/// Template for a message from a catalog, English if there's no catalog or it has no such message
+pub(crate) fn get(catalog: Option<&Catalog>, text: Text) -> &str {
+    catalog
+        .and_then(|c| c.0.text(text))
+        .unwrap_or_else(|| text.english())
+}
+
+/// Message from a catalog with placeholders replaced by `args`, for messages passed around as
+/// plain text such as reasons of failed checks
+pub(crate) fn format(catalog: Option<&Catalog>, text: Text, args: &[&str]) -> String {
+    let mut res = String::new();
+    split_template(get(catalog, text), |part| match part {
+        Part::Text(text) => res.push_str(text),
+        Part::Arg(ix) => res.push_str(args.get(ix).copied().unwrap_or_default()),
+    });
+    res
+}
+
+/// Piece of a message template
+enum Part<'a> {
+    /// Text as is
+    Text(&'a str),
+    /// Placeholder `{ix}`
+    Arg(usize),
+}
+
+/// Split a message template into text and placeholders, `{` that doesn't start a placeholder is
+/// kept as text
+fn split_template<F>(template: &str, mut part: F)
+where
+    F: FnMut(Part),
+{
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let placeholder = rest[start + 1..].find('}').and_then(|len| {
+            let ix = rest[start + 1..start + 1 + len].parse::<usize>().ok()?;
+            Some((ix, start + len + 2))
+        });
+        match placeholder {
+            Some((ix, next)) => {
+                text_chunk(&rest[..start], &mut part);
+                part(Part::Arg(ix));
+                rest = &rest[next..];
+            }
+            None => {
+                text_chunk(&rest[..=start], &mut part);
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    text_chunk(rest, &mut part);
+}
+
+fn text_chunk<F: FnMut(Part)>(text: &str, part: &mut F) {
+    if !text.is_empty() {
+        part(Part::Text(text));
+    }
+}
+
+impl Doc {
+    /// Write a message template, placeholders are written by `arg`
+    pub(crate) fn template<F>(&mut self, template: &str, mut arg: F)
+    where
+        F: FnMut(&mut Doc, usize),
+    {
+        split_template(template, |part| match part {
+            Part::Text(text) => self.text(text),
+            Part::Arg(ix) => arg(self, ix),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::Doc;
+
+    #[test]
+    fn template_placeholders() {
+        let mut doc = Doc::default();
+        doc.template("{1} and {0}, {x} {", |doc, ix| doc.literal(&ix.to_string()));
+        assert_eq!(doc.monochrome(true), "1 and 0, {x} {");
+    }
+
+    #[test]
+    fn format_placeholders() {
+        let r = super::format(None, super::Text::OneOfTwo, &["`-a`", "`-b`"]);
+        assert_eq!(r, "`-a` or `-b`");
+    }
+}
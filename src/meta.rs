@@ -1,4 +1,8 @@
-use crate::{buffer::Doc, item::Item};
+use crate::{
+    buffer::Doc,
+    item::Item,
+    locale::{self, Catalog, Text},
+};
 
 #[doc(hidden)]
 #[derive(Clone, Debug)]
@@ -21,14 +25,47 @@ pub enum Meta {
     ///
     /// whole set of arguments go into the same section as the first one
     Subsection(Box<Meta>, Box<Doc>),
-    /// Buffer is rendered after
-    Suffix(Box<Meta>, Box<Doc>),
+    /// Note is rendered after
+    Suffix(Box<Meta>, Box<Note>),
     /// This item is not rendered in the help message
     Skip,
+    /// Hidden item, behaves like [`Skip`](Meta::Skip) everywhere except for
+    /// [`describe`](crate::OptionParser::describe)
+    Hidden(Box<Meta>),
     /// TODO make it Option<Box<Doc>>
     CustomUsage(Box<Meta>, Box<Doc>),
     /// this meta must be prefixed with -- in unsage group
     Strict(Box<Meta>),
+    /// Completion hint for items inside, used to generate static completion scripts
+    #[cfg(feature = "autocomplete")]
+    Complete(Box<Meta>, crate::complete_shell::CompHint),
+}
+
+/// Text rendered after some items in the help message
+#[doc(hidden)]
+#[derive(Clone, Debug)]
+pub enum Note {
+    /// Text as is
+    Doc(Doc),
+    /// Built-in message from the catalog with values for placeholders
+    Text(Text, Vec<Doc>),
+}
+
+impl Note {
+    pub(crate) fn render(&self, locale: Option<&Catalog>) -> Doc {
+        match self {
+            Note::Doc(doc) => doc.clone(),
+            Note::Text(text, args) => {
+                let mut doc = Doc::default();
+                doc.template(locale::get(locale, *text), |doc, ix| {
+                    if let Some(arg) = args.get(ix) {
+                        doc.doc(arg);
+                    }
+                });
+                doc
+            }
+        }
+    }
 }
 
 // to get std::mem::take to work
@@ -126,7 +163,9 @@ impl Meta {
                 | Meta::Subsection(m, _)
                 | Meta::Strict(m)
                 | Meta::Suffix(m, _) => go(m, is_pos, v),
-                Meta::Skip => {}
+                #[cfg(feature = "autocomplete")]
+                Meta::Complete(m, _) => go(m, is_pos, v),
+                Meta::Skip | Meta::Hidden(_) => {}
             }
         }
         let mut is_pos = false;
@@ -158,7 +197,7 @@ impl Meta {
         match meta {
             Meta::And(xs) => xs.first().and_then(Self::first_item),
             Meta::Item(item) => Some(*item.clone()),
-            Meta::Skip | Meta::Or(_) => None,
+            Meta::Skip | Meta::Hidden(_) | Meta::Or(_) => None,
             Meta::Optional(x)
             | Meta::Strict(x)
             | Meta::Required(x)
@@ -167,6 +206,8 @@ impl Meta {
             | Meta::Subsection(x, _)
             | Meta::Suffix(x, _)
             | Meta::CustomUsage(x, _) => Self::first_item(x),
+            #[cfg(feature = "autocomplete")]
+            Meta::Complete(x, _) => Self::first_item(x),
         }
     }
 
@@ -270,10 +311,16 @@ impl Meta {
                 m.normalize(for_usage, norm);
                 *self = std::mem::take(m);
             }
+            #[cfg(feature = "autocomplete")]
+            Meta::Complete(m, _) => {
+                m.normalize(for_usage, norm);
+                *self = std::mem::take(m);
+            }
             Meta::Item(i) => i.normalize(for_usage),
             Meta::Skip => {
                 // nothing to do with items and skip just bubbles upwards
             }
+            Meta::Hidden(_) => *self = Meta::Skip,
             Meta::CustomUsage(m, u) => {
                 m.normalize(for_usage, norm);
                 // strip CustomUsage if we are not in usage so writer can simply render it
@@ -345,7 +392,74 @@ impl Meta {
             | Meta::Many(m) => {
                 m.collect_shorts(flags, args);
             }
-            Meta::Skip | Meta::Strict(_) => {}
+            #[cfg(feature = "autocomplete")]
+            Meta::Complete(m, _) => m.collect_shorts(flags, args),
+            Meta::Skip | Meta::Hidden(_) | Meta::Strict(_) => {}
+        }
+    }
+
+    /// collect long names for abbreviated and case insensitive matching, without the ones
+    /// that belong to commands
+    pub(crate) fn collect_longs(&self, to: &mut Vec<String>) {
+        match self {
+            Meta::And(xs) | Meta::Or(xs) => {
+                for x in xs {
+                    x.collect_longs(to);
+                }
+            }
+            Meta::Item(m) => match &**m {
+                Item::Any { .. } | Item::Positional { .. } | Item::Command { .. } => {}
+                Item::Flag {
+                    longs, negatable, ..
+                } => {
+                    to.extend(longs.iter().map(|l| (*l).to_owned()));
+                    if *negatable {
+                        to.extend(longs.iter().map(|l| format!("no-{}", l)));
+                    }
+                }
+                Item::Argument { longs, .. } => to.extend(longs.iter().map(|l| (*l).to_owned())),
+            },
+            Meta::CustomUsage(m, _)
+            | Meta::Required(m)
+            | Meta::Optional(m)
+            | Meta::Adjacent(m)
+            | Meta::Subsection(m, _)
+            | Meta::Suffix(m, _)
+            | Meta::Many(m)
+            | Meta::Hidden(m)
+            | Meta::Strict(m) => m.collect_longs(to),
+            #[cfg(feature = "autocomplete")]
+            Meta::Complete(m, _) => m.collect_longs(to),
+            Meta::Skip => {}
+        }
+    }
+
+    /// collect names of commands available at this level, without nested ones
+    pub(crate) fn collect_commands(&self, to: &mut Vec<String>) {
+        match self {
+            Meta::And(xs) | Meta::Or(xs) => {
+                for x in xs {
+                    x.collect_commands(to);
+                }
+            }
+            Meta::Item(m) => {
+                if let Item::Command { name, short, .. } = &**m {
+                    to.push((*name).to_owned());
+                    to.extend(short.map(String::from));
+                }
+            }
+            Meta::CustomUsage(m, _)
+            | Meta::Required(m)
+            | Meta::Optional(m)
+            | Meta::Adjacent(m)
+            | Meta::Subsection(m, _)
+            | Meta::Suffix(m, _)
+            | Meta::Many(m)
+            | Meta::Hidden(m)
+            | Meta::Strict(m) => m.collect_commands(to),
+            #[cfg(feature = "autocomplete")]
+            Meta::Complete(m, _) => m.collect_commands(to),
+            Meta::Skip => {}
         }
     }
 }
@@ -2,8 +2,11 @@ use std::collections::BTreeSet;
 
 use crate::{
     buffer::{Block, Doc, Style, Token},
+    config::Config,
     info::Info,
     item::{Item, ShortLong},
+    locale::{self, Catalog, Text},
+    meta::Note,
     Meta,
 };
 
@@ -14,7 +17,7 @@ pub struct Metavar(pub(crate) &'static str);
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum HelpItem<'a> {
     DecorSuffix {
-        help: &'a Doc,
+        help: &'a Note,
         ty: HiTy,
     },
     GroupStart {
@@ -40,17 +43,22 @@ pub(crate) enum HelpItem<'a> {
         meta: &'a Meta,
         #[cfg(feature = "docgen")]
         info: &'a Info,
+        deprecated: bool,
     },
     Flag {
         name: ShortLong,
         env: Option<&'static str>,
         help: Option<&'a Doc>,
+        deprecated: bool,
+        negatable: bool,
     },
     Argument {
         name: ShortLong,
         metavar: Metavar,
         env: Option<&'static str>,
         help: Option<&'a Doc>,
+        deprecated: bool,
+        opt_value: bool,
     },
     AnywhereStart {
         inner: &'a Meta,
@@ -63,11 +71,16 @@ pub(crate) enum HelpItem<'a> {
 impl HelpItem<'_> {
     fn has_help(&self) -> bool {
         match self {
-            HelpItem::Positional { help, .. }
-            | HelpItem::Command { help, .. }
-            | HelpItem::Flag { help, .. }
-            | HelpItem::Any { help, .. }
-            | HelpItem::Argument { help, .. } => help.is_some(),
+            HelpItem::Positional { help, .. } | HelpItem::Any { help, .. } => help.is_some(),
+            HelpItem::Command {
+                help, deprecated, ..
+            }
+            | HelpItem::Flag {
+                help, deprecated, ..
+            }
+            | HelpItem::Argument {
+                help, deprecated, ..
+            } => help.is_some() || *deprecated,
             HelpItem::GroupStart { .. } | HelpItem::DecorSuffix { .. } => true,
             HelpItem::GroupEnd { .. }
             | HelpItem::AnywhereStart { .. }
@@ -188,8 +201,10 @@ impl Meta {
             | Meta::Suffix(x, _)
             | Meta::Strict(x)
             | Meta::CustomUsage(x, _) => x.peek_front_ty(),
+            #[cfg(feature = "autocomplete")]
+            Meta::Complete(x, _) => x.peek_front_ty(),
             Meta::Item(i) => Some(HiTy::from(i.as_ref())),
-            Meta::Skip => None,
+            Meta::Skip | Meta::Hidden(_) => None,
         }
     }
 }
@@ -219,6 +234,8 @@ impl<'a> HelpItems<'a> {
                 | Meta::Optional(x)
                 | Meta::Many(x)
                 | Meta::Strict(x) => go(hi, x, no_ss),
+                #[cfg(feature = "autocomplete")]
+                Meta::Complete(x, _) => go(hi, x, no_ss),
                 Meta::Item(item) => {
                     if matches!(item.as_ref(), Item::Positional { help: None, .. }) {
                         return;
@@ -242,7 +259,7 @@ impl<'a> HelpItems<'a> {
                         hi.items.push(HelpItem::DecorSuffix { help, ty });
                     }
                 }
-                Meta::Skip => (),
+                Meta::Skip | Meta::Hidden(_) => (),
             }
         }
         go(self, meta, false);
@@ -293,6 +310,7 @@ impl<'a> From<&'a Item> for HelpItem<'a> {
                 info,
                 #[cfg(not(feature = "docgen"))]
                     info: _,
+                deprecated,
             } => Self::Command {
                 name,
                 short: *short,
@@ -300,16 +318,22 @@ impl<'a> From<&'a Item> for HelpItem<'a> {
                 meta,
                 #[cfg(feature = "docgen")]
                 info,
+                deprecated: *deprecated,
             },
             Item::Flag {
                 name,
                 env,
                 help,
                 shorts: _,
+                longs: _,
+                deprecated,
+                negatable,
             } => Self::Flag {
                 name: *name,
-                env: *env,
+                env: env.first().copied(),
                 help: help.as_ref(),
+                deprecated: *deprecated,
+                negatable: *negatable,
             },
             Item::Argument {
                 name,
@@ -317,11 +341,16 @@ impl<'a> From<&'a Item> for HelpItem<'a> {
                 env,
                 help,
                 shorts: _,
+                longs: _,
+                deprecated,
+                opt_value,
             } => Self::Argument {
                 name: *name,
                 metavar: *metavar,
-                env: *env,
+                env: env.first().copied(),
                 help: help.as_ref(),
+                deprecated: *deprecated,
+                opt_value: *opt_value,
             },
             Item::Any {
                 metavar,
@@ -354,7 +383,13 @@ impl Doc {
 }
 
 #[allow(clippy::too_many_lines)] // lines are _very_ boring
-fn write_help_item(buf: &mut Doc, item: &HelpItem, include_env: bool) {
+fn write_help_item(
+    buf: &mut Doc,
+    item: &HelpItem,
+    include_env: bool,
+    config: Option<&Config>,
+    locale: Option<&Catalog>,
+) {
     match item {
         HelpItem::GroupStart { help, .. } => {
             buf.token(Token::BlockStart(Block::Block));
@@ -371,7 +406,7 @@ fn write_help_item(buf: &mut Doc, item: &HelpItem, include_env: bool) {
             buf.token(Token::BlockStart(Block::ItemTerm));
             buf.token(Token::BlockEnd(Block::ItemTerm));
             buf.token(Token::BlockStart(Block::ItemBody));
-            buf.doc(help);
+            buf.doc(&help.render(locale));
             buf.token(Token::BlockEnd(Block::ItemBody));
         }
         HelpItem::Any {
@@ -405,6 +440,7 @@ fn write_help_item(buf: &mut Doc, item: &HelpItem, include_env: bool) {
             meta: _,
             #[cfg(feature = "docgen")]
                 info: _,
+            deprecated,
         } => {
             buf.token(Token::BlockStart(Block::ItemTerm));
             buf.write_str(name, Style::Literal);
@@ -413,80 +449,90 @@ fn write_help_item(buf: &mut Doc, item: &HelpItem, include_env: bool) {
                 buf.write_char(*short, Style::Literal);
             }
             buf.token(Token::BlockEnd(Block::ItemTerm));
-            if let Some(help) = help {
-                buf.token(Token::BlockStart(Block::ItemBody));
-                buf.doc(help);
-                buf.token(Token::BlockEnd(Block::ItemBody));
-            }
+            write_item_body(buf, *help, *deprecated, locale);
         }
-        HelpItem::Flag { name, env, help } => {
+        HelpItem::Flag {
+            name,
+            env,
+            help,
+            deprecated,
+            negatable,
+        } => {
             buf.token(Token::BlockStart(Block::ItemTerm));
-            write_shortlong(buf, *name);
+            write_shortlong(buf, *name, *negatable);
             buf.token(Token::BlockEnd(Block::ItemTerm));
-            if let Some(help) = help {
-                buf.token(Token::BlockStart(Block::ItemBody));
-                buf.doc(help);
-                buf.token(Token::BlockEnd(Block::ItemBody));
-            }
+            let has_body = write_item_body(buf, *help, *deprecated, locale);
             if let Some(env) = env {
-                let val = if std::env::var_os(env).is_some() {
-                    ": set"
+                let text = if std::env::var_os(env).is_some() {
+                    Text::EnvSet
                 } else {
-                    ": not set"
+                    Text::EnvNotSet
                 };
-                if help.is_some() {
+                if has_body {
                     buf.token(Token::BlockStart(Block::ItemTerm));
                     buf.token(Token::BlockEnd(Block::ItemTerm));
                 }
                 buf.token(Token::BlockStart(Block::ItemBody));
                 if include_env {
-                    buf.write_str(&format!("[env:{}{}]", env, val), Style::Text);
+                    buf.template(locale::get(locale, text), |buf, _| buf.text(env));
                 } else {
-                    buf.text("Uses environment variable ");
-                    buf.literal(env);
+                    buf.template(locale::get(locale, Text::UsesEnv), |buf, _| {
+                        buf.literal(env);
+                    });
                 }
                 buf.token(Token::BlockEnd(Block::ItemBody));
             }
+            write_config_key(buf, *name, has_body || env.is_some(), config, locale);
         }
         HelpItem::Argument {
             name,
             metavar,
             env,
             help,
+            deprecated,
+            opt_value,
         } => {
             buf.token(Token::BlockStart(Block::ItemTerm));
-            write_shortlong(buf, *name);
-            buf.write_str("=", Style::Text);
-            buf.metavar(*metavar);
+            write_shortlong(buf, *name, false);
+            if *opt_value {
+                buf.write_str("[=", Style::Text);
+                buf.metavar(*metavar);
+                buf.write_str("]", Style::Text);
+            } else {
+                buf.write_str("=", Style::Text);
+                buf.metavar(*metavar);
+            }
             buf.token(Token::BlockEnd(Block::ItemTerm));
 
-            if let Some(help) = help {
-                buf.token(Token::BlockStart(Block::ItemBody));
-                buf.doc(help);
-                buf.token(Token::BlockEnd(Block::ItemBody));
-            }
+            let has_body = write_item_body(buf, *help, *deprecated, locale);
 
             if let Some(env) = env {
-                let val = match std::env::var_os(env) {
-                    Some(s) => std::borrow::Cow::from(format!(" = {:?}", s.to_string_lossy())),
-                    None => std::borrow::Cow::Borrowed(": N/A"),
-                };
+                let val = std::env::var_os(env).map(|s| format!("{:?}", s.to_string_lossy()));
 
-                if help.is_some() {
+                if has_body {
                     buf.token(Token::BlockStart(Block::ItemTerm));
                     buf.token(Token::BlockEnd(Block::ItemTerm));
                 }
                 buf.token(Token::BlockStart(Block::ItemBody));
 
                 if include_env {
-                    buf.write_str(&format!("[env:{}{}]", env, val), Style::Text);
+                    let text = match val {
+                        Some(_) => Text::EnvValue,
+                        None => Text::EnvMissing,
+                    };
+                    buf.template(locale::get(locale, text), |buf, ix| match ix {
+                        0 => buf.text(env),
+                        _ => buf.text(val.as_deref().unwrap_or_default()),
+                    });
                 } else {
-                    buf.text("Uses environment variable ");
-                    buf.literal(env);
+                    buf.template(locale::get(locale, Text::UsesEnv), |buf, _| {
+                        buf.literal(env);
+                    });
                 }
 
                 buf.token(Token::BlockEnd(Block::ItemBody));
             }
+            write_config_key(buf, *name, has_body || env.is_some(), config, locale);
         }
         HelpItem::AnywhereStart { inner, .. } => {
             buf.token(Token::BlockStart(Block::Section3));
@@ -500,21 +546,71 @@ fn write_help_item(buf: &mut Doc, item: &HelpItem, include_env: bool) {
     }
 }
 
-fn write_shortlong(buf: &mut Doc, name: ShortLong) {
+/// Write help for an item followed by a deprecation note, returns `true` if anything was written
+fn write_item_body(
+    buf: &mut Doc,
+    help: Option<&Doc>,
+    deprecated: bool,
+    locale: Option<&Catalog>,
+) -> bool {
+    if help.is_none() && !deprecated {
+        return false;
+    }
+    buf.token(Token::BlockStart(Block::ItemBody));
+    if let Some(help) = help {
+        buf.doc(help);
+    }
+    if deprecated {
+        if help.is_some() {
+            buf.text(" ");
+        }
+        buf.text(locale::get(locale, Text::Deprecated));
+    }
+    buf.token(Token::BlockEnd(Block::ItemBody));
+    true
+}
+
+/// Mention the config key for this item if config source has a value for it, value itself is
+/// not shown since it can be a secret
+fn write_config_key(
+    buf: &mut Doc,
+    name: ShortLong,
+    has_body: bool,
+    config: Option<&Config>,
+    locale: Option<&Catalog>,
+) {
+    let key = match name.as_long() {
+        Some(key) => key,
+        None => return,
+    };
+    if config.and_then(|c| c.get(key)).is_some() {
+        if has_body {
+            buf.token(Token::BlockStart(Block::ItemTerm));
+            buf.token(Token::BlockEnd(Block::ItemTerm));
+        }
+        buf.token(Token::BlockStart(Block::ItemBody));
+        buf.template(locale::get(locale, Text::ConfigKey), |buf, _| buf.text(key));
+        buf.token(Token::BlockEnd(Block::ItemBody));
+    }
+}
+
+fn write_shortlong(buf: &mut Doc, name: ShortLong, negatable: bool) {
+    let dashes = if negatable { "--[no-]" } else { "--" };
     match name {
         ShortLong::Short(s) => {
             buf.write_char('-', Style::Literal);
             buf.write_char(s, Style::Literal);
         }
         ShortLong::Long(l) => {
-            buf.write_str("    --", Style::Literal);
+            buf.write_str("    ", Style::Literal);
+            buf.write_str(dashes, Style::Literal);
             buf.write_str(l, Style::Literal);
         }
         ShortLong::ShortLong(s, l) => {
             buf.write_char('-', Style::Literal);
             buf.write_char(s, Style::Literal);
             buf.write_str(", ", Style::Text);
-            buf.write_str("--", Style::Literal);
+            buf.write_str(dashes, Style::Literal);
             buf.write_str(l, Style::Literal);
         }
     }
@@ -527,6 +623,8 @@ pub(crate) fn render_help(
     parser_meta: &Meta,
     help_meta: &Meta,
     include_env: bool,
+    config: Option<&Config>,
+    locale: Option<&Catalog>,
 ) -> Doc {
     parser_meta.positional_invariant_check(false);
     let mut buf = Doc::default();
@@ -541,7 +639,7 @@ pub(crate) fn render_help(
     if let Some(usage) = &info.usage {
         buf.doc(usage);
     } else {
-        buf.write_str("Usage", Style::Emphasis);
+        buf.write_str(locale::get(locale, Text::Usage), Style::Emphasis);
         buf.write_str(": ", Style::Text);
         buf.token(Token::BlockStart(Block::Mono));
         buf.write_path(path);
@@ -560,7 +658,7 @@ pub(crate) fn render_help(
     items.append_meta(parser_meta);
     items.append_meta(help_meta);
 
-    buf.write_help_item_groups(items, include_env);
+    buf.write_help_item_groups(items, include_env, config, locale);
 
     if let Some(footer) = &info.footer {
         buf.token(Token::BlockStart(Block::Block));
@@ -620,27 +718,42 @@ impl Dedup {
 
 impl Doc {
     #[inline(never)]
-    pub(crate) fn write_help_item_groups(&mut self, mut items: HelpItems, include_env: bool) {
+    pub(crate) fn write_help_item_groups(
+        &mut self,
+        mut items: HelpItems,
+        include_env: bool,
+        config: Option<&Config>,
+        locale: Option<&Catalog>,
+    ) {
         while let Some(range) = items.find_group() {
             let mut dd = Dedup::default();
             for item in items.items.drain(range) {
                 if dd.check(&item) {
-                    write_help_item(self, &item, include_env);
+                    write_help_item(self, &item, include_env, config, locale);
                 }
             }
         }
 
-        for (ty, name) in [
-            (HiTy::Positional, "Available positional items:"),
-            (HiTy::Flag, "Available options:"),
-            (HiTy::Command, "Available commands:"),
+        for (ty, text) in [
+            (HiTy::Positional, Text::AvailablePositional),
+            (HiTy::Flag, Text::AvailableOptions),
+            (HiTy::Command, Text::AvailableCommands),
         ] {
-            self.write_help_items(&items, ty, name, include_env);
+            let name = locale::get(locale, text);
+            self.write_help_items(&items, ty, name, include_env, config, locale);
         }
     }
 
     #[inline(never)]
-    fn write_help_items(&mut self, items: &HelpItems, ty: HiTy, name: &str, include_env: bool) {
+    fn write_help_items(
+        &mut self,
+        items: &HelpItems,
+        ty: HiTy,
+        name: &str,
+        include_env: bool,
+        config: Option<&Config>,
+        locale: Option<&Catalog>,
+    ) {
         let mut xs = items.items_of_ty(ty).peekable();
         if xs.peek().is_some() {
             self.token(Token::BlockStart(Block::Block));
@@ -651,7 +764,7 @@ impl Doc {
             let mut dd = Dedup::default();
             for item in xs {
                 if dd.check(item) {
-                    write_help_item(self, item, include_env);
+                    write_help_item(self, item, include_env, config, locale);
                 }
             }
             self.token(Token::BlockEnd(Block::DefinitionList));
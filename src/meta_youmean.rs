@@ -18,6 +18,9 @@ pub(crate) enum Suggestion {
     /// expected -f, actual --f
     ExtraDash(char),
     Nested(String, Variant),
+    /// abbreviated name that matches several names, see
+    /// [`allow_abbreviations`](crate::OptionParser::allow_abbreviations)
+    Ambiguous(Vec<String>),
 }
 
 /// Looks for potential typos
@@ -36,6 +39,23 @@ pub(crate) fn suggest(args: &State, meta: &Meta) -> Option<(usize, Suggestion)>
     // it also should be a printable name
     let actual = arg.to_string();
 
+    // abbreviated command that could stand for several commands
+    if args.long_matching.abbreviations && matches!(arg, crate::args::Arg::Word(_)) {
+        if let Err(names) = args.long_matching.resolve(&actual, &args.commands) {
+            return Some((ix, Suggestion::Ambiguous(names)));
+        }
+    }
+
+    // abbreviated long name that could stand for several names available at this level
+    if let crate::args::Arg::Long(name, _, _) = arg {
+        if args.long_matching.enabled() {
+            if let Err(names) = args.long_matching.resolve(name, &args.longs) {
+                let names = names.iter().map(|n| format!("--{}", n)).collect();
+                return Some((ix, Suggestion::Ambiguous(names)));
+            }
+        }
+    }
+
     // all the help items one level deep
     let mut hi = HelpItems::default();
     hi.append_meta(meta);
@@ -140,7 +160,7 @@ pub(crate) fn suggest(args: &State, meta: &Meta) -> Option<(usize, Suggestion)>
 /// returns `usize::MAX` if there's no common characters at all mostly to avoid
 /// confusing error messages - "you typed 'foo', maybe you ment 'bar'" where
 /// 'foo' and 'bar' don't have anything in common
-fn damerau_levenshtein(a: &str, b: &str) -> usize {
+pub(crate) fn damerau_levenshtein(a: &str, b: &str) -> usize {
     #![allow(clippy::many_single_char_names)]
     let a_len = a.chars().count();
     let b_len = b.chars().count();
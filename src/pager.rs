@@ -0,0 +1,59 @@
+//! Showing long help messages with a pager, see [`pager`](crate::OptionParser::pager)
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+/// Pager used when `PAGER` environment variable is not set
+const DEFAULT_PAGER: &str = "less -R";
+
+/// Split pager command into a program and its arguments
+///
+/// Empty `PAGER` disables paging
+fn pager_command(var: Option<String>) -> Option<(String, Vec<String>)> {
+    let var = var.unwrap_or_else(|| DEFAULT_PAGER.to_owned());
+    let mut words = var.split_whitespace().map(str::to_owned);
+    let program = words.next()?;
+    Some((program, words.collect()))
+}
+
+/// Send text to a pager, returns `false` if pager can't be started
+///
+/// Once pager is running the text is considered shown even if user closes the pager early.
+pub(crate) fn page(text: &str) -> bool {
+    let (program, args) = match pager_command(std::env::var("PAGER").ok()) {
+        Some(cmd) => cmd,
+        None => return false,
+    };
+    let mut child = match Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(_) => return false,
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // pager exiting before reading everything is not an error
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child.wait().is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pager_command;
+
+    #[test]
+    fn pager_commands() {
+        let less = Some(("less".to_owned(), vec!["-R".to_owned()]));
+        assert_eq!(pager_command(None), less);
+        assert_eq!(pager_command(Some(" less  -R ".to_owned())), less);
+        assert_eq!(
+            pager_command(Some("more".to_owned())),
+            Some(("more".to_owned(), Vec::new()))
+        );
+        assert_eq!(pager_command(Some(String::new())), None);
+    }
+}
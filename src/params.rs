@@ -59,7 +59,9 @@
 //!
 #![cfg_attr(not(doctest), doc = include_str!("docs2/command.md"))]
 //!
-use std::{ffi::OsString, marker::PhantomData, str::FromStr};
+use std::{
+    ffi::OsString, fmt::Display, marker::PhantomData, ops::RangeBounds, rc::Rc, str::FromStr,
+};
 
 use crate::{
     args::{Arg, State},
@@ -67,7 +69,9 @@ use crate::{
     from_os_str::parse_os_str,
     item::ShortLong,
     meta_help::Metavar,
-    Doc, Error, Item, Meta, OptionParser, Parser,
+    prompt::Prompt,
+    validate::Checks,
+    Doc, Error, Item, Meta, OptionParser, Parser, ValueSource,
 };
 
 #[cfg(doc)]
@@ -109,15 +113,40 @@ pub struct NamedArg {
     pub(crate) long: Vec<&'static str>,
     pub(crate) env: Vec<&'static str>,
     pub(crate) help: Option<Doc>,
+    pub(crate) deprecated: Option<&'static str>,
 }
 
 impl NamedArg {
-    pub(crate) fn flag_item(&self) -> Option<Item> {
+    /// Check if flag is present in environment variables or in the config source
+    fn flag_source(&self, args: &State) -> Option<ValueSource> {
+        if let Some(name) = self
+            .env
+            .iter()
+            .find(|name| std::env::var_os(name).is_some())
+        {
+            return Some(ValueSource::Env(name));
+        }
+        match (&args.config, self.long.first()) {
+            (Some(config), Some(key)) if config.get_flag(key) => Some(ValueSource::Config(key)),
+            _ => None,
+        }
+    }
+
+    /// Value from the config source along with the key used to look it up
+    fn config_value(&self, args: &State) -> Option<(&'static str, String)> {
+        let key = self.long.first()?;
+        Some((key, args.config.as_ref()?.get(key)?))
+    }
+
+    pub(crate) fn flag_item(&self, negatable: bool) -> Option<Item> {
         Some(Item::Flag {
             name: ShortLong::try_from(self).ok()?,
             help: self.help.clone(),
-            env: self.env.first().copied(),
+            env: self.env.clone(),
             shorts: self.short.clone(),
+            longs: self.long.clone(),
+            deprecated: self.deprecated.is_some(),
+            negatable: negatable && !self.long.is_empty(),
         })
     }
 }
@@ -182,6 +211,31 @@ impl NamedArg {
         self
     }
 
+    /// Mark a `flag`/`switch`/`argument` as deprecated
+    ///
+    /// Deprecated item is still parsed as usual, but using it produces a warning with the
+    /// `message`: [`run`](OptionParser::run) prints it to `stderr`,
+    /// [`run_inner_with_warnings`](OptionParser::run_inner_with_warnings) returns it along with
+    /// the result. Help marks the item as "(deprecated)" and shell completion stops offering it.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let output = long("output").argument::<String>("FILE");
+    /// let out = long("out")
+    ///     .deprecated("use --output")
+    ///     .argument::<String>("FILE");
+    /// let parser = construct!([output, out]).to_options();
+    ///
+    /// let (r, warnings) = parser.run_inner_with_warnings(&["--out", "a.txt"]).unwrap();
+    /// assert_eq!(r, "a.txt");
+    /// assert_eq!(warnings[0].to_string(), "`--out` is deprecated: use --output");
+    /// ```
+    #[must_use]
+    pub fn deprecated(mut self, message: &'static str) -> Self {
+        self.deprecated = Some(message);
+        self
+    }
+
     /// Simple boolean flag
     ///
     /// A special case of a [`flag`](NamedArg::flag) that gets decoded into a `bool`, mostly serves as a convenient
@@ -197,6 +251,47 @@ impl NamedArg {
         build_flag_parser(true, Some(false), self)
     }
 
+    /// Boolean flag that can be turned off with a `--no-` prefix
+    ///
+    /// Similar to [`switch`](NamedArg::switch) but also accepts `--no-` versions of all the
+    /// long names: `long("color").negatable()` accepts both `--color` and `--no-color`. If both
+    /// are present - the last one wins, absent flag produces `false`. Help shows both spellings
+    /// as a single item: `--[no-]color`, shell completion offers both.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = long("color").negatable().to_options();
+    ///
+    /// assert_eq!(parser.run_inner(&["--color"]).unwrap(), true);
+    /// assert_eq!(parser.run_inner(&["--no-color"]).unwrap(), false);
+    /// assert_eq!(parser.run_inner(&["--no-color", "--color"]).unwrap(), true);
+    /// assert_eq!(parser.run_inner(&[]).unwrap(), false);
+    /// ```
+    ///
+    /// # Derive usage
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// #[derive(Debug, Clone, Bpaf)]
+    /// #[bpaf(options)]
+    /// struct Options {
+    ///     #[bpaf(negatable)]
+    ///     /// Use colors in the output
+    ///     color: bool,
+    /// }
+    /// # let r = options().run_inner(&["--color", "--no-color"]).unwrap();
+    /// # assert!(!r.color);
+    /// ```
+    #[must_use]
+    pub fn negatable(self) -> ParseFlag<bool> {
+        ParseFlag {
+            present: true,
+            absent: Some(false),
+            negated: Some(false),
+            named: self,
+        }
+    }
+
     /// Flag with custom present/absent values
     ///
     /// More generic version of [`switch`](NamedArg::switch) that can use arbitrary type instead of
@@ -254,6 +349,48 @@ impl NamedArg {
         build_argument(self, metavar)
     }
 
+    /// Argument with an optional value
+    ///
+    /// Similar to [`argument`](NamedArg::argument), but the value can only be attached to the
+    /// name: `--color=always` or `-calways`. If the name is present on its own - parser
+    /// produces `default_when_bare`, in `--color never` the `never` is left for other parsers,
+    /// such as a positional item. Help shows it as `--color[=WHEN]`.
+    ///
+    /// Absent argument is handled the same way as for [`argument`](NamedArg::argument), use
+    /// [`fallback`](Parser::fallback) or [`optional`](Parser::optional) to make it optional.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let color = long("color")
+    ///     .help("When to use colors")
+    ///     .argument_opt_value::<String>("WHEN", "always".to_owned())
+    ///     .fallback("auto".to_owned());
+    /// let file = positional::<String>("FILE").optional();
+    /// let parser = construct!(color, file).to_options();
+    ///
+    /// let r = parser.run_inner(&["--color=never"]).unwrap();
+    /// assert_eq!(r, ("never".to_owned(), None));
+    ///
+    /// let r = parser.run_inner(&["--color", "never"]).unwrap();
+    /// assert_eq!(r, ("always".to_owned(), Some("never".to_owned())));
+    ///
+    /// let r = parser.run_inner(&[]).unwrap();
+    /// assert_eq!(r, ("auto".to_owned(), None));
+    /// ```
+    #[must_use]
+    pub fn argument_opt_value<T>(
+        self,
+        metavar: &'static str,
+        default_when_bare: T,
+    ) -> ParseArgument<T>
+    where
+        T: FromStr + Clone + 'static,
+    {
+        let mut arg = build_argument(self, metavar);
+        arg.bare = Some(Rc::new(move || default_when_bare.clone()));
+        arg
+    }
+
     /// `adjacent` requires for the argument to be present in the same word as the flag:
     /// `-f bar` - no, `-fbar` or `-f=bar` - yes.
     pub(crate) fn matches_arg(&self, arg: &Arg, adjacent: bool) -> bool {
@@ -263,6 +400,16 @@ impl NamedArg {
             Arg::ArgWord(_) | Arg::Word(_) | Arg::PosWord(_) => false,
         }
     }
+
+    /// Check if argument is a `--no-` version of one of the long names
+    pub(crate) fn matches_negated(&self, arg: &Arg) -> bool {
+        match arg {
+            Arg::Long(l, _, _) => l
+                .strip_prefix("no-")
+                .map_or(false, |l| self.long.contains(&l)),
+            Arg::Short(..) | Arg::ArgWord(_) | Arg::Word(_) | Arg::PosWord(_) => false,
+        }
+    }
 }
 
 impl<T> OptionParser<T> {
@@ -309,6 +456,7 @@ impl<T> OptionParser<T> {
             help: self.short_descr().map(Into::into),
             subparser: self,
             adjacent: false,
+            deprecated: None,
         }
     }
 }
@@ -323,6 +471,7 @@ pub struct ParseCommand<T> {
     pub(crate) help: Option<Doc>,
     pub(crate) subparser: OptionParser<T>,
     pub(crate) adjacent: bool,
+    pub(crate) deprecated: Option<&'static str>,
 }
 
 impl<P> ParseCommand<P> {
@@ -422,6 +571,17 @@ impl<P> ParseCommand<P> {
         self.adjacent = true;
         self
     }
+
+    /// Mark a command as deprecated
+    ///
+    /// Behavior is similar to [`deprecated`](NamedArg::deprecated): command is still parsed, but
+    /// using it produces a warning with the `message`, help marks it as "(deprecated)" and shell
+    /// completion stops offering it.
+    #[must_use]
+    pub fn deprecated(mut self, message: &'static str) -> Self {
+        self.deprecated = Some(message);
+        self
+    }
 }
 
 impl<T> Parser<T> for ParseCommand<T> {
@@ -440,7 +600,9 @@ impl<T> Parser<T> for ParseCommand<T> {
                 // in completion mode prefer to autocomplete the command name vs going inside the
                 // parser
                 args.clear_comps();
-                args.push_command(self.longs[0], self.shorts.first().copied(), &self.help);
+                if self.deprecated.is_none() {
+                    args.push_command(self.longs[0], self.shorts.first().copied(), &self.help);
+                }
                 return Err(Error(Message::Missing(Vec::new())));
             }
 
@@ -448,6 +610,10 @@ impl<T> Parser<T> for ParseCommand<T> {
                 args.set_scope(cur..args.scope().end);
             }
 
+            let source = args.cli_source();
+            args.record_source(|| self.longs[0].to_owned(), source);
+            args.check_deprecated(|| self.longs[0].to_owned(), self.deprecated);
+
             args.path.push(self.longs[0].to_string());
             if self.adjacent {
                 let mut orig_args = args.clone();
@@ -484,7 +650,9 @@ impl<T> Parser<T> for ParseCommand<T> {
             }
         } else {
             #[cfg(feature = "autocomplete")]
-            args.push_command(self.longs[0], self.shorts.first().copied(), &self.help);
+            if self.deprecated.is_none() {
+                args.push_command(self.longs[0], self.shorts.first().copied(), &self.help);
+            }
 
             let missing = MissingItem {
                 item: self.item(),
@@ -508,6 +676,7 @@ impl<T> ParseCommand<T> {
             help: self.help.clone(),
             meta: Box::new(self.subparser.inner.meta()),
             info: Box::new(self.subparser.info.clone()),
+            deprecated: self.deprecated.is_some(),
         }
     }
 }
@@ -519,34 +688,57 @@ where
     ParseFlag {
         present,
         absent,
+        negated: None,
         named,
     }
 }
 
 #[derive(Clone)]
-/// Parser for a named switch, created with [`NamedArg::flag`] or [`NamedArg::switch`]
+/// Parser for a named switch, created with [`NamedArg::flag`], [`NamedArg::switch`] or
+/// [`NamedArg::negatable`]
 pub struct ParseFlag<T> {
     present: T,
     absent: Option<T>,
+    /// value for `--no-` versions of long names, only for negatable flags
+    negated: Option<T>,
     named: NamedArg,
 }
 
 impl<T: Clone + 'static> Parser<T> for ParseFlag<T> {
     fn eval(&self, args: &mut State) -> Result<T, Error> {
-        if args.take_flag(&self.named) || self.named.env.iter().find_map(std::env::var_os).is_some()
-        {
+        let taken = match &self.negated {
+            Some(negated) => args.take_negatable_flag(&self.named).map(|present| {
+                if present {
+                    &self.present
+                } else {
+                    negated
+                }
+            }),
+            None if args.take_flag(&self.named) => Some(&self.present),
+            None => None,
+        };
+        let found = match taken {
+            Some(value) => Some((value, args.cli_source())),
+            None => self
+                .named
+                .flag_source(args)
+                .map(|source| (&self.present, source)),
+        };
+        if let Some((value, source)) = found {
             #[cfg(feature = "autocomplete")]
             if args.touching_last_remove() {
-                args.push_flag(&self.named);
+                args.push_flag(&self.named, self.negated.is_some());
             }
-            Ok(self.present.clone())
+            args.record_source(|| self.named.provenance_name(), source);
+            args.check_deprecated(|| self.named.provenance_name(), self.named.deprecated);
+            Ok(value.clone())
         } else {
             #[cfg(feature = "autocomplete")]
-            args.push_flag(&self.named);
+            args.push_flag(&self.named, self.negated.is_some());
             match &self.absent {
                 Some(ok) => Ok(ok.clone()),
                 None => {
-                    if let Some(item) = self.named.flag_item() {
+                    if let Some(item) = self.named.flag_item(self.negated.is_some()) {
                         let missing = MissingItem {
                             item,
                             position: args.scope().start,
@@ -564,7 +756,7 @@ impl<T: Clone + 'static> Parser<T> for ParseFlag<T> {
     }
 
     fn meta(&self) -> Meta {
-        if let Some(item) = self.named.flag_item() {
+        if let Some(item) = self.named.flag_item(self.negated.is_some()) {
             item.required(self.absent.is_none())
         } else {
             Meta::Skip
@@ -606,6 +798,10 @@ fn build_argument<T>(named: NamedArg, metavar: &'static str) -> ParseArgument<T>
         metavar,
         ty: PhantomData,
         adjacent: false,
+        prompt: None,
+        delimiter: None,
+        checks: Checks::default(),
+        bare: None,
     }
 }
 
@@ -616,6 +812,11 @@ pub struct ParseArgument<T> {
     named: NamedArg,
     metavar: &'static str,
     adjacent: bool,
+    prompt: Option<Prompt>,
+    delimiter: Option<char>,
+    checks: Checks<T>,
+    /// value for a name without a value, only for [`argument_opt_value`](NamedArg::argument_opt_value)
+    bare: Option<Rc<dyn Fn() -> T>>,
 }
 
 impl<T> ParseArgument<T> {
@@ -634,24 +835,214 @@ impl<T> ParseArgument<T> {
         self
     }
 
+    /// Split the value into several values with a delimiter
+    ///
+    /// `--tag a,b,c` is handled as if it was `--tag a --tag b --tag c`: each element is parsed
+    /// on its own and parse failures mention the element that failed. Combine it with
+    /// [`many`](crate::Parser::many) or [`collect`](crate::Parser::collect) to get all the
+    /// values, a parser that takes a single value fails if there are several. Shell completion
+    /// completes the element under the cursor.
+    ///
+    /// Only values this parser takes from the command line are split, values from environment
+    /// variables or the config source and values of other parsers with the same name are used
+    /// as is.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = long("tag")
+    ///     .argument::<u32>("TAG")
+    ///     .delimiter(',')
+    ///     .many()
+    ///     .to_options();
+    ///
+    /// let r = parser.run_inner(&["--tag", "1,2", "--tag=3"]).unwrap();
+    /// assert_eq!(r, vec![1, 2, 3]);
+    ///
+    /// let r = parser.run_inner(&["--tag", "1,x,3"]).unwrap_err().unwrap_stderr();
+    /// assert_eq!(r, "couldn't parse `x`: invalid digit found in string");
+    /// ```
+    ///
+    /// # Derive usage
+    /// ```rust
+    /// # use bpaf::*;
+    /// #[derive(Debug, Clone, Bpaf)]
+    /// struct Options {
+    ///     /// Tags to apply
+    ///     #[bpaf(argument("TAG"), delimiter(','))]
+    ///     tag: Vec<String>,
+    /// }
+    /// ```
+    #[must_use]
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = Some(delimiter);
+        self
+    }
+
+    /// Fail unless the value is inside the range
+    ///
+    /// Help message mentions the range: `[1..=64]`. Use
+    /// [`guard_with`](Parser::guard_with) for checks that are not covered by `range`,
+    /// [`min_len`](Self::min_len) and [`matches`](Self::matches).
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = long("jobs")
+    ///     .help("Number of jobs")
+    ///     .argument::<u32>("N")
+    ///     .range(1..=64)
+    ///     .to_options();
+    ///
+    /// let r = parser.run_inner(&["--jobs", "100"]).unwrap_err().unwrap_stderr();
+    /// assert_eq!(r, "`100`: must be in range 1..=64");
+    ///
+    /// let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    /// let expected = "\
+    /// Usage: --jobs=N
+    ///
+    /// Available options:
+    ///         --jobs=N  Number of jobs [1..=64]
+    ///     -h, --help    Prints help information
+    /// ";
+    /// assert_eq!(r, expected);
+    /// ```
+    #[must_use]
+    pub fn range<R>(mut self, range: R) -> Self
+    where
+        R: RangeBounds<T> + 'static,
+        T: PartialOrd + Display,
+    {
+        self.checks.range(range);
+        self
+    }
+
+    /// Fail unless the value is at least this many characters long
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = long("password")
+    ///     .argument::<String>("PASSWORD")
+    ///     .min_len(8)
+    ///     .to_options();
+    ///
+    /// let r = parser.run_inner(&["--password", "hunter2"]).unwrap_err().unwrap_stderr();
+    /// assert_eq!(r, "`hunter2`: must be at least 8 characters long");
+    /// ```
+    #[must_use]
+    pub fn min_len(mut self, len: usize) -> Self {
+        self.checks.min_len(len);
+        self
+    }
+
+    /// Fail unless the text of the value satisfies the check
+    ///
+    /// Check runs before the value is parsed.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = long("name")
+    ///     .argument::<String>("NAME")
+    ///     .matches(|s| s.chars().all(|c| c.is_ascii_alphanumeric()))
+    ///     .to_options();
+    ///
+    /// let r = parser.run_inner(&["--name", "a b"]).unwrap_err().unwrap_stderr();
+    /// assert_eq!(r, "`a b`: not a valid NAME");
+    /// ```
+    #[must_use]
+    pub fn matches<F>(mut self, check: F) -> Self
+    where
+        F: Fn(&str) -> bool + 'static,
+    {
+        self.checks.matches(check, self.metavar);
+        self
+    }
+
+    /// Ask for the value if it is missing
+    ///
+    /// When the argument is required and is not present on the command line, in environment
+    /// variables or in the config source `bpaf` shows the message and reads the value from
+    /// `stdin`. Parsed value goes through the usual pipeline of
+    /// [`parse`](crate::Parser::parse), [`guard`](crate::Parser::guard), etc. Empty input
+    /// counts as missing value.
+    ///
+    /// Prompts are only shown when `stdin` is a terminal, for tests use
+    /// [`set_prompt_input`](crate::Args::set_prompt_input). Arguments inside
+    /// [`optional`](crate::Parser::optional), [`fallback`](crate::Parser::fallback) or
+    /// [`many`](crate::Parser::many) are never missing so they are never asked about. Out of
+    /// several missing alternatives only the first one with a prompt is asked about.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let user = long("user").argument::<String>("USER").prompt("User name");
+    /// let port = long("port").argument::<u16>("PORT").prompt("Port");
+    /// let parser = construct!(user, port).to_options();
+    ///
+    /// let args = Args::from(&["--port", "8080"]).set_prompt_input(&["alice"]);
+    /// let r = parser.run_inner(args).unwrap();
+    /// assert_eq!(r, ("alice".to_owned(), 8080));
+    ///
+    /// let args = Args::from(&["--user", "bob"]).set_prompt_input(&["eighty"]);
+    /// let r = parser.run_inner(args).unwrap_err().unwrap_stderr();
+    /// assert_eq!(r, "couldn't parse: invalid digit found in string");
+    /// ```
+    #[must_use]
+    pub fn prompt(mut self, message: &'static str) -> Self {
+        self.prompt = Some(Prompt {
+            message,
+            hidden: false,
+        });
+        self
+    }
+
+    /// Ask for the value if it is missing without showing the input
+    ///
+    /// Same as [`prompt`](Self::prompt), but meant for passwords and tokens. If input can't be
+    /// hidden the value is treated as missing.
+    #[must_use]
+    pub fn prompt_hidden(mut self, message: &'static str) -> Self {
+        self.prompt = Some(Prompt {
+            message,
+            hidden: true,
+        });
+        self
+    }
+
     fn item(&self) -> Option<Item> {
         Some(Item::Argument {
             name: ShortLong::try_from(&self.named).ok()?,
             metavar: Metavar(self.metavar),
-            env: self.named.env.first().copied(),
-            help: self.named.help.clone(),
+            env: self.named.env.clone(),
+            help: self.checks.help(&self.named.help),
             shorts: self.named.short.clone(),
+            longs: self.named.long.clone(),
+            deprecated: self.named.deprecated.is_some(),
+            opt_value: self.bare.is_some(),
         })
     }
 
-    fn take_argument(&self, args: &mut State) -> Result<OsString, Error> {
-        match args.take_arg(&self.named, self.adjacent, Metavar(self.metavar)) {
+    /// Value for the argument and where it came from, `None` stands for a name without a value
+    fn take_argument(&self, args: &mut State) -> Result<(Option<OsString>, ValueSource), Error> {
+        let taken = if self.bare.is_some() {
+            Ok(args.take_opt_arg(&self.named))
+        } else {
+            args.take_arg(&self.named, self.adjacent, Metavar(self.metavar))
+                .map(|w| w.map(Some))
+        };
+        match taken {
             Ok(Some(w)) => {
+                // take the first element of a delimited value, the rest stays for the next parser
+                if let (Some(d), Some(val)) = (self.delimiter, w.as_ref().and_then(|w| w.to_str()))
+                {
+                    if let Some(pos) = val.find(d) {
+                        let end = pos + d.len_utf8();
+                        args.push_back(OsString::from(&val[end..]), &val[..end]);
+                        return Ok((Some(OsString::from(&val[..pos])), args.cli_source()));
+                    }
+                }
                 #[cfg(feature = "autocomplete")]
                 if args.touching_last_remove() {
                     args.push_metavar(self.metavar, &self.named.help, true);
                 }
-                Ok(w)
+                Ok((w, args.cli_source()))
             }
             Err(err) => {
                 #[cfg(feature = "autocomplete")]
@@ -661,9 +1052,24 @@ impl<T> ParseArgument<T> {
             _ => {
                 #[cfg(feature = "autocomplete")]
                 args.push_argument(&self.named, self.metavar);
-                if let Some(val) = self.named.env.iter().find_map(std::env::var_os) {
+                if let Some((name, val)) = self
+                    .named
+                    .env
+                    .iter()
+                    .find_map(|name| Some((*name, std::env::var_os(name)?)))
+                {
                     args.current = None;
-                    return Ok(val);
+                    return Ok((Some(val), ValueSource::Env(name)));
+                }
+
+                if let Some((key, val)) = self.named.config_value(args) {
+                    args.current = None;
+                    return Ok((Some(val.into()), ValueSource::Config(key)));
+                }
+
+                if let Some(val) = args.prompt(&self.named.provenance_name(), &self.prompt) {
+                    args.current = None;
+                    return Ok((Some(val), ValueSource::Prompt));
                 }
 
                 if let Some(item) = self.item() {
@@ -689,10 +1095,32 @@ where
     <T as std::str::FromStr>::Err: std::fmt::Display,
 {
     fn eval(&self, args: &mut State) -> Result<T, Error> {
-        let os = self.take_argument(args)?;
-        match parse_os_str::<T>(os) {
-            Ok(ok) => Ok(ok),
-            Err(err) => Err(Error(Message::ParseFailed(args.current, err))),
+        let (os, source) = self.take_argument(args)?;
+        // failures name the element of a delimited value rather than the rest of it
+        let element = self.delimiter.and(os.clone());
+        let res = match (os, &self.bare) {
+            (Some(os), _) => parse_checked(os, &self.checks, args),
+            (None, Some(bare)) => Ok(bare()),
+            (None, None) => unreachable!("only optional value arguments can be bare"),
+        };
+        match res {
+            Ok(ok) => {
+                args.record_source(|| self.named.provenance_name(), source);
+                args.check_deprecated(|| self.named.provenance_name(), self.named.deprecated);
+                Ok(ok)
+            }
+            Err(msg) => {
+                if let (Some(element), ValueSource::Cli(_)) = (element, &source) {
+                    args.set_value(element);
+                }
+                Err(Error(match (source, msg) {
+                    (
+                        ValueSource::Config(key),
+                        Message::ParseFailed(_, err) | Message::GuardFailed(_, err),
+                    ) => Message::ConfigFailed(key, err),
+                    (_, msg) => msg,
+                }))
+            }
         }
     }
 
@@ -705,12 +1133,31 @@ where
     }
 }
 
+/// Parse a value and run the checks on it, failures are reported against the current item
+fn parse_checked<T>(os: OsString, checks: &Checks<T>, args: &State) -> Result<T, Message>
+where
+    T: FromStr + 'static,
+    <T as std::str::FromStr>::Err: std::fmt::Display,
+{
+    let (current, locale) = (args.current, args.locale.as_ref());
+    checks
+        .check_text(&os, locale)
+        .map_err(|msg| Message::GuardFailed(current, msg))?;
+    let value = parse_os_str::<T>(os).map_err(|err| Message::ParseFailed(current, err))?;
+    checks
+        .check_value(&value, locale)
+        .map_err(|msg| Message::GuardFailed(current, msg))?;
+    Ok(value)
+}
+
 pub(crate) fn build_positional<T>(metavar: &'static str) -> ParsePositional<T> {
     ParsePositional {
         metavar,
         help: None,
         result_type: PhantomData,
         strict: false,
+        prompt: None,
+        checks: Checks::default(),
     }
 }
 
@@ -724,6 +1171,8 @@ pub struct ParsePositional<T> {
     help: Option<Doc>,
     result_type: PhantomData<T>,
     strict: bool,
+    prompt: Option<Prompt>,
+    checks: Checks<T>,
 }
 
 impl<T> ParsePositional<T> {
@@ -774,10 +1223,76 @@ impl<T> ParsePositional<T> {
         self
     }
 
+    /// Ask for the value if it is missing
+    ///
+    /// See [`ParseArgument::prompt`] for details.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = positional::<String>("HOST").prompt("Host to connect to").to_options();
+    ///
+    /// let r = parser.run_inner(Args::from(&[]).set_prompt_input(&["example.com"]));
+    /// assert_eq!(r.unwrap(), "example.com");
+    /// ```
+    #[must_use]
+    pub fn prompt(mut self, message: &'static str) -> Self {
+        self.prompt = Some(Prompt {
+            message,
+            hidden: false,
+        });
+        self
+    }
+
+    /// Ask for the value if it is missing without showing the input
+    ///
+    /// See [`ParseArgument::prompt_hidden`] for details.
+    #[must_use]
+    pub fn prompt_hidden(mut self, message: &'static str) -> Self {
+        self.prompt = Some(Prompt {
+            message,
+            hidden: true,
+        });
+        self
+    }
+
+    /// Fail unless the value is inside the range
+    ///
+    /// See [`ParseArgument::range`] for details.
+    #[must_use]
+    pub fn range<R>(mut self, range: R) -> Self
+    where
+        R: RangeBounds<T> + 'static,
+        T: PartialOrd + Display,
+    {
+        self.checks.range(range);
+        self
+    }
+
+    /// Fail unless the value is at least this many characters long
+    ///
+    /// See [`ParseArgument::min_len`] for details.
+    #[must_use]
+    pub fn min_len(mut self, len: usize) -> Self {
+        self.checks.min_len(len);
+        self
+    }
+
+    /// Fail unless the text of the value satisfies the check
+    ///
+    /// See [`ParseArgument::matches`] for details.
+    #[must_use]
+    pub fn matches<F>(mut self, check: F) -> Self
+    where
+        F: Fn(&str) -> bool + 'static,
+    {
+        self.checks.matches(check, self.metavar);
+        self
+    }
+
     fn meta(&self) -> Meta {
         let meta = Meta::from(Item::Positional {
             metavar: Metavar(self.metavar),
-            help: self.help.clone(),
+            help: self.checks.help(&self.help),
         });
         if self.strict {
             Meta::Strict(Box::new(meta))
@@ -826,10 +1341,25 @@ where
     <T as std::str::FromStr>::Err: std::fmt::Display,
 {
     fn eval(&self, args: &mut State) -> Result<T, Error> {
-        let os = parse_pos_word(args, self.strict, self.metavar, &self.help)?;
-        match parse_os_str::<T>(os) {
-            Ok(ok) => Ok(ok),
-            Err(err) => Err(Error(Message::ParseFailed(args.current, err))),
+        let (os, source) = match parse_pos_word(args, self.strict, self.metavar, &self.help) {
+            Ok(os) => (os, args.cli_source()),
+            Err(Error(Message::Missing(missing))) => {
+                match args.prompt(self.metavar, &self.prompt) {
+                    Some(os) => {
+                        args.current = None;
+                        (os, ValueSource::Prompt)
+                    }
+                    None => return Err(Error(Message::Missing(missing))),
+                }
+            }
+            Err(err) => return Err(err),
+        };
+        match parse_checked(os, &self.checks, args) {
+            Ok(ok) => {
+                args.record_source(|| self.metavar.to_owned(), source);
+                Ok(ok)
+            }
+            Err(msg) => Err(Error(msg)),
         }
     }
 
@@ -0,0 +1,128 @@
+//! Asking for values missing from the command line, see
+//! [`prompt`](crate::parsers::ParseArgument::prompt)
+//!
+//! Parsing goes as usual first. If it fails because some required items are missing the first
+//! of those items is marked as requested and parsing starts again from scratch, this time the
+//! parser with a prompt for the requested item asks for the value. Items are requested one at a
+//! time so out of several missing alternatives only the first one is asked about. Answers are
+//! kept between attempts so each item is asked about only once.
+
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    ffi::OsString,
+    io::{BufRead, Write},
+    rc::Rc,
+};
+
+use crate::{error::Message, provenance::item_name};
+
+/// Where answers come from
+#[derive(Debug)]
+pub(crate) enum Input {
+    /// Ask on stderr, read answers from stdin
+    Terminal,
+    /// Answers prepared in advance, see [`set_prompt_input`](crate::Args::set_prompt_input)
+    Scripted(VecDeque<String>),
+}
+
+/// Prompt configured on a parser
+#[derive(Debug, Clone)]
+pub(crate) struct Prompt {
+    pub(crate) message: &'static str,
+    /// Don't echo the input, for passwords and tokens
+    pub(crate) hidden: bool,
+}
+
+#[derive(Debug)]
+struct Prompter {
+    input: Input,
+    /// Names of items that were missing during previous attempts
+    requested: Vec<String>,
+    answers: Vec<(String, OsString)>,
+}
+
+/// Prompting state shared between all the copies of [`State`](crate::State)
+#[derive(Debug, Clone)]
+pub(crate) struct Prompts(Rc<RefCell<Prompter>>);
+
+impl Prompts {
+    pub(crate) fn new(input: Input) -> Self {
+        Self(Rc::new(RefCell::new(Prompter {
+            input,
+            requested: Vec::new(),
+            answers: Vec::new(),
+        })))
+    }
+
+    /// Mark the first item missing from the failure that wasn't requested before as requested
+    ///
+    /// Returns `true` if there is such item and parsing should be attempted again.
+    pub(crate) fn request(&self, failure: &Message) -> bool {
+        let names = match failure {
+            Message::Missing(items) => items.iter().filter_map(|m| item_name(&m.item)).collect(),
+            Message::NoEnv(name) => vec![(*name).to_owned()],
+            _ => Vec::new(),
+        };
+        let mut prompter = self.0.borrow_mut();
+        match names.into_iter().find(|n| !prompter.requested.contains(n)) {
+            Some(name) => {
+                prompter.requested.push(name);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Value for a missing item, asks for it once the item is requested
+    pub(crate) fn answer(&self, name: &str, prompt: &Prompt) -> Option<OsString> {
+        let mut prompter = self.0.borrow_mut();
+        if let Some((_, val)) = prompter.answers.iter().find(|(n, _)| n == name) {
+            return Some(val.clone());
+        }
+        if !prompter.requested.iter().any(|n| n == name) {
+            return None;
+        }
+        let answer = match &mut prompter.input {
+            Input::Terminal => ask(prompt)?,
+            Input::Scripted(lines) => lines.pop_front()?,
+        };
+        if answer.is_empty() {
+            return None;
+        }
+        let answer = OsString::from(answer);
+        prompter.answers.push((name.to_owned(), answer.clone()));
+        Some(answer)
+    }
+}
+
+fn ask(prompt: &Prompt) -> Option<String> {
+    let mut stderr = std::io::stderr();
+    let _ = write!(stderr, "{}: ", prompt.message);
+    let _ = stderr.flush();
+
+    // better to fail than to show a secret on the screen
+    if prompt.hidden && !set_echo(false) {
+        let _ = writeln!(stderr);
+        return None;
+    }
+    let mut line = String::new();
+    let res = std::io::stdin().lock().read_line(&mut line);
+    if prompt.hidden {
+        set_echo(true);
+        let _ = writeln!(stderr);
+    }
+    match res {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line.trim_end_matches(&['\r', '\n'][..]).to_owned()),
+    }
+}
+
+/// Turn terminal echo on or off, returns `true` on success
+fn set_echo(on: bool) -> bool {
+    std::process::Command::new("stty")
+        .arg(if on { "echo" } else { "-echo" })
+        .stdin(std::process::Stdio::inherit())
+        .status()
+        .map_or(false, |s| s.success())
+}
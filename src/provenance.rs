@@ -0,0 +1,87 @@
+//! Tracking where parsed values came from, see [`run_inner_with_provenance`]
+//!
+//! [`run_inner_with_provenance`]: crate::OptionParser::run_inner_with_provenance
+
+use crate::{item::Item, parsers::NamedArg};
+
+/// Where a value for a consumed item came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValueSource {
+    /// Command line, contains index of the item the value was taken from
+    ///
+    /// Index counts items passed to the parser, program name is not included
+    Cli(usize),
+    /// Environment variable with this name, see [`env`](NamedArg::env)
+    Env(&'static str),
+    /// Config source with this key, see [`config_source`](crate::OptionParser::config_source)
+    Config(&'static str),
+    /// Value was produced by [`fallback`](crate::Parser::fallback) or
+    /// [`fallback_with`](crate::Parser::fallback_with)
+    Fallback,
+    /// Value was produced by [`pure`](crate::pure) or [`pure_with`](crate::pure_with) used as an
+    /// alternative to this item
+    Pure,
+    /// Value was entered in response to a
+    /// [`prompt`](crate::parsers::ParseArgument::prompt)
+    Prompt,
+}
+
+/// Sources of all the values consumed by a parser, created with
+/// [`run_inner_with_provenance`](crate::OptionParser::run_inner_with_provenance)
+///
+/// Items are identified by their name: long name with dashes (`--threads`) if present, short
+/// name with a dash (`-t`) otherwise, env variable name for env-only items, metavariable for
+/// positional items and command name for commands.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Provenance {
+    pub(crate) entries: Vec<(String, ValueSource)>,
+}
+
+impl Provenance {
+    /// Get source of the first value consumed by an item with this name
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<ValueSource> {
+        self.entries
+            .iter()
+            .find_map(|(n, s)| if n == name { Some(*s) } else { None })
+    }
+
+    /// Iterate over all consumed items in order they were consumed
+    ///
+    /// Items consumed multiple times, for example with [`many`](crate::Parser::many), are listed
+    /// once per value.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, ValueSource)> {
+        self.entries.iter().map(|(n, s)| (n.as_str(), *s))
+    }
+
+    /// Check if there are no recorded items
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl NamedArg {
+    pub(crate) fn provenance_name(&self) -> String {
+        if let Some(long) = self.long.first() {
+            format!("--{}", long)
+        } else if let Some(short) = self.short.first() {
+            format!("-{}", short)
+        } else {
+            self.env.first().copied().unwrap_or_default().to_owned()
+        }
+    }
+}
+
+pub(crate) fn item_name(item: &Item) -> Option<String> {
+    match item {
+        Item::Any { .. } => None,
+        Item::Positional { metavar, .. } => Some(metavar.0.to_owned()),
+        Item::Command { name, .. } => Some((*name).to_owned()),
+        Item::Flag { name, .. } | Item::Argument { name, .. } => Some(match name.as_long() {
+            Some(long) => format!("--{}", long),
+            None => format!("-{}", name.as_short()?),
+        }),
+    }
+}
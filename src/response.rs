@@ -0,0 +1,201 @@
+//! Response files: `@path` arguments replaced with arguments read from a file, see
+//! [`response_files`](crate::OptionParser::response_files)
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+use crate::error::Message;
+
+/// Response files can include other response files, but only this deep
+const MAX_DEPTH: usize = 16;
+
+/// Reason response file couldn't be expanded
+#[derive(Debug, Clone)]
+pub(crate) enum ResponseError {
+    /// File can't be read, contains the OS error message
+    Io(String),
+    /// Quote opened on this line is never closed
+    UnterminatedQuote,
+    /// File includes itself, directly or through other files
+    Cycle,
+    /// Files are nested deeper than [`MAX_DEPTH`]
+    TooDeep,
+}
+
+/// Replace every `@path` item with arguments from the file
+///
+/// Items after `--` are left as is, including `--` that comes from a response file, so are
+/// items that name an empty path: `@`.
+pub(crate) fn expand<I>(items: I) -> Result<Vec<OsString>, Message>
+where
+    I: Iterator<Item = OsString>,
+{
+    let mut res = Vec::new();
+    let mut stack = Vec::new();
+    let mut pos_only = false;
+    for item in items {
+        match response_path(&item) {
+            Some(path) if !pos_only => {
+                expand_file(Path::new(path), &mut stack, &mut pos_only, &mut res)?;
+            }
+            _ => {
+                pos_only |= item == "--";
+                res.push(item);
+            }
+        }
+    }
+    Ok(res)
+}
+
+fn response_path(item: &OsString) -> Option<&str> {
+    let path = item.to_str()?.strip_prefix('@')?;
+    if path.is_empty() {
+        None
+    } else {
+        Some(path)
+    }
+}
+
+fn expand_file(
+    path: &Path,
+    stack: &mut Vec<PathBuf>,
+    pos_only: &mut bool,
+    res: &mut Vec<OsString>,
+) -> Result<(), Message> {
+    let fail = |line, err| Message::ResponseFile(path.display().to_string(), line, err);
+
+    if stack.len() >= MAX_DEPTH {
+        return Err(fail(None, ResponseError::TooDeep));
+    }
+    let content =
+        std::fs::read_to_string(path).map_err(|e| fail(None, ResponseError::Io(e.to_string())))?;
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_owned());
+    if stack.contains(&canonical) {
+        return Err(fail(None, ResponseError::Cycle));
+    }
+
+    let words =
+        split(&content).map_err(|line| fail(Some(line), ResponseError::UnterminatedQuote))?;
+    stack.push(canonical);
+    for word in words {
+        let word = OsString::from(word);
+        match response_path(&word) {
+            // nested files are relative to the file that mentions them
+            Some(nested) if !*pos_only => {
+                let nested = path.parent().unwrap_or(Path::new("")).join(nested);
+                expand_file(&nested, stack, pos_only, res)?;
+            }
+            _ => {
+                *pos_only |= word == "--";
+                res.push(word);
+            }
+        }
+    }
+    stack.pop();
+    Ok(())
+}
+
+/// Split response file contents into words using shell-like rules
+///
+/// Words are separated by whitespace, single quotes keep everything inside as is, double
+/// quotes allow escaping `"` and `\` with a backslash, backslash outside of quotes escapes
+/// any character. Lines starting with `#`, possibly after some whitespace, are comments, `#`
+/// anywhere else is a part of a word. Returns a line number of an unterminated quote on failure.
+pub(crate) fn split(content: &str) -> Result<Vec<String>, usize> {
+    let mut res = Vec::new();
+    let mut word = None::<String>;
+    let mut line = 1;
+    // only whitespace so far on this line
+    let mut line_start = true;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let was_line_start = std::mem::replace(&mut line_start, false);
+        match c {
+            '\n' | ' ' | '\t' | '\r' => {
+                res.extend(word.take());
+                if c == '\n' {
+                    line += 1;
+                }
+                line_start = was_line_start || c == '\n';
+            }
+            '#' if was_line_start => {
+                line_start = true;
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        line += 1;
+                        break;
+                    }
+                }
+            }
+            '\'' => {
+                let start = line;
+                let w = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => {
+                            line += usize::from(c == '\n');
+                            w.push(c);
+                        }
+                        None => return Err(start),
+                    }
+                }
+            }
+            '"' => {
+                let start = line;
+                let w = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') if matches!(chars.peek(), Some('"' | '\\')) => {
+                            w.extend(chars.next());
+                        }
+                        Some(c) => {
+                            line += usize::from(c == '\n');
+                            w.push(c);
+                        }
+                        None => return Err(start),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                // line continuation
+                Some('\n') => line += 1,
+                Some(c) => word.get_or_insert_with(String::new).push(c),
+                None => {}
+            },
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    res.extend(word);
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split;
+
+    #[test]
+    fn split_words() {
+        let input = "--a b\n# comment 'x\n  'c d' \"e \\\" f\" g\\ h ''\n--x=\"y z\"";
+        assert_eq!(
+            split(input).unwrap(),
+            ["--a", "b", "c d", "e \" f", "g h", "", "--x=y z"]
+        );
+    }
+
+    #[test]
+    fn split_comments_at_line_start() {
+        let input = "a #b\n  # c 'd\nc#d # e\n";
+        assert_eq!(split(input).unwrap(), ["a", "#b", "c#d", "#", "e"]);
+    }
+
+    #[test]
+    fn split_unterminated() {
+        assert_eq!(split("a\nb 'c\nd"), Err(2));
+        assert_eq!(split("\"a"), Err(1));
+    }
+}
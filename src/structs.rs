@@ -3,7 +3,9 @@ use crate::{
     args::State,
     buffer::MetaInfo,
     error::{Message, MissingItem},
-    Doc, Error, Meta, Parser,
+    meta::Note,
+    provenance::item_name,
+    Doc, Error, Meta, Parser, ValueSource,
 };
 use std::marker::PhantomData;
 
@@ -35,7 +37,10 @@ where
                 args.swap_comps(&mut clone);
                 if e.can_catch() {
                     match (self.fallback)() {
-                        Ok(ok) => Ok(ok),
+                        Ok(ok) => {
+                            record_fallback(args, &self.inner);
+                            Ok(ok)
+                        }
                         Err(e) => Err(Error(Message::PureFailed(e.to_string()))),
                     }
                 } else {
@@ -50,8 +55,8 @@ where
         if self.value_str.is_empty() {
             m
         } else {
-            let buf = Doc::from(self.value_str.as_str());
-            Meta::Suffix(Box::new(m), Box::new(buf))
+            let note = Note::Doc(Doc::from(self.value_str.as_str()));
+            Meta::Suffix(Box::new(m), Box::new(note))
         }
     }
 }
@@ -234,7 +239,7 @@ where
     }
 
     fn meta(&self) -> Meta {
-        Meta::Skip
+        Meta::Hidden(Box::new(self.inner.meta()))
     }
 }
 
@@ -293,7 +298,7 @@ impl<T> Parser<T> for ParseOrElse<T> {
             Err(err) => (None, Some(err)),
         };
 
-        if this_or_that_picks_first(
+        let picks_first = this_or_that_picks_first(
             err_a,
             err_b,
             args,
@@ -301,7 +306,23 @@ impl<T> Parser<T> for ParseOrElse<T> {
             &mut args_b,
             #[cfg(feature = "autocomplete")]
             comp_items,
-        )? {
+        )?;
+
+        if args.tracks_sources() {
+            // value produced by `pure` in place of a real item
+            let (winner, loser) = if picks_first {
+                (&self.this, &self.that)
+            } else {
+                (&self.that, &self.this)
+            };
+            if let Meta::Skip = winner.meta() {
+                if let Some(name) = Meta::first_item(&loser.meta()).and_then(|i| item_name(&i)) {
+                    args.record_source(|| name, ValueSource::Pure);
+                }
+            }
+        }
+
+        if picks_first {
             Ok(res_a.unwrap())
         } else {
             Ok(res_b.unwrap())
@@ -472,6 +493,7 @@ where
                 #[cfg(feature = "autocomplete")]
                 args.swap_comps(&mut clone);
                 if e.can_catch() {
+                    record_fallback(args, &self.inner);
                     Ok(self.value.clone())
                 } else {
                     Err(Error(e))
@@ -485,8 +507,17 @@ where
         if self.value_str.is_empty() {
             m
         } else {
-            let buf = Doc::from(self.value_str.as_str());
-            Meta::Suffix(Box::new(m), Box::new(buf))
+            let note = Note::Doc(Doc::from(self.value_str.as_str()));
+            Meta::Suffix(Box::new(m), Box::new(note))
+        }
+    }
+}
+
+/// Record that the first item of a parser got its value from a fallback
+fn record_fallback<T, P: Parser<T>>(args: &mut State, inner: &P) {
+    if args.tracks_sources() {
+        if let Some(name) = Meta::first_item(&inner.meta()).and_then(|i| item_name(&i)) {
+            args.record_source(|| name, ValueSource::Fallback);
         }
     }
 }
@@ -570,7 +601,35 @@ where
         if (self.check)(&t) {
             Ok(t)
         } else {
-            Err(Error(Message::GuardFailed(args.current, self.message)))
+            Err(Error(Message::GuardFailed(
+                args.current,
+                self.message.to_owned(),
+            )))
+        }
+    }
+
+    fn meta(&self) -> Meta {
+        self.inner.meta()
+    }
+}
+
+/// Parser fails with a message produced by check, created with [`guard_with`](Parser::guard_with).
+pub struct ParseGuardWith<P, F> {
+    pub(crate) inner: P,
+    pub(crate) check: F,
+}
+
+impl<T, P, F, E> Parser<T> for ParseGuardWith<P, F>
+where
+    P: Parser<T>,
+    F: Fn(&T) -> Result<(), E>,
+    E: ToString,
+{
+    fn eval(&self, args: &mut State) -> Result<T, Error> {
+        let t = self.inner.eval(args)?;
+        match (self.check)(&t) {
+            Ok(()) => Ok(t),
+            Err(e) => Err(Error(Message::GuardFailed(args.current, e.to_string()))),
         }
     }
 
@@ -939,6 +998,7 @@ pub struct ParseComp<P, F> {
     pub(crate) inner: P,
     pub(crate) op: F,
     pub(crate) group: Option<String>,
+    pub(crate) cache: crate::complete_cache::CompCache,
 }
 
 #[cfg(feature = "autocomplete")]
@@ -949,16 +1009,68 @@ impl<P, F> ParseComp<P, F> {
         self.group = Some(group.into());
         self
     }
+
+    #[must_use]
+    /// Limit how long the completion function can take to answer a single request
+    ///
+    /// Completion function runs in a separate process and when the budget runs out `bpaf`
+    /// offers no values for this item instead of making the shell wait. The process keeps
+    /// running and with [`cache`](ParseComp::cache) enabled its results are used by the next
+    /// request. Only one such process runs at a time for the same request.
+    ///
+    /// A separate process can only be started when the app runs from a shell completion script,
+    /// otherwise completion function runs to the end and results that come late are dropped.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// # use std::time::Duration;
+    /// fn remote_branches(input: &String) -> Vec<(String, Option<String>)> {
+    ///     // talks to a slow server
+    /// # let _ = input;
+    /// # Vec::new()
+    /// }
+    ///
+    /// let branch = positional::<String>("BRANCH")
+    ///     .complete(remote_branches)
+    ///     .timeout(Duration::from_millis(200))
+    ///     .cache(Duration::from_secs(600));
+    /// # drop(branch);
+    /// ```
+    pub fn timeout(mut self, budget: std::time::Duration) -> Self {
+        self.cache.timeout = Some(budget);
+        self
+    }
+
+    #[must_use]
+    /// Keep results of the completion function on disk for reuse by later requests
+    ///
+    /// Results are stored in `$XDG_CACHE_HOME/bpaf/<app>` (`~/.cache/bpaf/<app>` if the variable
+    /// is not set) and keyed by the command path, [`group`](ParseComp::group), names of the item,
+    /// metavariable and the word being completed - completion function shouldn't depend on
+    /// anything else. Results
+    /// of a function given to [`complete_with_context`](crate::Parser::complete_with_context)
+    /// are also keyed by the items before the word being completed.
+    /// Results older than `max_age` are still served, while a background process refreshes them,
+    /// one process at a time.
+    ///
+    /// See [`timeout`](ParseComp::timeout) for an example.
+    pub fn cache(mut self, max_age: std::time::Duration) -> Self {
+        self.cache.max_age = Some(max_age);
+        self
+    }
 }
 
 #[cfg(feature = "autocomplete")]
-impl<P, T, F, M> Parser<T> for ParseComp<P, F>
-where
-    P: Parser<T> + Sized,
-    M: Into<String>,
-    F: Fn(&T) -> Vec<(M, Option<M>)>,
-{
-    fn eval(&self, args: &mut State) -> Result<T, Error> {
+impl<P, F> ParseComp<P, F> {
+    /// Replace metavariables produced by the inner parser with suggestions made by `op`
+    ///
+    /// `with_context` tells if `op` looks at the [`CompContext`](crate::CompContext), cached
+    /// suggestions then depend on it too
+    fn eval_with<T, G>(&self, args: &mut State, with_context: bool, op: G) -> Result<T, Error>
+    where
+        P: Parser<T>,
+        G: Fn(&T, &crate::CompContext) -> crate::complete_cache::Suggestions,
+    {
         // stash old
         let mut comp_items = Vec::new();
         args.swap_comps_with(&mut comp_items);
@@ -977,27 +1089,49 @@ where
 
         let res = res?;
 
+        if args.comp_ref().is_none() {
+            return Ok(res);
+        }
+
         // completion function generates suggestions based on the parsed inner value, for
         // that `res` must contain a parsed value
         let depth = args.depth();
+        let word = args
+            .items
+            .last()
+            .map_or_else(String::new, |w| w.os_str().to_string_lossy().into_owned());
+        let path = args.path.clone();
+        let context = crate::CompContext::new(args.resolved_items(), path.clone());
+        let mut item = String::new();
+        crate::complete_cache::item_key(&self.inner.meta(), &mut item);
         if let Some(comp) = &mut args.comp_mut() {
             for ci in comp_items {
                 let is_meta = ci.is_metavar();
                 if let Some(is_arg) = is_meta {
-                    let suggestions = (self.op)(&res);
+                    let ctx = crate::complete_cache::Context {
+                        path: &path,
+                        group: self.group.as_deref(),
+                        item: &item,
+                        meta: ci.metavar().unwrap_or(""),
+                        word: &word,
+                        before: if with_context { context.before() } else { &[] },
+                    };
+                    let suggestions = self.cache.get(&ctx, || op(&res, &context));
+                    let suggestions = match suggestions {
+                        Some(suggestions) => suggestions,
+                        // out of time, better to offer nothing than to make the shell wait
+                        None => {
+                            comp.push_shell(crate::complete_shell::ShellComp::Nothing, depth);
+                            continue;
+                        }
+                    };
                     // strip metavar when completion makes a single good suggestion
                     if suggestions.len() != 1 {
                         comp.push_comp(ci);
                     }
                     for (replacement, description) in suggestions {
                         let group = self.group.clone();
-                        comp.push_value(
-                            replacement.into(),
-                            description.map(Into::into),
-                            group,
-                            depth,
-                            is_arg,
-                        );
+                        comp.push_value(replacement, description, group, depth, is_arg);
                     }
                 } else {
                     comp.push_comp(ci);
@@ -1006,12 +1140,54 @@ where
         }
         Ok(res)
     }
+}
+
+#[cfg(feature = "autocomplete")]
+impl<P, T, F, M> Parser<T> for ParseComp<P, F>
+where
+    P: Parser<T> + Sized,
+    M: Into<String>,
+    F: Fn(&T) -> Vec<(M, Option<M>)>,
+{
+    fn eval(&self, args: &mut State) -> Result<T, Error> {
+        self.eval_with(args, false, |res, _| suggestions((self.op)(res)))
+    }
 
     fn meta(&self) -> Meta {
-        self.inner.meta()
+        Meta::Complete(
+            Box::new(self.inner.meta()),
+            crate::complete_shell::CompHint::Dynamic,
+        )
     }
 }
 
+#[cfg(feature = "autocomplete")]
+impl<P, T, F, M> Parser<T> for ParseComp<P, crate::complete_context::WithContext<F>>
+where
+    P: Parser<T> + Sized,
+    M: Into<String>,
+    F: Fn(&T, &crate::CompContext) -> Vec<(M, Option<M>)>,
+{
+    fn eval(&self, args: &mut State) -> Result<T, Error> {
+        self.eval_with(args, true, |res, ctx| suggestions((self.op.0)(res, ctx)))
+    }
+
+    fn meta(&self) -> Meta {
+        Meta::Complete(
+            Box::new(self.inner.meta()),
+            crate::complete_shell::CompHint::Dynamic,
+        )
+    }
+}
+
+#[cfg(feature = "autocomplete")]
+fn suggestions<M: Into<String>>(items: Vec<(M, Option<M>)>) -> crate::complete_cache::Suggestions {
+    items
+        .into_iter()
+        .map(|(r, d)| (r.into(), d.map(Into::into)))
+        .collect()
+}
+
 /*
 #[cfg(feature = "autocomplete")]
 pub struct ParseCompStyle<P> {
@@ -1078,7 +1254,7 @@ where
             // nothing to consume, might as well skip this segment right now
             // it will most likely fail, but it doesn't matter, we are only looking for the
             // left most match
-            if before == 0 {
+            if scratch.is_empty() {
                 continue;
             }
 
@@ -1094,8 +1270,9 @@ where
 
             // values consumed by adjacent must be actually adjacent - if a scope contains
             // already parsed values inside we need to trim it
-            if original_scope.end - start > before {
-                this_arg.set_scope(this_arg.adjacently_available_from(start));
+            let available = this_arg.adjacently_available_from(start);
+            if available.end < original_scope.end {
+                this_arg.set_scope(available);
             }
 
             loop {
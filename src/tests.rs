@@ -94,3 +94,25 @@ fn de_yoda() {
     let r = parser.run_inner(&["-a", "-b"]).unwrap();
     assert_eq!(r, (true, true));
 }
+
+#[test]
+fn failures_remember_output_settings() {
+    use crate::{long, ParseFailure, Parser};
+    let parser = long("name")
+        .argument::<String>("NAME")
+        .to_options()
+        .help_width(40)
+        .pager(true);
+
+    let mut layout = crate::buffer::Layout::default();
+    layout.set_width(40);
+    for args in [&["--help"][..], &[][..]] {
+        match parser.run_inner(args).unwrap_err() {
+            ParseFailure::Stdout(output, _) | ParseFailure::Stderr(output) => {
+                assert!(output.pager);
+                assert_eq!(output.layout, layout);
+            }
+            ParseFailure::Completion(_) => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Value checks attached to [`ParseArgument`](crate::parsers::ParseArgument) and
+//! [`ParsePositional`](crate::parsers::ParsePositional): `range`, `min_len` and `matches`
+
+use std::{
+    ffi::OsStr,
+    fmt::Display,
+    ops::{Bound, RangeBounds},
+    rc::Rc,
+};
+
+use crate::{
+    buffer::Doc,
+    locale::{self, Catalog, Text},
+};
+
+/// Failed check: message from the catalog and the value for its placeholder
+type Failure = (Text, String);
+
+/// Check on the text of the value, before it gets parsed
+type TextCheck = Rc<dyn Fn(&str) -> Result<(), Failure>>;
+
+/// Check on the parsed value
+type ValueCheck<T> = Rc<dyn Fn(&T) -> Result<(), Failure>>;
+
+/// Checks to perform on a value, failed check produces a message
+pub(crate) struct Checks<T> {
+    /// checks on the text of the value, before it gets parsed
+    text: Vec<TextCheck>,
+    /// checks on the parsed value
+    value: Vec<ValueCheck<T>>,
+    /// extra text for the help message, such as `[1..=64]`
+    help: Vec<String>,
+}
+
+impl<T> Default for Checks<T> {
+    fn default() -> Self {
+        Self {
+            text: Vec::new(),
+            value: Vec::new(),
+            help: Vec::new(),
+        }
+    }
+}
+
+impl<T> Clone for Checks<T> {
+    fn clone(&self) -> Self {
+        Self {
+            text: self.text.clone(),
+            value: self.value.clone(),
+            help: self.help.clone(),
+        }
+    }
+}
+
+impl<T> Checks<T> {
+    pub(crate) fn range<R>(&mut self, range: R)
+    where
+        R: RangeBounds<T> + 'static,
+        T: PartialOrd + Display,
+    {
+        let text = range_text(range.start_bound(), range.end_bound());
+        self.help.push(format!("[{}]", text));
+        self.value.push(Rc::new(move |v: &T| {
+            if range.contains(v) {
+                Ok(())
+            } else {
+                Err((Text::OutOfRange, text.clone()))
+            }
+        }));
+    }
+
+    pub(crate) fn min_len(&mut self, len: usize) {
+        self.text.push(Rc::new(move |s: &str| {
+            if s.chars().count() >= len {
+                Ok(())
+            } else {
+                Err((Text::TooShort, len.to_string()))
+            }
+        }));
+    }
+
+    pub(crate) fn matches<F>(&mut self, check: F, metavar: &'static str)
+    where
+        F: Fn(&str) -> bool + 'static,
+    {
+        self.text.push(Rc::new(move |s: &str| {
+            if check(s) {
+                Ok(())
+            } else {
+                Err((Text::NotValid, metavar.to_owned()))
+            }
+        }));
+    }
+
+    /// Check the text of the value, non utf8 parts are replaced for the check
+    pub(crate) fn check_text(&self, os: &OsStr, locale: Option<&Catalog>) -> Result<(), String> {
+        if self.text.is_empty() {
+            return Ok(());
+        }
+        let text = os.to_string_lossy();
+        self.text
+            .iter()
+            .try_for_each(|check| check(&text))
+            .map_err(|failure| message(locale, failure))
+    }
+
+    pub(crate) fn check_value(&self, value: &T, locale: Option<&Catalog>) -> Result<(), String> {
+        self.value
+            .iter()
+            .try_for_each(|check| check(value))
+            .map_err(|failure| message(locale, failure))
+    }
+
+    /// Add automatically generated notes to the help message
+    pub(crate) fn help(&self, help: &Option<Doc>) -> Option<Doc> {
+        if self.help.is_empty() {
+            return help.clone();
+        }
+        let mut res = help.clone().unwrap_or_default();
+        for note in &self.help {
+            if !res.is_empty() {
+                res.text(" ");
+            }
+            res.text(note);
+        }
+        Some(res)
+    }
+}
+
+fn message(locale: Option<&Catalog>, (text, arg): Failure) -> String {
+    locale::format(locale, text, &[&arg])
+}
+
+/// Render bounds the same way Rust range literals look: `1..=64`, `1..`, `..64`
+fn range_text<T: Display>(start: Bound<&T>, end: Bound<&T>) -> String {
+    let start = match start {
+        Bound::Included(s) => s.to_string(),
+        // not possible with range literals, only with a pair of bounds
+        Bound::Excluded(s) => format!("{}<", s),
+        Bound::Unbounded => String::new(),
+    };
+    match end {
+        Bound::Included(e) => format!("{}..={}", start, e),
+        Bound::Excluded(e) => format!("{}..{}", start, e),
+        Bound::Unbounded => format!("{}..", start),
+    }
+}
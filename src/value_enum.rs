@@ -0,0 +1,176 @@
+//! Arguments with a fixed set of values, see [`ValueEnum`]
+
+use std::marker::PhantomData;
+
+use crate::{
+    error::{textual_part, Message},
+    locale::{self, Catalog, Text},
+    meta::Note,
+    meta_youmean::damerau_levenshtein,
+    Doc, Error, Meta, Parser, State,
+};
+
+/// A type with a fixed set of accepted values
+///
+/// Usually derived with `#[derive(Bpaf)]` and `#[bpaf(value_enum)]` annotation on an enum with
+/// unit variants. Derived implementation also implements [`FromStr`](std::str::FromStr) that
+/// accepts kebab-case variant names, so the type can be used directly with
+/// [`argument`](crate::parsers::NamedArg::argument) and [`positional`](crate::positional), and
+/// [`Display`](std::fmt::Display) that prints the same names back. Doc comments on variants
+/// become descriptions in shell completion.
+///
+/// Use [`possible_values`](Parser::possible_values), or `possible_values` annotation with the
+/// derive macro, to list accepted values in the help message and to offer them in shell
+/// completion.
+///
+/// ```rust
+/// # use bpaf::*;
+/// #[derive(Debug, Clone, Copy, Bpaf)]
+/// #[bpaf(value_enum)]
+/// enum Mode {
+///     /// Go fast
+///     Fast,
+///     /// Go slow
+///     Slow,
+/// }
+///
+/// let parser = long("mode")
+///     .argument::<Mode>("MODE")
+///     .possible_values()
+///     .to_options();
+///
+/// let r = parser.run_inner(&["--mode", "fast"]).unwrap();
+/// assert!(matches!(r, Mode::Fast));
+///
+/// let r = parser.run_inner(&["--mode", "fsat"]).unwrap_err().unwrap_stderr();
+/// assert_eq!(r, "couldn't parse `fsat`: expected one of `fast`, `slow`, did you mean `fast`?");
+/// ```
+pub trait ValueEnum: Sized {
+    /// All accepted values with optional descriptions, in declaration order
+    fn variants() -> &'static [(&'static str, Option<&'static str>)];
+}
+
+/// Error message for a value that doesn't match any of the variants, used by derive macro
+#[doc(hidden)]
+pub fn unknown_value(value: &str, variants: &[(&str, Option<&str>)]) -> String {
+    localized_unknown_value(None, Some(value), variants)
+}
+
+/// Error message for a value that doesn't match any of the variants using a message catalog,
+/// suggests the closest variant if the value is known
+fn localized_unknown_value(
+    locale: Option<&Catalog>,
+    value: Option<&str>,
+    variants: &[(&str, Option<&str>)],
+) -> String {
+    let mut expected = String::new();
+    for (ix, (name, _)) in variants.iter().enumerate() {
+        if ix > 0 {
+            expected.push_str(", ");
+        }
+        expected.push('`');
+        expected.push_str(name);
+        expected.push('`');
+    }
+
+    let mut best_match = None;
+    let mut best_dist = usize::MAX;
+    for (name, _) in variants {
+        let dist = value.map_or(0, |value| damerau_levenshtein(value, name));
+        if best_dist > dist && dist > 0 && dist < 4 {
+            best_dist = dist;
+            best_match = Some(name);
+        }
+    }
+    match best_match {
+        Some(name) => {
+            let name = format!("`{}`", name);
+            locale::format(locale, Text::ExpectedOneOfSuggest, &[&expected, &name])
+        }
+        None => locale::format(locale, Text::ExpectedOneOf, &[&expected]),
+    }
+}
+
+/// Parser that lists values of a [`ValueEnum`] in help and completion, created with
+/// [`possible_values`](Parser::possible_values)
+pub struct ParsePossibleValues<P, T> {
+    pub(crate) inner: P,
+    pub(crate) ty: PhantomData<T>,
+}
+
+impl<P, T> Parser<T> for ParsePossibleValues<P, T>
+where
+    P: Parser<T>,
+    T: ValueEnum,
+{
+    fn eval(&self, args: &mut State) -> Result<T, Error> {
+        #[cfg(feature = "autocomplete")]
+        let res = {
+            // same as with ParseComp inner parser replaces the value with a metavar, unlike
+            // ParseComp there's no parsed value to look at - partial input won't parse,
+            // values are filtered by the text being completed instead
+            let mut comp_items = Vec::new();
+            args.swap_comps_with(&mut comp_items);
+            let res = self.inner.eval(args);
+            args.swap_comps_with(&mut comp_items);
+
+            let depth = args.depth();
+            let lit = args.comp_lit().unwrap_or_default().to_owned();
+            if let Some(comp) = args.comp_mut() {
+                for ci in comp_items {
+                    if let Some(is_arg) = ci.is_metavar() {
+                        let values = T::variants()
+                            .iter()
+                            .filter(|(name, _)| name.starts_with(lit.as_str()))
+                            .collect::<Vec<_>>();
+                        // strip metavar when there's a single good suggestion
+                        if values.len() != 1 {
+                            comp.push_comp(ci);
+                        }
+                        for (name, help) in values {
+                            let help = help.map(str::to_owned);
+                            comp.push_value((*name).to_owned(), help, None, depth, is_arg);
+                        }
+                    } else {
+                        comp.push_comp(ci);
+                    }
+                }
+            }
+            res
+        };
+
+        #[cfg(not(feature = "autocomplete"))]
+        let res = self.inner.eval(args);
+
+        // message from `FromStr` is in English, use the catalog instead
+        match res {
+            Err(Error(Message::ParseFailed(mix, _))) => {
+                let value = textual_part(args, mix);
+                let locale = args.locale.as_ref();
+                let msg = localized_unknown_value(locale, value.as_deref(), T::variants());
+                Err(Error(Message::ParseFailed(mix, msg)))
+            }
+            res => res,
+        }
+    }
+
+    fn meta(&self) -> Meta {
+        let mut values = String::new();
+        for (ix, (name, _)) in T::variants().iter().enumerate() {
+            if ix > 0 {
+                values.push_str(", ");
+            }
+            values.push_str(name);
+        }
+        let note = Note::Text(Text::PossibleValues, vec![Doc::from(values.as_str())]);
+        let meta = Meta::Suffix(Box::new(self.inner.meta()), Box::new(note));
+
+        #[cfg(feature = "autocomplete")]
+        let meta = Meta::Complete(
+            Box::new(meta),
+            crate::complete_shell::CompHint::Values(T::variants()),
+        );
+
+        meta
+    }
+}
@@ -0,0 +1,73 @@
+//! Problems that don't prevent parsing from succeeding, see
+//! [`run_inner_with_warnings`](crate::OptionParser::run_inner_with_warnings)
+
+use crate::{
+    buffer::{Block, Color, Doc, Layout, Stream, Token},
+    locale::{self, Catalog, Text},
+};
+
+/// A problem with the command line that doesn't prevent parsing from succeeding
+///
+/// [`run`](crate::OptionParser::run) prints warnings to `stderr`,
+/// [`run_inner_with_warnings`](crate::OptionParser::run_inner_with_warnings) returns them along
+/// with the parsed value. `Display` renders an English message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Warning {
+    /// A [`deprecated`](crate::parsers::NamedArg::deprecated) item was used
+    Deprecated {
+        /// Long name with dashes (`--out`) if present, short name with a dash (`-o`) otherwise,
+        /// env variable name for env-only items and command name for commands
+        name: String,
+        /// Message passed to `deprecated`
+        message: &'static str,
+    },
+}
+
+impl Warning {
+    pub(crate) fn render(&self, locale: Option<&Catalog>) -> Doc {
+        let mut doc = Doc::default();
+        match self {
+            Warning::Deprecated { name, message } => {
+                doc.template(locale::get(locale, Text::DeprecatedUsed), |doc, ix| {
+                    if ix == 0 {
+                        doc.token(Token::BlockStart(Block::TermRef));
+                        doc.literal(name);
+                        doc.token(Token::BlockEnd(Block::TermRef));
+                    } else {
+                        doc.text(message);
+                    }
+                });
+            }
+        }
+        doc
+    }
+
+    /// Print the warning to `stderr` using width settings from the parser
+    pub(crate) fn print(&self, layout: Layout, locale: Option<&Catalog>) {
+        let color = Color::default();
+        let prefix = locale::get(locale, Text::Warning);
+        #[allow(unused_mut)]
+        let mut warning;
+        #[cfg(not(feature = "color"))]
+        {
+            warning = prefix;
+        }
+
+        #[cfg(feature = "color")]
+        {
+            warning = String::new();
+            color.push_str(crate::buffer::Style::Invalid, &mut warning, prefix);
+        }
+
+        let layout = layout.or_terminal(Stream::Stderr);
+        let doc = self.render(locale);
+        eprintln!("{} {}", warning, doc.render_console(true, color, layout));
+    }
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(None).monochrome(true))
+    }
+}
@@ -0,0 +1,204 @@
+use bpaf::*;
+
+#[test]
+fn unique_prefix_is_accepted() {
+    let verbose = long("verbose").switch();
+    let output = long("output").argument::<String>("FILE");
+    let parser = construct!(verbose, output)
+        .to_options()
+        .allow_abbreviations();
+
+    let r = parser.run_inner(&["--verb", "--out", "a.txt"]).unwrap();
+    assert_eq!(r, (true, "a.txt".to_owned()));
+
+    let r = parser.run_inner(&["--o=b.txt"]).unwrap();
+    assert_eq!(r, (false, "b.txt".to_owned()));
+
+    // without the setting prefixes are rejected
+    let verbose = long("verbose").switch();
+    let output = long("output").argument::<String>("FILE");
+    let parser = construct!(verbose, output).to_options();
+    let r = parser
+        .run_inner(&["--verb", "--output", "a.txt"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "no such flag: `--verb`, did you mean `--verbose`?");
+}
+
+#[test]
+fn ambiguous_prefix_lists_candidates() {
+    let verbose = long("verbose").switch();
+    let verify = long("verify").switch();
+    let parser = construct!(verbose, verify)
+        .to_options()
+        .allow_abbreviations();
+
+    let err = parser.run_inner(&["--verbose", "--ver"]).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        Some(&ErrorKind::AmbiguousPrefix {
+            index: 1,
+            name: "--ver".to_owned(),
+            candidates: vec!["--verbose".to_owned(), "--verify".to_owned()],
+        })
+    );
+    assert_eq!(
+        err.to_json().unwrap(),
+        r#"{"kind":"ambiguous_prefix","index":1,"name":"--ver","candidates":["--verbose","--verify"],"message":"`--ver` is ambiguous, it could be `--verbose`, `--verify`"}"#
+    );
+
+    // exact match wins over a longer name
+    let ver = long("ver").switch();
+    let version = long("version-info").switch();
+    let parser = construct!(ver, version).to_options().allow_abbreviations();
+    let r = parser.run_inner(&["--ver", "--vers"]).unwrap();
+    assert_eq!(r, (true, true));
+
+    // help is a long name too
+    let help = long("hello").switch().to_options().allow_abbreviations();
+    let r = help.run_inner(&["--he"]).unwrap_err().unwrap_stderr();
+    assert_eq!(r, "`--he` is ambiguous, it could be `--hello`, `--help`");
+
+    let r = help
+        .locale(locale::German)
+        .run_inner(&["--he"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "`--he` ist mehrdeutig, in Frage kommen `--hello`, `--help`"
+    );
+}
+
+#[test]
+fn case_insensitive_longs() {
+    let verbose = long("verbose").switch();
+    let color = long("color").negatable();
+    let parser = construct!(verbose, color)
+        .to_options()
+        .case_insensitive_longs();
+
+    let r = parser.run_inner(&["--VERBOSE", "--No-Color"]).unwrap();
+    assert_eq!(r, (true, false));
+
+    // prefixes still need allow_abbreviations
+    let r = parser.run_inner(&["--VERB"]).unwrap_err().unwrap_stderr();
+    assert_eq!(r, "`--VERB` is not expected in this context");
+
+    let verbose = long("verbose").switch();
+    let color = long("color").negatable();
+    let parser = construct!(verbose, color)
+        .to_options()
+        .case_insensitive_longs()
+        .allow_abbreviations();
+    let r = parser.run_inner(&["--VERB", "--col"]).unwrap();
+    assert_eq!(r, (true, true));
+}
+
+#[test]
+fn abbreviated_commands() {
+    let verbose = long("verbose").switch();
+    let build = verbose.to_options().command("build");
+    let bench = pure(false).to_options().command("bench");
+    let check = pure(false).to_options().command("check").short('b');
+    let parser = construct!([build, bench, check])
+        .to_options()
+        .allow_abbreviations()
+        .case_insensitive_longs();
+
+    let r = parser.run_inner(&["bu", "--verb"]).unwrap();
+    assert!(r);
+
+    let r = parser.run_inner(&["BENCH"]).unwrap();
+    assert!(!r);
+
+    // short command alias is an exact match
+    let r = parser.run_inner(&["b"]).unwrap();
+    assert!(!r);
+
+    let r = parser.run_inner(&["be"]).unwrap();
+    assert!(!r);
+
+    let r = parser.run_inner(&["bX"]).unwrap_err().unwrap_stderr();
+    assert!(!r.contains("ambiguous"), "{}", r);
+
+    let bu = pure(true).to_options().command("bump");
+    let build = pure(false).to_options().command("build");
+    let parser = construct!([bu, build]).to_options().allow_abbreviations();
+    let err = parser.run_inner(&["bu"]).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        Some(&ErrorKind::AmbiguousPrefix {
+            index: 0,
+            name: "bu".to_owned(),
+            candidates: vec!["build".to_owned(), "bump".to_owned()],
+        })
+    );
+}
+
+#[test]
+fn nested_command_names_are_resolved_per_level() {
+    let build = pure("build").to_options().command("build");
+    let remote = construct!([build]).to_options().command("remote");
+    let bench = pure("bench").to_options().command("bench");
+    let parser = construct!([remote, bench])
+        .to_options()
+        .allow_abbreviations();
+
+    // "b" is only ambiguous across levels, not within one
+    let r = parser.run_inner(&["r", "b"]).unwrap();
+    assert_eq!(r, "build");
+
+    let r = parser.run_inner(&["b"]).unwrap();
+    assert_eq!(r, "bench");
+}
+
+#[test]
+fn long_names_are_resolved_per_level() {
+    let verbose = long("verbose").switch().to_options().command("one");
+    let verify = long("verify").switch().to_options().command("two");
+    let parser = construct!([verbose, verify])
+        .to_options()
+        .allow_abbreviations();
+
+    // "--ver" is only ambiguous across commands, not within one
+    let r = parser.run_inner(&["one", "--ver"]).unwrap();
+    assert!(r);
+
+    let r = parser.run_inner(&["two", "--ver"]).unwrap();
+    assert!(r);
+
+    // top level names don't get in the way of command names either
+    let top = long("version-info").switch();
+    let verify = long("verify").switch().to_options().command("two");
+    let parser = construct!(top, verify).to_options().allow_abbreviations();
+    let r = parser.run_inner(&["--vers", "two", "--veri"]).unwrap();
+    assert_eq!(r, (true, true));
+
+    // ambiguity within a command is still an error
+    let verbose = long("verbose").switch();
+    let verify = long("verify").switch();
+    let two = construct!(verbose, verify).to_options().command("two");
+    let parser = two.to_options().allow_abbreviations();
+    let err = parser.run_inner(&["two", "--ver"]).unwrap_err();
+    assert_eq!(
+        err.unwrap_stderr(),
+        "`--ver` is ambiguous, it could be `--verbose`, `--verify`"
+    );
+}
+
+#[cfg(feature = "autocomplete")]
+#[test]
+fn completion_is_not_affected() {
+    let verbose = long("verbose").switch();
+    let verify = long("verify").switch();
+    let parser = construct!(verbose, verify)
+        .to_options()
+        .allow_abbreviations();
+
+    let r = parser
+        .run_inner(Args::from(&["--ver"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "--verbose\t--verbose\t\t\n--verify\t--verify\t\t\n\n");
+}
@@ -20,3 +20,92 @@ fn test_toggle_flag() {
     let r = parser.run_inner(&["-y", "-y", "-n", "-y"]).unwrap();
     assert_eq!(r, Some(Flag::Y));
 }
+
+#[cfg(feature = "autocomplete")]
+#[test]
+fn completion_command_install_and_uninstall() {
+    use bpaf::batteries::{completion_command, CompletionAction, Completions};
+
+    let parser = completion_command("app").to_options();
+    let dir = std::env::temp_dir().join(format!("bpaf-completions-{}", std::process::id()));
+    // all the environment changes are in one test so they don't race with each other
+    std::env::set_var("XDG_DATA_HOME", &dir);
+    std::env::set_var("XDG_CONFIG_HOME", dir.join("config"));
+    std::env::set_var("SHELL", "/usr/bin/zsh");
+
+    let r = parser
+        .run_inner(&["completions", "install", "--dry-run"])
+        .unwrap();
+    assert_eq!(
+        r,
+        Completions {
+            app: "app",
+            action: CompletionAction::Install,
+            shell: Shell::Zsh,
+            dry_run: true,
+        }
+    );
+    let path = dir.join("zsh/site-functions/_app");
+    assert_eq!(r.path().unwrap(), path);
+    let report = r.run().unwrap();
+    assert!(report.starts_with(&format!("Would write {}\n", path.display())));
+    assert!(!path.exists());
+
+    let r = parser
+        .run_inner(&["completions", "install", "--shell", "bash"])
+        .unwrap();
+    let path = dir.join("bash-completion/completions/app");
+    assert_eq!(r.run().unwrap(), format!("Wrote {}\n", path.display()));
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        Shell::Bash.dynamic_script("app")
+    );
+
+    let r = parser
+        .run_inner(&["completions", "uninstall", "--shell", "bash", "--dry-run"])
+        .unwrap();
+    assert_eq!(
+        r.run().unwrap(),
+        format!("Would remove {}\n", path.display())
+    );
+    assert!(path.exists());
+
+    let r = parser
+        .run_inner(&["completions", "uninstall", "--shell", "bash"])
+        .unwrap();
+    assert_eq!(r.run().unwrap(), format!("Removed {}\n", path.display()));
+    assert!(!path.exists());
+    assert_eq!(
+        r.run().unwrap(),
+        format!("{} is not installed\n", path.display())
+    );
+
+    let r = parser
+        .run_inner(&["completions", "print", "--shell", "fish"])
+        .unwrap();
+    assert_eq!(
+        r.path().unwrap(),
+        dir.join("config/fish/completions/app.fish")
+    );
+
+    std::env::set_var("SHELL", "/bin/tcsh");
+    let r = parser
+        .run_inner(&["completions", "print"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "can't detect the shell from $SHELL, pass it with --shell"
+    );
+
+    let r = parser
+        .run_inner(&["completions", "print", "--shell", "tcsh"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "couldn't parse `tcsh`: tcsh is not supported, use bash, zsh or fish"
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
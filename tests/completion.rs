@@ -1425,3 +1425,322 @@ fn positionals_with_no_completions_are_not_duplicated() {
 \tBETA\t\tBeta argument\n\n"
     );
 }
+
+#[test]
+fn powershell_completion_results() {
+    let a = short('a')
+        .long("arg")
+        .help("Alpha's argument")
+        .argument::<String>("ALPHA");
+    let b = short('b').switch();
+    let parser = construct!(a, b).to_options();
+
+    let r = parser
+        .run_inner(Args::from(&[""]).set_comp(10))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(
+        r,
+        "\
+[System.Management.Automation.CompletionResult]::new('--arg', '--arg=ALPHA', 'ParameterName', 'Alpha''s argument')
+[System.Management.Automation.CompletionResult]::new('-b', '-b', 'ParameterName', '-b')
+"
+    );
+
+    // placeholders are not inserted
+    let r = parser
+        .run_inner(Args::from(&["-a", ""]).set_comp(10))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "");
+}
+
+#[test]
+fn powershell_shell_completion() {
+    let parser = short('a')
+        .argument::<String>("FILE")
+        .complete_shell(ShellComp::File { mask: Some("*.rs") })
+        .to_options();
+
+    let r = parser
+        .run_inner(Args::from(&["-a", ""]).set_comp(10))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(
+        r,
+        "Get-ChildItem -Path \"$wordToComplete*\" | Where-Object { $_.PSIsContainer -or $_.Name -like '*.rs' } | ForEach-Object { [System.Management.Automation.CompletionResult]::new((Resolve-Path -Relative $_.FullName), $_.Name, 'ProviderItem', $_.Name) }\n"
+    );
+
+    let parser = short('a')
+        .argument::<String>("FILE")
+        .complete_shell(ShellComp::Raw {
+            bash: "",
+            zsh: "",
+            fish: "",
+            elvish: "",
+            pwsh: "Get-Date",
+        })
+        .to_options();
+    let r = parser
+        .run_inner(Args::from(&["-a", ""]).set_comp(10))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "Get-Date\n");
+}
+
+#[test]
+fn nushell_json_records() {
+    let a = short('a')
+        .long("arg")
+        .help("Alpha \"quoted\" argument")
+        .argument::<String>("ALPHA");
+    let b = short('b').switch();
+    let parser = construct!(a, b).to_options();
+
+    let r = parser
+        .run_inner(Args::from(&[""]).set_comp(11))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(
+        r,
+        "[
+{\"value\": \"--arg\", \"description\": \"Alpha \\\"quoted\\\" argument\"},
+{\"value\": \"-b\"}]
+"
+    );
+
+    let r = parser
+        .run_inner(Args::from(&["--arg", "x"]).set_comp(11))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "[{\"value\": \"x\"}]\n");
+}
+
+#[test]
+fn nushell_groups_and_files() {
+    let a = short('a')
+        .argument::<String>("A")
+        .complete(|_| vec![("alpha", Some("first")), ("beta", None)])
+        .group("Values");
+    let parser = a.to_options();
+
+    let r = parser
+        .run_inner(Args::from(&["-a", ""]).set_comp(11))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(
+        r,
+        "[
+{\"value\": \"alpha\", \"description\": \"first\", \"group\": \"Values\"},
+{\"value\": \"beta\", \"group\": \"Values\"}]
+"
+    );
+
+    let parser = short('a')
+        .argument::<String>("FILE")
+        .complete_shell(ShellComp::File { mask: None })
+        .to_options();
+    let r = parser
+        .run_inner(Args::from(&["-a", ""]).set_comp(11))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "null\n");
+}
+
+#[test]
+fn dynamic_completion_cache_and_timeout() {
+    use std::{cell::Cell, rc::Rc, time::Duration};
+    let dir = std::env::temp_dir().join(format!("bpaf-comp-cache-{}", std::process::id()));
+    std::env::set_var("XDG_CACHE_HOME", &dir);
+
+    let calls = Rc::new(Cell::new(0));
+    let counter = {
+        let calls = calls.clone();
+        move |_: &String| {
+            calls.set(calls.get() + 1);
+            vec![(format!("v{}", calls.get()), None)]
+        }
+    };
+    let parser = positional::<String>("NAME")
+        .complete(counter)
+        .cache(Duration::from_secs(600))
+        .to_options();
+
+    let run = |parser: &OptionParser<String>| {
+        parser
+            .run_inner(Args::from(&["v"]).set_comp(0))
+            .unwrap_err()
+            .unwrap_stdout()
+    };
+
+    // fresh values are served from the cache
+    assert_eq!(run(&parser), "v1");
+    assert_eq!(run(&parser), "v1");
+    assert_eq!(calls.get(), 1);
+
+    // stale values are still served, the next request sees refreshed ones
+    let calls = Rc::new(Cell::new(0));
+    let counter = {
+        let calls = calls.clone();
+        move |_: &String| {
+            calls.set(calls.get() + 1);
+            vec![(format!("v{}", calls.get()), None)]
+        }
+    };
+    let parser = positional::<String>("NAME")
+        .complete(counter)
+        .group("stale")
+        .cache(Duration::from_millis(1))
+        .to_options();
+    assert_eq!(run(&parser), "v1");
+    std::thread::sleep(Duration::from_millis(10));
+    assert_eq!(run(&parser), "v1");
+    assert_eq!(run(&parser), "v2");
+
+    // slow completer gives up, but the result makes it to the cache
+    let slow = |_: &String| {
+        std::thread::sleep(Duration::from_millis(50));
+        vec![("slow", None)]
+    };
+    let parser = positional::<String>("NAME")
+        .complete(slow)
+        .timeout(Duration::from_millis(1))
+        .to_options();
+    assert_eq!(run(&parser), "\nNothing\n");
+
+    let parser = positional::<String>("NAME")
+        .complete(slow)
+        .group("slow")
+        .timeout(Duration::from_millis(1))
+        .cache(Duration::from_secs(600))
+        .to_options();
+    assert_eq!(run(&parser), "\nNothing\n");
+    assert_eq!(run(&parser), "slow");
+
+    // suggestions that depend on other items are cached separately for each of them
+    let repo = long("repo").argument::<String>("REPO");
+    let branch = long("branch")
+        .argument::<String>("BRANCH")
+        .complete_with_context(|_, ctx: &CompContext| {
+            let repo = ctx.value::<String>(&long("repo")).unwrap_or_default();
+            vec![(format!("{}-main", repo), None)]
+        })
+        .cache(Duration::from_secs(600));
+    let parser = construct!(repo, branch).to_options();
+    let run = |args: &[&str]| {
+        parser
+            .run_inner(Args::from(args).set_comp(0))
+            .unwrap_err()
+            .unwrap_stdout()
+    };
+    assert_eq!(run(&["--repo", "a", "--branch", ""]), "a-main");
+    assert_eq!(run(&["--repo", "b", "--branch", ""]), "b-main");
+    assert_eq!(run(&["--repo", "a", "--branch", ""]), "a-main");
+
+    // items with the same metavariable are cached separately
+    let remote = long("remote")
+        .argument::<String>("NAME")
+        .complete(|_| vec![("origin", None)])
+        .cache(Duration::from_secs(600));
+    let user = long("user")
+        .argument::<String>("NAME")
+        .complete(|_| vec![("alice", None)])
+        .cache(Duration::from_secs(600));
+    let parser = construct!(remote, user).to_options();
+    let run = |args: &[&str]| {
+        parser
+            .run_inner(Args::from(args).set_comp(0))
+            .unwrap_err()
+            .unwrap_stdout()
+    };
+    assert_eq!(run(&["--remote", ""]), "origin");
+    assert_eq!(run(&["--user", ""]), "alice");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn completion_with_context() {
+    fn branches(input: &String, ctx: &CompContext) -> Vec<(String, Option<String>)> {
+        let repo = ctx.raw(&long("repo").short('r'));
+        let repo = repo.and_then(|r| r.to_str()).unwrap_or("");
+        let depth = ctx.value::<usize>(&short('d')).unwrap_or(0);
+        let verbose = ctx.is_present(&long("verbose"));
+        vec![(
+            format!(
+                "{}{}-{}-{}-{}",
+                input,
+                repo,
+                depth,
+                verbose,
+                ctx.path().join("/")
+            ),
+            None,
+        )]
+    }
+
+    let verbose = long("verbose").switch();
+    let repo = short('r')
+        .long("repo")
+        .argument::<String>("REPO")
+        .optional();
+    let depth = short('d').argument::<usize>("DEPTH").optional();
+    let branch = long("branch")
+        .argument::<String>("BRANCH")
+        .complete_with_context(branches)
+        .optional();
+    let parser = construct!(verbose, repo, depth, branch).to_options();
+
+    let run = |args: &[&str]| {
+        parser
+            .run_inner(Args::from(args).set_comp(0).set_name("app"))
+            .unwrap_err()
+            .unwrap_stdout()
+    };
+
+    assert_eq!(run(&["--repo", "foo", "--branch", "b"]), "bfoo-0-false-app");
+    assert_eq!(
+        run(&["--repo=foo", "-d", "3", "--verbose", "--branch", "b"]),
+        "bfoo-3-true-app"
+    );
+    assert_eq!(run(&["-rfoo", "--branch", "b"]), "bfoo-0-false-app");
+    assert_eq!(run(&["-r", "foo", "--branch", "b"]), "bfoo-0-false-app");
+
+    // value that is still being typed is not visible
+    let branch = long("branch")
+        .argument::<String>("BRANCH")
+        .complete_with_context(|_, ctx: &CompContext| {
+            let branch = ctx
+                .raw(&long("branch"))
+                .map(|r| r.to_string_lossy().into_owned());
+            vec![(format!("{:?} {:?}", branch, ctx.path()), None)]
+        });
+    let parser = construct!(branch).to_options().command("switch");
+    let parser = construct!([parser]).to_options();
+    let r = parser
+        .run_inner(Args::from(&["switch", "--branch", "ma"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "None [\"switch\"]");
+}
+
+#[test]
+fn completion_context_resolves_abbreviations() {
+    let repo = long("repository").argument::<String>("REPO").optional();
+    let branch = long("branch")
+        .argument::<String>("BRANCH")
+        .complete_with_context(|_, ctx: &CompContext| {
+            let repo = ctx.value::<String>(&long("repository"));
+            vec![(format!("{:?}", repo), None)]
+        })
+        .optional();
+    let parser = construct!(repo, branch).to_options().allow_abbreviations();
+    let run = |args: &[&str]| {
+        parser
+            .run_inner(Args::from(args).set_comp(0))
+            .unwrap_err()
+            .unwrap_stdout()
+    };
+    assert_eq!(run(&["--repo", "bpaf", "--branch", ""]), "Some(\"bpaf\")");
+    assert_eq!(run(&["--repo=bpaf", "--branch", ""]), "Some(\"bpaf\")");
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use bpaf::*;
+
+fn config() -> HashMap<String, String> {
+    let mut config = HashMap::new();
+    config.insert("threads".to_owned(), "4".to_owned());
+    config.insert("verbose".to_owned(), "true".to_owned());
+    config.insert("quiet".to_owned(), "off".to_owned());
+    config.insert("name".to_owned(), "bob".to_owned());
+    config
+}
+
+#[test]
+fn config_resolution_order() {
+    let env_name = "BPAF_CONFIG_TEST_THREADS";
+    let parser = long("threads")
+        .env(env_name)
+        .argument::<usize>("N")
+        .fallback(1)
+        .to_options()
+        .config_source(config());
+
+    let r = parser.run_inner(&[]).unwrap();
+    assert_eq!(r, 4);
+
+    let r = parser.run_inner(&["--threads", "8"]).unwrap();
+    assert_eq!(r, 8);
+
+    std::env::set_var(env_name, "6");
+    let r = parser.run_inner(&[]).unwrap();
+    assert_eq!(r, 6);
+    std::env::remove_var(env_name);
+
+    let parser = long("jobs")
+        .argument::<usize>("N")
+        .fallback(1)
+        .to_options()
+        .config_source(config());
+    let r = parser.run_inner(&[]).unwrap();
+    assert_eq!(r, 1);
+}
+
+#[test]
+fn config_flags() {
+    let verbose = short('v').long("verbose").switch();
+    let quiet = short('q').long("quiet").switch();
+    let parser = construct!(verbose, quiet)
+        .to_options()
+        .config_source(config());
+
+    let r = parser.run_inner(&[]).unwrap();
+    assert_eq!(r, (true, false));
+
+    let r = parser.run_inner(&["-q"]).unwrap();
+    assert_eq!(r, (true, true));
+}
+
+#[test]
+fn config_keyed_by_long_name_only() {
+    let parser = short('t')
+        .argument::<usize>("N")
+        .fallback(1)
+        .to_options()
+        .config_source(config());
+    let r = parser.run_inner(&[]).unwrap();
+    assert_eq!(r, 1);
+}
+
+#[test]
+fn config_parse_failure_mentions_key() {
+    let parser = long("name")
+        .argument::<usize>("N")
+        .to_options()
+        .config_source(config());
+    let r = parser.run_inner(&[]).unwrap_err().unwrap_stderr();
+    assert_eq!(
+        r,
+        "couldn't parse config value `name`: invalid digit found in string"
+    );
+}
+
+#[test]
+fn config_closure_source() {
+    let parser = long("name")
+        .argument::<String>("NAME")
+        .to_options()
+        .config_source(|key: &str| Some(format!("<{}>", key)));
+    let r = parser.run_inner(&[]).unwrap();
+    assert_eq!(r, "<name>");
+}
+
+#[test]
+fn config_values_in_help() {
+    let threads = long("threads")
+        .help("Number of threads")
+        .argument::<usize>("N")
+        .fallback(1);
+    let verbose = short('v').long("verbose").switch();
+    let jobs = long("jobs").argument::<usize>("N").optional();
+    let parser = construct!(threads, verbose, jobs)
+        .to_options()
+        .config_source(config());
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: [--threads=N] [-v] [--jobs=N]
+
+Available options:
+        --threads=N  Number of threads
+                     [config:threads]
+    -v, --verbose    [config:verbose]
+        --jobs=N
+    -h, --help       Prints help information
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn config_in_subcommands() {
+    let mut inner = HashMap::new();
+    inner.insert("threads".to_owned(), "16".to_owned());
+
+    let threads = || long("threads").argument::<usize>("N").fallback(1);
+    let cmd_parent = threads().to_options().command("parent");
+    let cmd_own = threads().to_options().config_source(inner).command("own");
+    let parser = construct!([cmd_parent, cmd_own])
+        .to_options()
+        .config_source(config());
+
+    let r = parser.run_inner(&["parent"]).unwrap();
+    assert_eq!(r, 4);
+
+    let r = parser.run_inner(&["own"]).unwrap();
+    assert_eq!(r, 16);
+}
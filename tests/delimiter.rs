@@ -0,0 +1,219 @@
+#![allow(clippy::ptr_arg)]
+use bpaf::*;
+
+#[test]
+fn delimited_values_are_separate() {
+    let tag = long("tag").short('t').argument::<u32>("TAG").delimiter(',');
+    let parser = tag.many().to_options();
+
+    let r = parser
+        .run_inner(&["--tag", "1,2", "--tag=3,4", "-t5,6", "-t", "7"])
+        .unwrap();
+    assert_eq!(r, [1, 2, 3, 4, 5, 6, 7]);
+
+    // empty elements are kept
+    let parser = long("tag")
+        .argument::<String>("TAG")
+        .delimiter(',')
+        .collect::<Vec<_>>()
+        .to_options();
+    let r = parser.run_inner(&["--tag", "a,,b"]).unwrap();
+    assert_eq!(r, ["a", "", "b"]);
+}
+
+#[test]
+fn delimited_values_with_aliases_and_commands() {
+    let tag = long("tag")
+        .long("label")
+        .argument::<String>("TAG")
+        .delimiter(';')
+        .many()
+        .to_options();
+    let parser = tag.command("add").to_options();
+
+    let r = parser.run_inner(&["add", "--label", "a;b"]).unwrap();
+    assert_eq!(r, ["a", "b"]);
+}
+
+#[test]
+fn single_value_rejects_several_elements() {
+    let parser = long("tag")
+        .argument::<String>("TAG")
+        .delimiter(',')
+        .to_options();
+
+    let r = parser.run_inner(&["--tag", "a"]).unwrap();
+    assert_eq!(r, "a");
+
+    let err = parser.run_inner(&["--tag", "a,b"]).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        Some(&ErrorKind::SingleValue {
+            index: 0,
+            name: "--tag".to_owned(),
+        })
+    );
+    assert_eq!(
+        err.unwrap_stderr(),
+        "argument `--tag` expects a single value"
+    );
+
+    // repeated name is still reported as such
+    let r = parser
+        .run_inner(&["--tag", "a", "--tag", "b"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "argument `--tag` cannot be used multiple times in this context"
+    );
+}
+
+#[test]
+fn optional_value_is_split() {
+    let parser = long("color")
+        .argument_opt_value::<String>("WHEN", "always".to_owned())
+        .delimiter(',')
+        .many()
+        .to_options();
+
+    let r = parser.run_inner(&["--color=a,b", "--color"]).unwrap();
+    assert_eq!(r, ["a", "b", "always"]);
+}
+
+#[test]
+fn parse_failure_names_the_element() {
+    let parser = long("port")
+        .argument::<u16>("PORT")
+        .delimiter(',')
+        .many()
+        .to_options();
+
+    let err = parser.run_inner(&["--port=80,http,443"]).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        Some(&ErrorKind::ParseFailed {
+            index: Some(0),
+            value: Some("http".to_owned()),
+            message: "invalid digit found in string".to_owned(),
+        })
+    );
+    assert_eq!(
+        err.unwrap_stderr(),
+        "couldn't parse `http`: invalid digit found in string"
+    );
+}
+
+#[test]
+fn other_items_are_not_split() {
+    let tag = long("tag").argument::<String>("TAG").delimiter(',').many();
+    let name = long("name").argument::<String>("NAME");
+    let file = positional::<String>("FILE");
+    let parser = construct!(tag, name, file).to_options();
+
+    let r = parser
+        .run_inner(&["--name", "a,b", "--tag", "x,y", "c,d"])
+        .unwrap();
+    assert_eq!(
+        r,
+        (
+            vec!["x".to_owned(), "y".to_owned()],
+            "a,b".to_owned(),
+            "c,d".to_owned()
+        )
+    );
+
+    // strictly positional items are left alone
+    let r = parser.run_inner(&["--name", "n", "--", "--tag"]).unwrap();
+    assert_eq!(r, (Vec::new(), "n".to_owned(), "--tag".to_owned()));
+}
+
+#[test]
+fn env_values_are_not_split() {
+    let parser = long("tag")
+        .env("BPAF_DELIMITER_TEST_TAG")
+        .argument::<String>("TAG")
+        .delimiter(',')
+        .to_options();
+
+    std::env::set_var("BPAF_DELIMITER_TEST_TAG", "a,b");
+    let r = parser.run_inner(&[]).unwrap();
+    std::env::remove_var("BPAF_DELIMITER_TEST_TAG");
+    assert_eq!(r, "a,b");
+}
+
+#[cfg(feature = "autocomplete")]
+#[test]
+fn completion_completes_current_element() {
+    fn colors(input: &String) -> Vec<(String, Option<String>)> {
+        ["red", "green", "blue"]
+            .iter()
+            .filter(|c| c.starts_with(input.as_str()))
+            .map(|c| (c.to_string(), None))
+            .collect()
+    }
+    let parser = long("color")
+        .argument::<String>("COLOR")
+        .delimiter(',')
+        .complete(colors)
+        .many()
+        .to_options();
+
+    let r = parser
+        .run_inner(Args::from(&["--color", "red,gr"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "red,green");
+
+    let r = parser
+        .run_inner(Args::from(&["--color=red,b"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "--color=red,blue");
+
+    let r = parser
+        .run_inner(Args::from(&["--color", "blue,"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(
+        r,
+        "\tCOLOR\t\t\nblue,red\tred\t\t\nblue,green\tgreen\t\t\nblue,blue\tblue\t\t\n\n"
+    );
+}
+
+#[test]
+fn parsers_with_the_same_name_are_not_split() {
+    let one = long("tag")
+        .argument::<String>("TAG")
+        .delimiter(',')
+        .many()
+        .to_options()
+        .command("one");
+    let two = long("tag")
+        .argument::<String>("TAG")
+        .many()
+        .to_options()
+        .command("two");
+    let parser = construct!([one, two]).to_options();
+
+    let r = parser.run_inner(&["one", "--tag", "a,b"]).unwrap();
+    assert_eq!(r, ["a", "b"]);
+
+    let r = parser.run_inner(&["two", "--tag", "c,d"]).unwrap();
+    assert_eq!(r, ["c,d"]);
+
+    // plain parser outside of a command with a delimited one
+    let tag = long("tag").argument::<String>("TAG");
+    let cmd = long("tag")
+        .argument::<String>("TAG")
+        .delimiter(',')
+        .many()
+        .to_options()
+        .command("cmd");
+    let parser = construct!(tag, cmd).to_options();
+
+    let r = parser
+        .run_inner(&["--tag", "a,b", "cmd", "--tag", "c,d"])
+        .unwrap();
+    assert_eq!(r, ("a,b".to_owned(), vec!["c".to_owned(), "d".to_owned()]));
+}
@@ -0,0 +1,134 @@
+use bpaf::*;
+
+#[test]
+fn deprecated_items_produce_warnings() {
+    let verbose = short('v').switch();
+    let quiet = short('q').long("quiet").deprecated("use -v").switch();
+    let out = long("out")
+        .deprecated("use --output")
+        .argument::<String>("FILE")
+        .optional();
+    let parser = construct!(verbose, quiet, out).to_options();
+
+    let (r, warnings) = parser
+        .run_inner_with_warnings(&["-q", "--out", "a.txt"])
+        .unwrap();
+    assert_eq!(r, (false, true, Some("a.txt".to_owned())));
+    assert_eq!(
+        warnings,
+        vec![
+            Warning::Deprecated {
+                name: "--quiet".to_owned(),
+                message: "use -v",
+            },
+            Warning::Deprecated {
+                name: "--out".to_owned(),
+                message: "use --output",
+            },
+        ]
+    );
+    assert_eq!(
+        warnings[1].to_string(),
+        "`--out` is deprecated: use --output"
+    );
+
+    let (_, warnings) = parser.run_inner_with_warnings(&["-v"]).unwrap();
+    assert!(warnings.is_empty());
+
+    // plain run_inner still succeeds
+    let r = parser.run_inner(&["-q"]).unwrap();
+    assert_eq!(r, (false, true, None));
+}
+
+#[test]
+fn deprecated_command() {
+    let build = pure(()).to_options().command("build");
+    let make = pure(())
+        .to_options()
+        .command("make")
+        .deprecated("use build");
+    let parser = construct!([build, make]).to_options();
+
+    let (_, warnings) = parser.run_inner_with_warnings(&["make"]).unwrap();
+    assert_eq!(warnings[0].to_string(), "`make` is deprecated: use build");
+
+    let (_, warnings) = parser.run_inner_with_warnings(&["build"]).unwrap();
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn losing_branch_does_not_warn() {
+    let output = long("output").argument::<String>("FILE");
+    let out = long("out")
+        .deprecated("use --output")
+        .argument::<String>("FILE");
+    let parser = construct!([output, out]).to_options();
+
+    let (r, warnings) = parser
+        .run_inner_with_warnings(&["--output", "a.txt"])
+        .unwrap();
+    assert_eq!(r, "a.txt");
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn deprecated_items_in_help() {
+    let output = long("output")
+        .help("Output file")
+        .argument::<String>("FILE");
+    let out = long("out")
+        .help("Output file")
+        .deprecated("use --output")
+        .argument::<String>("FILE");
+    let quiet = short('q').deprecated("use -v").switch();
+    let output = construct!([output, out]);
+    let parser = construct!(output, quiet).to_options();
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: (--output=FILE | --out=FILE) [-q]
+
+Available options:
+        --output=FILE  Output file
+        --out=FILE     Output file (deprecated)
+    -q                 (deprecated)
+    -h, --help         Prints help information
+";
+    assert_eq!(r, expected);
+
+    let r = parser
+        .locale(locale::German)
+        .run_inner(&["-q", "--out", "x", "--help"])
+        .unwrap_err()
+        .unwrap_stdout();
+    assert!(r.contains("Output file (veraltet)"), "{}", r);
+}
+
+#[cfg(feature = "autocomplete")]
+#[test]
+fn deprecated_items_are_not_completed() {
+    let output = long("output").argument::<String>("FILE");
+    let out = long("out")
+        .deprecated("use --output")
+        .argument::<String>("FILE");
+    let parser = construct!([output, out]).to_options();
+
+    let r = parser
+        .run_inner(Args::from(&["--ou"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "--output");
+
+    let build = pure(()).to_options().command("build");
+    let bench = pure(())
+        .to_options()
+        .command("bench")
+        .deprecated("use build");
+    let parser = construct!([build, bench]).to_options();
+
+    let r = parser
+        .run_inner(Args::from(&["b"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "build");
+}
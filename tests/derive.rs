@@ -123,3 +123,60 @@ Available commands:
     let r = parser.run_inner(&["one"]).unwrap();
     assert_eq!(r, One);
 }
+
+#[test]
+fn derive_field_groups() {
+    #[derive(Debug, Clone, Bpaf, PartialEq)]
+    #[bpaf(options, conflicts(json, yaml), requires(cert, key))]
+    struct Opts {
+        #[bpaf(argument("CERT"))]
+        cert: Option<String>,
+        json: bool,
+        verbose: bool,
+        yaml: bool,
+        #[bpaf(argument("KEY"))]
+        key: Option<String>,
+    }
+
+    let r = opts()
+        .run_inner(&["--json", "--cert", "a", "--key", "b"])
+        .unwrap();
+    assert_eq!(
+        r,
+        Opts {
+            cert: Some("a".to_owned()),
+            json: true,
+            verbose: false,
+            yaml: false,
+            key: Some("b".to_owned()),
+        }
+    );
+
+    let r = opts()
+        .run_inner(&["--yaml", "--json"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "`--json` cannot be used at the same time as `--yaml`");
+
+    let r = opts()
+        .run_inner(&["--cert", "a"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "`--cert` requires `--key`");
+
+    let r = opts().run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: [--cert=CERT] [--json] [--verbose] [--yaml] [--key=KEY]
+
+Available options:
+        --cert=CERT
+        --json
+        --verbose
+        --yaml
+                     [at most one of --json, --yaml]
+        --key=KEY
+                     [--cert requires --key]
+    -h, --help       Prints help information
+";
+    assert_eq!(r, expected);
+}
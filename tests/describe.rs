@@ -0,0 +1,215 @@
+use bpaf::{describe::Node, *};
+
+#[test]
+fn commands_and_info() {
+    let dry = long("dry-run").help("Only pretend").switch();
+    let build = dry
+        .to_options()
+        .descr("Build the project")
+        .command("build")
+        .short('b')
+        .help("Build it");
+    let parser = build
+        .to_options()
+        .version("1.0")
+        .header("head")
+        .footer("foot");
+
+    let spec = parser.describe();
+    assert_eq!(spec.version.as_deref(), Some("1.0"));
+    assert_eq!(spec.header.as_deref(), Some("head"));
+    assert_eq!(spec.footer.as_deref(), Some("foot"));
+    assert!(!spec.fallback_to_usage);
+
+    let cmd = match &spec.args {
+        Node::Command(cmd) => cmd,
+        other => panic!("{:?}", other),
+    };
+    assert_eq!(cmd.name, "build");
+    assert_eq!(cmd.short, Some('b'));
+    assert_eq!(cmd.help.as_deref(), Some("Build it"));
+    assert_eq!(cmd.spec.descr.as_deref(), Some("Build the project"));
+    assert_eq!(
+        cmd.spec.args.to_json(),
+        r#"{"kind":"optional","item":{"kind":"flag","short":null,"long":"dry-run","env":[],"metavar":null,"help":"Only pretend","deprecated":false,"negatable":false,"opt_value":false}}"#
+    );
+
+    // version flag is only present when version is set
+    assert_eq!(
+        spec.builtins.to_json(),
+        r#"{"kind":"and","items":[{"kind":"flag","short":"h","long":"help","env":[],"metavar":null,"help":"Prints help information","deprecated":false,"negatable":false,"opt_value":false},{"kind":"flag","short":"V","long":"version","env":[],"metavar":null,"help":"Prints version information","deprecated":false,"negatable":false,"opt_value":false}]}"#
+    );
+    assert!(matches!(cmd.spec.builtins, Node::Flag(_)));
+}
+
+#[test]
+fn deprecated_items() {
+    let out = long("out-dir")
+        .deprecated("use --output")
+        .argument::<String>("DIR");
+    let old = pure(()).to_options().command("old").deprecated("use new");
+    let parser = construct!(out, old).to_options();
+
+    let spec = parser.describe();
+    let items = match spec.args {
+        Node::And(items) => items,
+        other => panic!("{:?}", other),
+    };
+    assert_eq!(
+        items[0].to_json(),
+        r#"{"kind":"argument","short":null,"long":"out-dir","env":[],"metavar":"DIR","help":null,"deprecated":true,"negatable":false,"opt_value":false}"#
+    );
+    match &items[1] {
+        Node::Command(cmd) => assert!(cmd.deprecated),
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn negatable_flags() {
+    let color = long("color").negatable();
+    let spec = color.to_options().describe();
+    assert_eq!(
+        spec.args.to_json(),
+        r#"{"kind":"optional","item":{"kind":"flag","short":null,"long":"color","env":[],"metavar":null,"help":null,"deprecated":false,"negatable":true,"opt_value":false}}"#
+    );
+}
+
+#[test]
+fn optional_value_arguments() {
+    let color = long("color").argument_opt_value::<String>("WHEN", "always".to_owned());
+    let spec = color.to_options().describe();
+    assert_eq!(
+        spec.args.to_json(),
+        r#"{"kind":"argument","short":null,"long":"color","env":[],"metavar":"WHEN","help":null,"deprecated":false,"negatable":false,"opt_value":true}"#
+    );
+}
+
+#[test]
+fn structure_is_preserved() {
+    let a = short('a').req_flag(());
+    let b = short('b').req_flag(());
+    let ab = construct!([a, b]);
+    let n = long("num")
+        .env("NUM")
+        .help("A \"number\"")
+        .argument::<u32>("N")
+        .many();
+    let secret = long("secret").switch().hide();
+    let extra = short('x').switch().group_help("Extra things").hide_usage();
+    let parser = construct!(ab, n, secret, extra).to_options();
+
+    let json = parser.describe().to_json();
+    let expected = concat!(
+        r#"{"descr":null,"header":null,"footer":null,"usage":null,"version":null,"fallback_to_usage":false,"args":{"kind":"and","items":["#,
+        r#"{"kind":"or","items":[{"kind":"flag","short":"a","long":null,"env":[],"metavar":null,"help":null,"deprecated":false,"negatable":false,"opt_value":false},{"kind":"flag","short":"b","long":null,"env":[],"metavar":null,"help":null,"deprecated":false,"negatable":false,"opt_value":false}]},"#,
+        r#"{"kind":"many","item":{"kind":"optional","item":{"kind":"argument","short":null,"long":"num","env":["NUM"],"metavar":"N","help":"A \"number\"","deprecated":false,"negatable":false,"opt_value":false}}},"#,
+        r#"{"kind":"hidden","item":{"kind":"optional","item":{"kind":"flag","short":null,"long":"secret","env":[],"metavar":null,"help":null,"deprecated":false,"negatable":false,"opt_value":false}}},"#,
+        r#"{"kind":"usage","usage":"","item":{"kind":"group","header":"Extra things","item":{"kind":"optional","item":{"kind":"flag","short":"x","long":null,"env":[],"metavar":null,"help":null,"deprecated":false,"negatable":false,"opt_value":false}}}}]},"#,
+        r#""builtins":{"kind":"flag","short":"h","long":"help","env":[],"metavar":null,"help":"Prints help information","deprecated":false,"negatable":false,"opt_value":false}}"#
+    );
+    assert_eq!(json, expected);
+}
+
+#[test]
+fn hidden_items_still_parse_and_stay_out_of_help() {
+    let secret = long("secret").switch().hide();
+    let parser = secret.to_options();
+
+    assert!(parser.run_inner(&["--secret"]).unwrap());
+    let help = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    assert_eq!(
+        help,
+        "Usage: \n\nAvailable options:\n    -h, --help  Prints help information\n"
+    );
+}
+
+#[test]
+fn positionals_and_defaults() {
+    let file = positional::<String>("FILE").help("Input file");
+    let jobs = short('j')
+        .argument::<usize>("JOBS")
+        .fallback(4)
+        .display_fallback();
+    let rest = any::<String, _, _>("REST", Some).many();
+    let parser = construct!(jobs, file, rest).to_options();
+
+    let spec = parser.describe();
+    let items = match spec.args {
+        Node::And(items) => items,
+        other => panic!("{:?}", other),
+    };
+    assert_eq!(
+        items[0].to_json(),
+        r#"{"kind":"suffix","text":"[default: 4]","item":{"kind":"optional","item":{"kind":"argument","short":"j","long":null,"env":[],"metavar":"JOBS","help":null,"deprecated":false,"negatable":false,"opt_value":false}}}"#
+    );
+    assert_eq!(
+        items[1].to_json(),
+        r#"{"kind":"positional","metavar":"FILE","help":"Input file"}"#
+    );
+    assert_eq!(
+        items[2].to_json(),
+        r#"{"kind":"many","item":{"kind":"optional","item":{"kind":"any","metavar":"REST","help":null}}}"#
+    );
+}
+
+#[test]
+fn all_env_variables() {
+    let token = long("token")
+        .env("APP_TOKEN")
+        .env("TOKEN")
+        .argument::<String>("TOKEN");
+    let spec = token.to_options().describe();
+    assert_eq!(
+        spec.args.to_json(),
+        r#"{"kind":"argument","short":null,"long":"token","env":["APP_TOKEN","TOKEN"],"metavar":"TOKEN","help":null,"deprecated":false,"negatable":false,"opt_value":false}"#
+    );
+}
+
+#[cfg(feature = "autocomplete")]
+#[test]
+fn completion_hints() {
+    #[derive(Debug, Clone, Copy)]
+    enum Mode {
+        Fast,
+        Slow,
+    }
+
+    impl ValueEnum for Mode {
+        fn variants() -> &'static [(&'static str, Option<&'static str>)] {
+            &[("fast", Some("Go fast")), ("slow", None)]
+        }
+    }
+
+    impl std::str::FromStr for Mode {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "fast" => Ok(Mode::Fast),
+                "slow" => Ok(Mode::Slow),
+                _ => Err(unknown_value(s, Self::variants())),
+            }
+        }
+    }
+
+    let mode = long("mode").argument::<Mode>("MODE").possible_values();
+    let file = positional::<String>("FILE").complete_shell(ShellComp::File { mask: Some("*.rs") });
+    let parser = construct!(mode, file).to_options();
+
+    let items = match parser.describe().args {
+        Node::And(items) => items,
+        other => panic!("{:?}", other),
+    };
+    assert_eq!(
+        items[0].to_json(),
+        concat!(
+            r#"{"kind":"complete","hint":"values","values":[{"name":"fast","help":"Go fast"},{"name":"slow","help":null}],"#,
+            r#""item":{"kind":"suffix","text":"[possible values: fast, slow]","item":{"kind":"argument","short":null,"long":"mode","env":[],"metavar":"MODE","help":null,"deprecated":false,"negatable":false,"opt_value":false}}}"#
+        )
+    );
+    assert_eq!(
+        items[1].to_json(),
+        r#"{"kind":"complete","hint":"file","mask":"*.rs","item":{"kind":"positional","metavar":"FILE","help":null}}"#
+    );
+}
@@ -242,3 +242,121 @@ fn strictly_positional_help() {
         .unwrap_stderr();
     assert_eq!(r, "`--help` is not expected in this context");
 }
+
+#[test]
+fn error_kind_conflict_and_only_once() {
+    let a = short('a').req_flag(());
+    let b = short('b').req_flag(());
+    let parser = construct!([a, b]).to_options();
+
+    let r = parser.run_inner(&["-ab"]).unwrap_err();
+    assert_eq!(
+        r.kind(),
+        Some(&ErrorKind::Conflict {
+            index: 0,
+            name: "-b".to_owned(),
+            winner_index: 0,
+            winner_name: "-a".to_owned(),
+        })
+    );
+
+    let r = parser.run_inner(&["-a", "-a"]).unwrap_err();
+    assert_eq!(
+        r.kind(),
+        Some(&ErrorKind::OnlyOnce {
+            index: 1,
+            name: "-a".to_owned(),
+            first_index: 0,
+        })
+    );
+}
+
+#[test]
+fn error_kind_values() {
+    let n = long("count")
+        .argument::<u32>("N")
+        .guard(|n| *n > 0, "must be positive");
+    let name = positional::<String>("NAME");
+    let parser = construct!(n, name).to_options();
+
+    let r = parser.run_inner(&["--count=x", "bob"]).unwrap_err();
+    assert_eq!(
+        r.kind(),
+        Some(&ErrorKind::ParseFailed {
+            index: Some(0),
+            value: Some("x".to_owned()),
+            message: "invalid digit found in string".to_owned(),
+        })
+    );
+
+    let r = parser.run_inner(&["bob", "--count", "0"]).unwrap_err();
+    assert_eq!(
+        r.kind(),
+        Some(&ErrorKind::GuardFailed {
+            index: Some(2),
+            value: Some("0".to_owned()),
+            message: "must be positive".to_owned(),
+        })
+    );
+
+    let r = parser.run_inner(&["bob", "--count"]).unwrap_err();
+    assert_eq!(
+        r.kind(),
+        Some(&ErrorKind::NoArgument {
+            index: 1,
+            name: "--count".to_owned(),
+            metavar: "N".to_owned(),
+        })
+    );
+
+    let r = parser.run_inner(&["--count", "1"]).unwrap_err();
+    assert_eq!(
+        r.kind(),
+        Some(&ErrorKind::Missing {
+            expected: vec!["NAME".to_owned()],
+            index: None,
+        })
+    );
+    assert_eq!(
+        r.to_json().unwrap(),
+        r#"{"kind":"missing","expected":["NAME"],"index":null,"message":"expected `NAME`, pass `--help` for usage information"}"#
+    );
+
+    let r = parser.run_inner(&["--cuont", "1", "bob"]).unwrap_err();
+    assert_eq!(
+        r.kind(),
+        Some(&ErrorKind::Suggestion {
+            index: 0,
+            name: "--cuont".to_owned(),
+            suggestion: "--count".to_owned(),
+        })
+    );
+
+    let r = parser
+        .run_inner(&["--count", "1", "bob", "extra"])
+        .unwrap_err();
+    assert_eq!(
+        r.kind(),
+        Some(&ErrorKind::Unconsumed {
+            index: 3,
+            name: "extra".to_owned(),
+        })
+    );
+
+    let r = parser.run_inner(&["--help"]).unwrap_err();
+    assert_eq!(r.kind(), None);
+    assert_eq!(r.to_json(), None);
+
+    // errors keep their shape and can be inspected or created by users
+    let r = parser.run_inner(&["--count", "1"]).unwrap_err();
+    match &r {
+        ParseFailure::Stderr(doc) => assert_eq!(
+            doc.to_string(),
+            "expected `NAME`, pass `--help` for usage information"
+        ),
+        _ => panic!("{:?}", r),
+    }
+    let r = ParseFailure::Stderr(Doc::from("custom").into());
+    assert_eq!(r.kind(), None);
+    assert_eq!(r.to_json(), None);
+}
@@ -0,0 +1,158 @@
+use bpaf::*;
+
+#[test]
+fn requires_reports_positions() {
+    let verbose = short('v').switch();
+    let cert = long("cert").argument::<String>("CERT").optional();
+    let key = long("key").argument::<String>("KEY").optional();
+    let tls = requires(cert, key);
+    let parser = construct!(verbose, tls).to_options();
+
+    let err = parser.run_inner(&["-v", "--cert", "a.pem"]).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        Some(&ErrorKind::Requires {
+            index: 1,
+            name: "--cert".to_owned(),
+            required: "--key".to_owned(),
+        })
+    );
+    assert_eq!(err.unwrap_stderr(), "`--cert` requires `--key`");
+
+    let r = parser.run_inner(&["--key", "a.key", "-v"]).unwrap();
+    assert_eq!(r, (true, (None, Some("a.key".to_owned()))));
+}
+
+#[test]
+fn conflict_reports_positions() {
+    let json = long("json").switch();
+    let yaml = long("yaml").switch();
+    let verbose = short('v').switch();
+    let format = conflicts((json, yaml));
+    let parser = construct!(format, verbose).to_options();
+
+    let err = parser.run_inner(&["--yaml", "-v", "--json"]).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        Some(&ErrorKind::Conflict {
+            index: 2,
+            name: "--json".to_owned(),
+            winner_index: 0,
+            winner_name: "--yaml".to_owned(),
+        })
+    );
+    assert_eq!(
+        err.unwrap_stderr(),
+        "`--json` cannot be used at the same time as `--yaml`"
+    );
+}
+
+#[test]
+fn exactly_one_of_rejects_both() {
+    let user = long("user").argument::<String>("USER").optional();
+    let all = long("all").switch();
+    let parser = exactly_one_of((user, all)).to_options();
+
+    let r = parser
+        .run_inner(&["--all", "--user", "bob"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "`--user` cannot be used at the same time as `--all`");
+
+    let r = parser.run_inner(&["--user", "bob"]).unwrap();
+    assert_eq!(r, (Some("bob".to_owned()), false));
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: (--user=USER | --all)
+
+Available options:
+        --user=USER
+        --all
+                     [exactly one of --user, --all]
+    -h, --help       Prints help information
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn missing_group_is_recoverable() {
+    let json = long("json").switch();
+    let yaml = long("yaml").switch();
+    let parser = exactly_one_of((json, yaml)).optional().to_options();
+
+    let r = parser.run_inner(&[]).unwrap();
+    assert_eq!(r, None);
+}
+
+#[test]
+fn env_and_fallback_do_not_count() {
+    let token = long("token")
+        .env("BPAF_GROUPS_TEST_TOKEN")
+        .argument::<String>("TOKEN")
+        .optional();
+    let password = long("password")
+        .argument::<String>("PASSWORD")
+        .fallback("hunter2".to_owned());
+    let parser = conflicts((token, password)).to_options();
+
+    std::env::set_var("BPAF_GROUPS_TEST_TOKEN", "secret");
+    let r = parser.run_inner(&["--password", "x"]).unwrap();
+    assert_eq!(r, (Some("secret".to_owned()), "x".to_owned()));
+    std::env::remove_var("BPAF_GROUPS_TEST_TOKEN");
+
+    let r = parser
+        .run_inner(&["--password", "x", "--token", "y"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "`--token` cannot be used at the same time as `--password`"
+    );
+}
+
+#[test]
+fn requires_localized() {
+    let cert = long("cert").switch();
+    let key = long("key").switch();
+    let parser = requires(cert, key).to_options().locale(locale::German);
+
+    let r = parser.run_inner(&["--cert"]).unwrap_err().unwrap_stderr();
+    assert_eq!(r, "`--cert` erfordert `--key`");
+}
+
+#[test]
+fn group_json_error() {
+    let cert = long("cert").switch();
+    let key = long("key").switch();
+    let parser = requires(cert, key).to_options();
+
+    let r = parser
+        .run_inner(&["--cert"])
+        .unwrap_err()
+        .to_json()
+        .unwrap();
+    assert_eq!(
+        r,
+        r#"{"kind":"requires","index":0,"name":"--cert","required":"--key","message":"`--cert` requires `--key`"}"#
+    );
+}
+
+#[test]
+fn at_least_one_of_usage() {
+    let user = long("user").argument::<String>("USER").many();
+    let all = long("all").switch();
+    let parser = at_least_one_of((user, all)).to_options();
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: (--user=USER... | --all)...
+
+Available options:
+        --user=USER
+        --all
+                     [at least one of --user, --all]
+    -h, --help       Prints help information
+";
+    assert_eq!(r, expected);
+}
@@ -0,0 +1,209 @@
+use bpaf::{locale::German, *};
+
+fn parser() -> OptionParser<(bool, Option<u32>, String)> {
+    let verbose = short('v').long("verbose").help("Mehr Ausgabe").switch();
+    let jobs = long("jobs").argument::<u32>("N").optional();
+    let file = positional::<String>("DATEI").help("Eingabedatei");
+    construct!(verbose, jobs, file)
+        .to_options()
+        .version("1.0")
+        .locale(German)
+}
+
+#[test]
+fn german_help() {
+    let r = parser().run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Aufruf: [-v] [--jobs=N] DATEI
+
+Verfügbare Positionsargumente:
+    DATEI          Eingabedatei
+
+Verfügbare Optionen:
+    -v, --verbose  Mehr Ausgabe
+        --jobs=N
+    -h, --help     Zeigt Hilfeinformationen an
+    -V, --version  Zeigt Versionsinformationen an
+";
+    assert_eq!(r, expected);
+
+    let r = parser()
+        .run_inner(&["--version"])
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "Version: 1.0\n");
+}
+
+#[test]
+fn german_errors() {
+    let r = parser()
+        .run_inner(&["--jobs", "x", "a"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "`x` konnte nicht verarbeitet werden: invalid digit found in string"
+    );
+
+    let r = parser().run_inner(&["-v"]).unwrap_err().unwrap_stderr();
+    assert_eq!(r, "`DATEI` erwartet, `--help` zeigt die Verwendung an");
+
+    let r = parser()
+        .run_inner(&["a", "--jobs"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "`--jobs` benötigt ein Argument `N`");
+
+    let r = parser()
+        .run_inner(&["a", "-v", "-v"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "Argument `-v` kann in diesem Kontext nur einmal verwendet werden"
+    );
+
+    let r = parser()
+        .run_inner(&["a", "--verbos"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "unbekannte Option: `--verbos`, meinten Sie `--verbose`?");
+
+    let r = parser()
+        .run_inner(&["a", "-verbose"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "unbekannte Option: `-verbose` (mit einem Bindestrich), meinten Sie `--verbose`?"
+    );
+}
+
+#[test]
+fn subcommand_uses_parent_locale() {
+    let fast = short('f').switch();
+    let cmd = fast.to_options().descr("Build things").command("build");
+    let parser = construct!(cmd).to_options().locale(German);
+
+    let r = parser
+        .run_inner(&["build", "--help"])
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "\
+Build things
+
+Aufruf: build [-f]
+
+Verfügbare Optionen:
+    -f
+    -h, --help  Zeigt Hilfeinformationen an
+";
+    assert_eq!(r, expected);
+
+    let r = parser
+        .run_inner(&["build", "-x"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "`-x` wird in diesem Kontext nicht erwartet");
+}
+
+#[test]
+fn partial_locale_falls_back_to_english() {
+    struct Short;
+    impl Locale for Short {
+        fn text(&self, text: locale::Text) -> Option<&str> {
+            match text {
+                locale::Text::Conflict => Some("{1} xor {0}"),
+                _ => None,
+            }
+        }
+    }
+
+    let a = short('a').req_flag(());
+    let b = short('b').req_flag(());
+    let parser = construct!([a, b]).to_options().locale(Short);
+
+    let r = parser.run_inner(&["-a", "-b"]).unwrap_err().unwrap_stderr();
+    assert_eq!(r, "`-a` xor `-b`");
+
+    let r = parser.run_inner(&["-c"]).unwrap_err().unwrap_stderr();
+    assert_eq!(
+        r,
+        "expected `-a` or `-b`, got `-c`. Pass `--help` for usage information"
+    );
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Mode {
+    Fast,
+    Slow,
+}
+
+impl ValueEnum for Mode {
+    fn variants() -> &'static [(&'static str, Option<&'static str>)] {
+        &[("fast", None), ("slow", None)]
+    }
+}
+
+impl std::str::FromStr for Mode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fast" => Ok(Mode::Fast),
+            "slow" => Ok(Mode::Slow),
+            _ => Err(unknown_value(s, Self::variants())),
+        }
+    }
+}
+
+#[test]
+fn german_checks_groups_and_values() {
+    let jobs = long("jobs").argument::<u32>("N").range(1..=64);
+    let name = long("name").argument::<String>("NAME").min_len(3);
+    let mode = long("mode").argument::<Mode>("MODE").possible_values();
+    let json = long("json").switch();
+    let yaml = long("yaml").switch();
+    let format = exactly_one_of((json, yaml));
+    let parser = construct!(jobs, name, mode, format)
+        .to_options()
+        .locale(German);
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Aufruf: --jobs=N --name=NAME --mode=MODE (--json | --yaml)
+
+Verfügbare Optionen:
+        --jobs=N     [1..=64]
+        --name=NAME
+        --mode=MODE
+                     [mögliche Werte: fast, slow]
+        --json
+        --yaml
+                     [genau eines von --json, --yaml]
+    -h, --help       Zeigt Hilfeinformationen an
+";
+    assert_eq!(r, expected);
+
+    let r = parser
+        .run_inner(&["--jobs", "100", "--name", "bob", "--mode", "fast", "--json"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "`100`: muss im Bereich 1..=64 liegen");
+
+    let r = parser
+        .run_inner(&["--jobs", "1", "--name", "bo", "--mode", "fast", "--json"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "`bo`: muss mindestens 3 Zeichen lang sein");
+
+    let r = parser
+        .run_inner(&["--jobs", "1", "--name", "bob", "--mode", "fsat", "--json"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "`fsat` konnte nicht verarbeitet werden: erwartet wird einer der Werte `fast`, `slow`, \
+         meinten Sie `fast`?"
+    );
+}
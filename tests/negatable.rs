@@ -0,0 +1,120 @@
+use bpaf::*;
+
+#[test]
+fn last_one_wins() {
+    let parser = long("color").negatable().to_options();
+
+    let r = parser.run_inner(&["--color"]).unwrap();
+    assert!(r);
+    let r = parser.run_inner(&["--no-color"]).unwrap();
+    assert!(!r);
+    let r = parser.run_inner(&["--color", "--no-color"]).unwrap();
+    assert!(!r);
+    let r = parser
+        .run_inner(&["--no-color", "--color", "--color"])
+        .unwrap();
+    assert!(r);
+    let r = parser.run_inner(&[]).unwrap();
+    assert!(!r);
+}
+
+#[test]
+fn aliases_and_short_names() {
+    let color = short('c')
+        .long("color")
+        .long("colour")
+        .help("Use colors")
+        .negatable();
+    let verbose = short('v').switch();
+    let parser = construct!(color, verbose).to_options();
+
+    let r = parser.run_inner(&["-cv", "--no-colour"]).unwrap();
+    assert_eq!(r, (false, true));
+
+    let r = parser.run_inner(&["--no-colour", "-c"]).unwrap();
+    assert_eq!(r, (true, false));
+
+    // there's no negative version of a short name
+    let r = parser.run_inner(&["--no-c"]).unwrap_err().unwrap_stderr();
+    assert_eq!(r, "`--no-c` is not expected in this context");
+}
+
+#[test]
+fn negatable_help() {
+    let color = short('c').long("color").help("Use colors").negatable();
+    let pager = long("pager").negatable();
+    let parser = construct!(color, pager).to_options();
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: [--[no-]color] [--[no-]pager]
+
+Available options:
+    -c, --[no-]color  Use colors
+        --[no-]pager
+    -h, --help        Prints help information
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn negatable_with_env() {
+    let parser = long("color")
+        .env("BPAF_NEGATABLE_TEST_COLOR")
+        .negatable()
+        .to_options();
+
+    std::env::set_var("BPAF_NEGATABLE_TEST_COLOR", "1");
+    let r = parser.run_inner(&[]).unwrap();
+    assert!(r);
+    // command line takes priority
+    let r = parser.run_inner(&["--no-color"]).unwrap();
+    assert!(!r);
+    std::env::remove_var("BPAF_NEGATABLE_TEST_COLOR");
+}
+
+#[cfg(feature = "autocomplete")]
+#[test]
+fn negatable_completion() {
+    let color = long("color").negatable();
+    let verbose = long("verbose").switch();
+    let parser = construct!(color, verbose).to_options();
+
+    let r = parser
+        .run_inner(Args::from(&["--no"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "--no-color");
+
+    let r = parser
+        .run_inner(Args::from(&["--c"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "--color");
+
+    let r = parser
+        .run_inner(Args::from(&[""]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(
+        r,
+        "--color\t--color\t\t\n--no-color\t--no-color\t\t\n--verbose\t--verbose\t\t\n\n"
+    );
+}
+
+#[cfg(feature = "autocomplete")]
+#[test]
+fn negatable_static_completion() {
+    let parser = long("color").help("Use colors").negatable().to_options();
+
+    let r = parser.render_completion_script("app", Shell::Bash);
+    assert!(
+        r.contains("            \"\") opts=\"--color --no-color --help -h\" ;;\n"),
+        "{}",
+        r
+    );
+
+    let r = parser.render_completion_script("app", Shell::Fish);
+    assert!(r.contains("-l color -d 'Use colors'\n"), "{}", r);
+    assert!(r.contains("-l no-color -d 'Use colors'\n"), "{}", r);
+}
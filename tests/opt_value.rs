@@ -0,0 +1,101 @@
+use bpaf::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum When {
+    Always,
+    Never,
+    Auto,
+}
+
+impl std::str::FromStr for When {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "always" => Ok(When::Always),
+            "never" => Ok(When::Never),
+            "auto" => Ok(When::Auto),
+            _ => Err(format!("expected always, never or auto, got {:?}", s)),
+        }
+    }
+}
+
+fn parser() -> OptionParser<(When, Vec<String>)> {
+    let color = short('c')
+        .long("color")
+        .help("When to use colors")
+        .argument_opt_value("WHEN", When::Always)
+        .fallback(When::Auto);
+    let files = positional::<String>("FILE").many();
+    construct!(color, files).to_options()
+}
+
+#[test]
+fn attached_values() {
+    let parser = parser();
+
+    let r = parser.run_inner(&["--color=never"]).unwrap();
+    assert_eq!(r, (When::Never, Vec::new()));
+
+    let r = parser.run_inner(&["-c=never"]).unwrap();
+    assert_eq!(r, (When::Never, Vec::new()));
+
+    let r = parser.run_inner(&["-cnever"]).unwrap();
+    assert_eq!(r, (When::Never, Vec::new()));
+}
+
+#[test]
+fn bare_name_uses_default() {
+    let parser = parser();
+
+    let r = parser.run_inner(&["--color"]).unwrap();
+    assert_eq!(r, (When::Always, Vec::new()));
+
+    // separate word is not a value
+    let r = parser.run_inner(&["--color", "never"]).unwrap();
+    assert_eq!(r, (When::Always, vec!["never".to_owned()]));
+
+    let r = parser.run_inner(&["-c", "a.txt"]).unwrap();
+    assert_eq!(r, (When::Always, vec!["a.txt".to_owned()]));
+
+    let r = parser.run_inner(&["a.txt"]).unwrap();
+    assert_eq!(r, (When::Auto, vec!["a.txt".to_owned()]));
+}
+
+#[test]
+fn invalid_attached_value() {
+    let r = parser()
+        .run_inner(&["--color=sometimes"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "couldn't parse `sometimes`: expected always, never or auto, got \"sometimes\""
+    );
+}
+
+#[test]
+fn required_opt_value() {
+    let parser = long("color")
+        .argument_opt_value::<String>("WHEN", "always".to_owned())
+        .to_options();
+
+    let r = parser.run_inner(&[]).unwrap_err().unwrap_stderr();
+    assert_eq!(
+        r,
+        "expected `--color[=WHEN]`, pass `--help` for usage information"
+    );
+}
+
+#[test]
+fn opt_value_help() {
+    let r = parser().run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: [-c[=WHEN]] [FILE]...
+
+Available options:
+    -c, --color[=WHEN]  When to use colors
+    -h, --help          Prints help information
+";
+    assert_eq!(r, expected);
+}
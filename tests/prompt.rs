@@ -0,0 +1,180 @@
+use bpaf::*;
+
+#[test]
+fn prompt_for_argument() {
+    let user = long("user").argument::<String>("USER").prompt("User name");
+    let port = long("port").argument::<u16>("PORT").prompt("Port");
+    let parser = construct!(user, port).to_options();
+
+    let args = Args::from(&["--port", "8080"]).set_prompt_input(&["alice"]);
+    let r = parser.run_inner(args).unwrap();
+    assert_eq!(r, ("alice".to_owned(), 8080));
+
+    // both are missing, answers come in the order parsers ask
+    let args = Args::from(&[]).set_prompt_input(&["alice", "22"]);
+    let r = parser.run_inner(args).unwrap();
+    assert_eq!(r, ("alice".to_owned(), 22));
+
+    // nothing is asked when everything is present
+    let args = Args::from(&["--user", "bob", "--port", "1"]).set_prompt_input(&["alice"]);
+    let r = parser.run_inner(args).unwrap();
+    assert_eq!(r, ("bob".to_owned(), 1));
+}
+
+#[test]
+fn prompt_without_input() {
+    let parser = long("user")
+        .argument::<String>("USER")
+        .prompt("User name")
+        .to_options();
+
+    // no script and stdin is not used for slices
+    let r = parser.run_inner(&[]).unwrap_err().unwrap_stderr();
+    assert_eq!(
+        r,
+        "expected `--user=USER`, pass `--help` for usage information"
+    );
+
+    // running out of answers or giving an empty answer is the same as not answering
+    let r = parser
+        .run_inner(Args::from(&[]).set_prompt_input(&[]))
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "expected `--user=USER`, pass `--help` for usage information"
+    );
+
+    let r = parser
+        .run_inner(Args::from(&[]).set_prompt_input(&[""]))
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "expected `--user=USER`, pass `--help` for usage information"
+    );
+}
+
+#[test]
+fn prompt_for_positional() {
+    let verbose = short('v').switch();
+    let host = positional::<String>("HOST").prompt("Host to connect to");
+    let parser = construct!(verbose, host).to_options();
+
+    let args = Args::from(&["-v"]).set_prompt_input(&["example.com"]);
+    let r = parser.run_inner(args).unwrap();
+    assert_eq!(r, (true, "example.com".to_owned()));
+}
+
+#[test]
+fn prompt_goes_through_parse_and_guard() {
+    let parser = long("port")
+        .argument::<u16>("PORT")
+        .prompt("Port")
+        .guard(|p| *p > 1024, "port must be above 1024")
+        .to_options();
+
+    let args = Args::from(&[]).set_prompt_input(&["eighty"]);
+    let r = parser.run_inner(args).unwrap_err().unwrap_stderr();
+    assert_eq!(r, "couldn't parse: invalid digit found in string");
+
+    let args = Args::from(&[]).set_prompt_input(&["80"]);
+    let r = parser.run_inner(args).unwrap_err().unwrap_stderr();
+    assert_eq!(r, "check failed: port must be above 1024");
+}
+
+#[test]
+fn optional_items_are_not_asked() {
+    let user = long("user")
+        .argument::<String>("USER")
+        .prompt("User name")
+        .optional();
+    let port = long("port")
+        .argument::<u16>("PORT")
+        .prompt("Port")
+        .fallback(80);
+    let parser = construct!(user, port).to_options();
+
+    let args = Args::from(&[]).set_prompt_input(&["alice", "22"]);
+    let r = parser.run_inner(args).unwrap();
+    assert_eq!(r, (None, 80));
+}
+
+#[test]
+fn prompt_for_env_only_argument() {
+    let parser = env("BPAF_SECRET_TOKEN_FOR_PROMPT_TEST")
+        .argument::<String>("TOKEN")
+        .prompt_hidden("Token")
+        .to_options();
+
+    let args = Args::from(&[]).set_prompt_input(&["hunter2"]);
+    let r = parser.run_inner(args).unwrap();
+    assert_eq!(r, "hunter2");
+}
+
+#[test]
+fn prompt_inside_command() {
+    let parser = long("name")
+        .argument::<String>("NAME")
+        .prompt("Name")
+        .to_options()
+        .command("add")
+        .to_options();
+
+    let args = Args::from(&["add"]).set_prompt_input(&["alice"]);
+    let r = parser.run_inner(args).unwrap();
+    assert_eq!(r, "alice");
+}
+
+#[test]
+fn prompt_is_asked_once() {
+    // both branches ask for the same argument, the answer is reused
+    let a = long("name")
+        .argument::<String>("NAME")
+        .prompt("Name")
+        .guard(|n| n.len() > 10, "too short");
+    let b = long("name").argument::<String>("NAME").prompt("Name");
+    let parser = construct!([a, b]).to_options();
+
+    let args = Args::from(&[]).set_prompt_input(&["alice", "bob"]);
+    let r = parser.run_inner(args).unwrap();
+    assert_eq!(r, "alice");
+}
+
+#[test]
+fn only_first_alternative_is_asked() {
+    let token = long("token").argument::<String>("TOKEN").prompt("Token");
+    let password = long("password")
+        .argument::<String>("PASSWORD")
+        .prompt("Password");
+    let user = long("user").argument::<String>("USER").prompt("User");
+    let secret = construct!([token, password]);
+    let parser = construct!(secret, user).to_options();
+
+    let args = Args::from(&[]).set_prompt_input(&["t", "u", "x"]);
+    let r = parser.run_inner(args).unwrap();
+    assert_eq!(r, ("t".to_owned(), "u".to_owned()));
+
+    // second alternative is asked about once the first one gets no answer
+    let token = long("token").argument::<String>("TOKEN");
+    let password = long("password")
+        .argument::<String>("PASSWORD")
+        .prompt("Password");
+    let parser = construct!([token, password]).to_options();
+    let args = Args::from(&[]).set_prompt_input(&["p"]);
+    let r = parser.run_inner(args).unwrap();
+    assert_eq!(r, "p");
+}
+
+#[test]
+fn prompt_provenance() {
+    let user = long("user").argument::<String>("USER").prompt("User name");
+    let host = positional::<String>("HOST").prompt("Host");
+    let parser = construct!(user, host).to_options();
+
+    let args = Args::from(&[]).set_prompt_input(&["alice", "example.com"]);
+    let (r, p) = parser.run_inner_with_provenance(args).unwrap();
+    assert_eq!(r, ("alice".to_owned(), "example.com".to_owned()));
+    assert_eq!(p.get("--user"), Some(ValueSource::Prompt));
+    assert_eq!(p.get("HOST"), Some(ValueSource::Prompt));
+}
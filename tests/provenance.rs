@@ -0,0 +1,108 @@
+use bpaf::*;
+
+#[test]
+fn provenance_cli_indices() {
+    let threads = short('t').long("threads").argument::<usize>("N");
+    let verbose = short('v').switch();
+    let quiet = short('q').switch();
+    let file = positional::<String>("FILE");
+    let parser = construct!(threads, verbose, quiet, file).to_options();
+
+    let (r, p) = parser
+        .run_inner_with_provenance(&["-vq", "--threads=3", "a.txt"])
+        .unwrap();
+    assert_eq!(r, (3, true, true, "a.txt".to_owned()));
+    assert_eq!(p.get("-v"), Some(ValueSource::Cli(0)));
+    assert_eq!(p.get("-q"), Some(ValueSource::Cli(0)));
+    assert_eq!(p.get("--threads"), Some(ValueSource::Cli(1)));
+    assert_eq!(p.get("FILE"), Some(ValueSource::Cli(2)));
+
+    let (_, p) = parser
+        .run_inner_with_provenance(&["a.txt", "-t", "3"])
+        .unwrap();
+    assert_eq!(p.get("--threads"), Some(ValueSource::Cli(2)));
+    assert_eq!(p.get("FILE"), Some(ValueSource::Cli(0)));
+    assert_eq!(p.get("-v"), None);
+}
+
+#[test]
+fn provenance_env_and_fallback() {
+    let name = "BPAF_PROVENANCE_THREADS";
+    let threads = long("threads").env(name).argument::<usize>("N").fallback(4);
+    let parser = threads.to_options();
+
+    let (r, p) = parser.run_inner_with_provenance(&[]).unwrap();
+    assert_eq!(r, 4);
+    assert_eq!(p.get("--threads"), Some(ValueSource::Fallback));
+
+    std::env::set_var(name, "6");
+    let (r, p) = parser.run_inner_with_provenance(&[]).unwrap();
+    assert_eq!(r, 6);
+    assert_eq!(p.get("--threads"), Some(ValueSource::Env(name)));
+    std::env::remove_var(name);
+}
+
+#[test]
+fn provenance_fallback_with_and_pure() {
+    let a = long("alpha")
+        .argument::<usize>("A")
+        .fallback_with(|| Ok::<_, String>(1));
+    let b = long("beta").argument::<usize>("B");
+    let two = pure(2);
+    let b = construct!([b, two]);
+    let parser = construct!(a, b).to_options();
+
+    let (r, p) = parser.run_inner_with_provenance(&[]).unwrap();
+    assert_eq!(r, (1, 2));
+    assert_eq!(p.get("--alpha"), Some(ValueSource::Fallback));
+    assert_eq!(p.get("--beta"), Some(ValueSource::Pure));
+
+    let (r, p) = parser.run_inner_with_provenance(&["--beta", "3"]).unwrap();
+    assert_eq!(r, (1, 3));
+    assert_eq!(p.get("--beta"), Some(ValueSource::Cli(1)));
+}
+
+#[test]
+fn provenance_config() {
+    let threads = long("threads").argument::<usize>("N");
+    let parser = threads
+        .to_options()
+        .config_source(|_: &str| Some("5".to_owned()));
+    let (r, p) = parser.run_inner_with_provenance(&[]).unwrap();
+    assert_eq!(r, 5);
+    assert_eq!(p.get("--threads"), Some(ValueSource::Config("threads")));
+}
+
+#[test]
+fn provenance_many_and_commands() {
+    let files = short('f').argument::<String>("F").many();
+    let cmd = files.to_options().command("run");
+    let parser = cmd.to_options();
+
+    let (r, p) = parser
+        .run_inner_with_provenance(&["run", "-f", "a", "-f", "b"])
+        .unwrap();
+    assert_eq!(r, ["a", "b"]);
+    let items = p.iter().collect::<Vec<_>>();
+    assert_eq!(
+        items,
+        [
+            ("run", ValueSource::Cli(0)),
+            ("-f", ValueSource::Cli(2)),
+            ("-f", ValueSource::Cli(4)),
+        ]
+    );
+}
+
+#[test]
+fn provenance_discards_failed_branches() {
+    let a = short('a').switch();
+    let b = short('b').req_flag(());
+    let ab = construct!(a, b).map(|_| 1);
+    let c = short('a').req_flag(2);
+    let parser = construct!([ab, c]).to_options();
+
+    let (r, p) = parser.run_inner_with_provenance(&["-a"]).unwrap();
+    assert_eq!(r, 2);
+    assert_eq!(p.iter().count(), 1);
+}
@@ -0,0 +1,144 @@
+use bpaf::*;
+use std::path::PathBuf;
+
+/// Scratch directory unique to a test
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("bpaf-rsp-{}-{}", std::process::id(), name));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn parser() -> OptionParser<Res> {
+    let name = long("name").argument::<String>("NAME").many();
+    let verbose = short('v').switch();
+    let files = positional::<String>("FILE").many();
+    construct!(name, verbose, files)
+        .to_options()
+        .response_files(true)
+}
+
+type Res = (Vec<String>, bool, Vec<String>);
+
+fn run(parser: OptionParser<Res>, args: &[&str]) -> Result<Res, ParseFailure> {
+    parser.run_inner(args)
+}
+
+fn at(path: &std::path::Path) -> String {
+    format!("@{}", path.display())
+}
+
+#[test]
+fn response_file_expands_in_place() {
+    let dir = scratch("expand");
+    let file = dir.join("args.rsp");
+    std::fs::write(
+        &file,
+        "# names\n--name 'Bob Smith' --name=\"Alice \\\"A\\\"\"\n\n-v a\\ b\n",
+    )
+    .unwrap();
+
+    let r = run(parser(), &["x", at(&file).as_str(), "y"]).unwrap();
+    assert_eq!(
+        r,
+        (
+            vec!["Bob Smith".to_owned(), "Alice \"A\"".to_owned()],
+            true,
+            vec!["x".to_owned(), "a b".to_owned(), "y".to_owned()]
+        )
+    );
+
+    // not after --
+    let r = run(parser(), &["--", at(&file).as_str()]).unwrap();
+    assert_eq!(r.2, vec![at(&file)]);
+
+    // disabled unless requested
+    let r = run(parser().response_files(false), &[at(&file).as_str()]).unwrap();
+    assert_eq!(r.2, vec![at(&file)]);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn response_file_nested() {
+    let dir = scratch("nested");
+    std::fs::create_dir_all(dir.join("sub")).unwrap();
+    std::fs::write(dir.join("top.rsp"), "a @sub/inner.rsp d").unwrap();
+    std::fs::write(dir.join("sub").join("inner.rsp"), "b\nc").unwrap();
+
+    let r = run(parser(), &[at(&dir.join("top.rsp")).as_str()]).unwrap();
+    assert_eq!(r.2, ["a", "b", "c", "d"]);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn response_file_double_dash_inside() {
+    let dir = scratch("dashes");
+    let other = dir.join("other.rsp");
+    std::fs::write(&other, "b").unwrap();
+    std::fs::write(dir.join("top.rsp"), format!("a -- {}", at(&other))).unwrap();
+
+    let top = at(&dir.join("top.rsp"));
+    let r = run(parser(), &[top.as_str(), at(&other).as_str()]).unwrap();
+    assert_eq!(r.2, vec!["a".to_owned(), at(&other), at(&other)]);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn response_file_errors() {
+    let dir = scratch("errors");
+    let cycle = dir.join("cycle.rsp");
+    std::fs::write(&cycle, format!("a {}", at(&cycle))).unwrap();
+    let r = run(parser(), &[at(&cycle).as_str()])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        format!("response file `{}`: file includes itself", cycle.display())
+    );
+
+    let quote = dir.join("quote.rsp");
+    std::fs::write(&quote, "a\nb 'c\nd").unwrap();
+    let r = run(parser(), &[at(&quote).as_str()]).unwrap_err();
+    assert_eq!(
+        r.kind(),
+        Some(&ErrorKind::ResponseFile {
+            path: quote.display().to_string(),
+            line: Some(2),
+        })
+    );
+    assert_eq!(
+        r.unwrap_stderr(),
+        format!(
+            "response file `{}`, line 2: unterminated quote",
+            quote.display()
+        )
+    );
+
+    let missing = dir.join("missing.rsp");
+    let r = run(parser(), &[at(&missing).as_str()]).unwrap_err();
+    assert_eq!(
+        r.kind(),
+        Some(&ErrorKind::ResponseFile {
+            path: missing.display().to_string(),
+            line: None,
+        })
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn response_file_not_expanded_in_completion() {
+    let r = parser()
+        .run_inner(Args::from(&["@missing.rsp", "--na"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "--name");
+
+    let r = run(parser(), &["--bpaf-complete-rev=0", "@missing.rsp", "--na"])
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "--name");
+}
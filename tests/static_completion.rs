@@ -0,0 +1,224 @@
+use bpaf::*;
+
+fn parser() -> OptionParser<()> {
+    let verbose = short('v')
+        .long("verbose")
+        .help("Produce more output")
+        .switch();
+    let output = short('o')
+        .long("output")
+        .argument::<String>("DIR")
+        .complete_shell(ShellComp::Dir { mask: None });
+    let name = long("name")
+        .argument::<String>("NAME")
+        .complete(|s: &String| vec![(s.clone(), None)]);
+    let file = positional::<String>("FILE").complete_shell(ShellComp::File { mask: Some("*.rs") });
+    let top = construct!(verbose, output, name, file).map(|_| ());
+
+    let release = short('r').long("release").switch();
+    let build = construct!(release)
+        .to_options()
+        .descr("Build the project")
+        .command("build")
+        .short('b')
+        .map(|_| ());
+    construct!([build, top]).to_options()
+}
+
+#[test]
+fn static_bash() {
+    let r = parser().render_completion_script("app", Shell::Bash);
+    let expected = r#"_app() {
+    local cur prev words cword split
+    _init_completion -s || return
+
+    local state="" npos=0 i=1
+    while [ "$i" -lt "$cword" ]; do
+        case "$state:${words[i]}" in
+            ":build"|":b") state="build"; npos=0 ;;
+            ":--output"|":-o") i=$((i + 1)) ;;
+            ":--name") i=$((i + 1)) ;;
+            *:-*) ;;
+            *) npos=$((npos + 1)) ;;
+        esac
+        i=$((i + 1))
+    done
+
+    case "$state:$prev" in
+        ":--output"|":-o") _filedir -d; return ;;
+        ":--name") source <( "${words[0]}" --bpaf-complete-rev=8 "${words[@]:1}" ); return ;;
+    esac
+
+    local opts=""
+    if [[ "$cur" == -* ]]; then
+        case "$state" in
+            "") opts="--verbose -v --output -o --name --help -h" ;;
+            "build") opts="--release -r --help -h" ;;
+        esac
+    else
+        case "$state" in
+            "") opts="build" ;;
+        esac
+        case "$state:$npos" in
+            ":0") _filedir 'rs' ;;
+        esac
+    fi
+    COMPREPLY+=( $(compgen -W "$opts" -- "$cur") )
+}
+complete -F _app app
+"#;
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn static_zsh() {
+    let r = parser().render_completion_script("app", Shell::Zsh);
+    let expected = r#"#compdef app
+
+_app() {
+    local curcontext="$curcontext" state line
+    typeset -A opt_args
+    local -a bpaf_words
+    bpaf_words=("${words[@]}")
+    _arguments -s -S -C \
+        '(--verbose -v)--verbose[Produce more output]' \
+        '(--verbose -v)-v[Produce more output]' \
+        '(--output -o)--output=:DIR:_files -/' \
+        '(--output -o)-o+:DIR:_files -/' \
+        '(--name)--name=:NAME:{source <( "${bpaf_words[1]}" --bpaf-complete-rev=7 "${bpaf_words[@]:1}" )}' \
+        '(--help -h)--help[Prints help information]' \
+        '(--help -h)-h[Prints help information]' \
+        ': :->command' \
+        '*:: :->args'
+    case $state in
+        command)
+            local -a commands
+            commands=('build:Build the project')
+            _describe -t commands 'command' commands
+            _files -g '*.rs'
+            ;;
+        args)
+            curcontext="${curcontext%:*:*}:app-$line[1]:"
+            case $line[1] in
+                build|b) _app__build ;;
+            esac
+            ;;
+    esac
+}
+
+_app__build() {
+    local curcontext="$curcontext" state line
+    typeset -A opt_args
+    _arguments -s -S -C \
+        '(--release -r)--release' \
+        '(--release -r)-r' \
+        '(--help -h)--help[Prints help information]' \
+        '(--help -h)-h[Prints help information]'
+}
+
+if [ "$funcstack[1]" = "_app" ]; then
+    _app "$@"
+else
+    compdef _app app
+fi
+"#;
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn static_zsh_exclusions_and_escapes() {
+    let json = long("json").help("Output [as] JSON").switch();
+    let yaml = long("yaml").switch();
+    let format = construct!([json, yaml]);
+    let verbose = short('v').help("Don't be quiet").req_flag(()).many();
+    let mode = long("mode")
+        .argument::<String>("MODE")
+        .complete_shell(ShellComp::File {
+            mask: Some("*.toml"),
+        });
+    let files = positional::<String>("FILE")
+        .complete_shell(ShellComp::File { mask: None })
+        .many();
+    let parser = construct!(format, verbose, mode, files).to_options();
+
+    let r = parser.render_completion_script("app", Shell::Zsh);
+    let expected = r#"    _arguments -s -S -C \
+        '(--json --yaml)--json[Output \[as\] JSON]' \
+        '(--yaml --json)--yaml' \
+        '*-v[Don'\''t be quiet]' \
+        '(--mode)--mode=:MODE:_files -g "*.toml"' \
+        '(--help -h)--help[Prints help information]' \
+        '(--help -h)-h[Prints help information]' \
+        '*:FILE:_files'
+}
+"#;
+    assert!(r.contains(expected), "{}", r);
+}
+
+#[test]
+fn static_fish() {
+    let r = parser().render_completion_script("app", Shell::Fish);
+    let expected = "\
+function __app_dynamic
+    set -l tokens (commandline -opc) (commandline -ct)
+    $tokens[1] --bpaf-complete-rev=1 $tokens[2..-1]
+end
+
+complete -c app -f
+complete -c app -n 'not __fish_seen_subcommand_from build b' -a build -d 'Build the project'
+complete -c app -n 'not __fish_seen_subcommand_from build b' -s v -l verbose -d 'Produce more output'
+complete -c app -n 'not __fish_seen_subcommand_from build b' -s o -l output -r -a '(__fish_complete_directories)'
+complete -c app -n 'not __fish_seen_subcommand_from build b' -l name -r -a '(__app_dynamic)'
+complete -c app -n 'not __fish_seen_subcommand_from build b' -s h -l help -d 'Prints help information'
+complete -c app -n 'not __fish_seen_subcommand_from build b' -a '(__fish_complete_suffix .rs)'
+complete -c app -n '__fish_seen_subcommand_from build b' -s r -l release
+complete -c app -n '__fish_seen_subcommand_from build b' -s h -l help -d 'Prints help information'
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn static_fish_nested() {
+    let file = short('f')
+        .argument::<String>("FILE")
+        .complete_shell(ShellComp::File { mask: None });
+    let add = construct!(file)
+        .to_options()
+        .descr("Add a remote")
+        .command("add");
+    let remote = construct!([add]).to_options().command("remote");
+    let parser = construct!([remote]).to_options();
+
+    let r = parser.render_completion_script("app", Shell::Fish);
+    let expected = "\
+complete -c app -f
+complete -c app -n 'not __fish_seen_subcommand_from remote' -a remote
+complete -c app -n 'not __fish_seen_subcommand_from remote' -s h -l help -d 'Prints help information'
+complete -c app -n '__fish_seen_subcommand_from remote; and not __fish_seen_subcommand_from add' -a add -d 'Add a remote'
+complete -c app -n '__fish_seen_subcommand_from remote; and not __fish_seen_subcommand_from add' -s h -l help -d 'Prints help information'
+complete -c app -n '__fish_seen_subcommand_from remote; and __fish_seen_subcommand_from add' -s f -r -F
+complete -c app -n '__fish_seen_subcommand_from remote; and __fish_seen_subcommand_from add' -s h -l help -d 'Prints help information'
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn static_quotes_and_many() {
+    let files = positional::<String>("FILE")
+        .complete_shell(ShellComp::File { mask: None })
+        .many();
+    let dry = long("dry-run").help("Don't do anything").switch();
+    let parser = construct!(dry, files).to_options().version("1.0");
+
+    let r = parser.render_completion_script("my-app", Shell::Bash);
+    assert!(r.contains("            \"\") opts=\"--dry-run --help -h --version -V\" ;;\n"));
+    assert!(r.contains("            \":\"*) _filedir ;;\n"));
+    assert!(r.ends_with("complete -F _my_app my-app\n"));
+
+    let r = parser.render_completion_script("my-app", Shell::Zsh);
+    assert!(r.contains("'(--dry-run)--dry-run[Don'\\''t do anything]'"));
+
+    let r = parser.render_completion_script("my-app", Shell::Fish);
+    assert!(r.contains("-l dry-run -d 'Don'\\''t do anything'\n"));
+    assert!(r.contains("complete -c my-app -F\n"));
+}
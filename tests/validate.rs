@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use bpaf::*;
+
+#[test]
+fn range_checks_and_help() {
+    let jobs = long("jobs")
+        .help("Number of jobs")
+        .argument::<u32>("N")
+        .range(1..=64);
+    let level = short('l').argument::<i8>("L").range(..3);
+    let ratio = long("ratio").argument::<f64>("R").range(0.5..).optional();
+    let parser = construct!(jobs, level, ratio).to_options();
+
+    let r = parser.run_inner(&["--jobs", "64", "-l=-5"]).unwrap();
+    assert_eq!(r, (64, -5, None));
+
+    let err = parser.run_inner(&["--jobs", "0", "-l", "1"]).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        Some(&ErrorKind::GuardFailed {
+            index: Some(1),
+            value: Some("0".to_owned()),
+            message: "must be in range 1..=64".to_owned(),
+        })
+    );
+    assert_eq!(err.unwrap_stderr(), "`0`: must be in range 1..=64");
+
+    let r = parser
+        .run_inner(&["--jobs", "1", "-l", "3"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "`3`: must be in range ..3");
+
+    let r = parser
+        .run_inner(&["--jobs", "1", "-l", "2", "--ratio", "0.1"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "`0.1`: must be in range 0.5..");
+
+    // parse failures are reported before range checks
+    let r = parser
+        .run_inner(&["--jobs", "many", "-l", "1"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "couldn't parse `many`: invalid digit found in string");
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: --jobs=N -l=L [--ratio=R]
+
+Available options:
+        --jobs=N   Number of jobs [1..=64]
+    -l=L           [..3]
+        --ratio=R  [0.5..]
+    -h, --help     Prints help information
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn positional_checks() {
+    let name = positional::<String>("NAME")
+        .help("User name")
+        .min_len(3)
+        .matches(|s| s.chars().all(char::is_alphanumeric));
+    let port = positional::<u16>("PORT").range(1024..);
+    let parser = construct!(name, port).to_options();
+
+    let r = parser.run_inner(&["bob", "8080"]).unwrap();
+    assert_eq!(r, ("bob".to_owned(), 8080));
+
+    // length is counted in characters
+    let r = parser.run_inner(&["ёжик", "8080"]).unwrap();
+    assert_eq!(r, ("ёжик".to_owned(), 8080));
+
+    let r = parser
+        .run_inner(&["bo", "8080"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "`bo`: must be at least 3 characters long");
+
+    let r = parser
+        .run_inner(&["bob!", "8080"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "`bob!`: not a valid NAME");
+
+    let r = parser
+        .run_inner(&["bob", "80"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "`80`: must be in range 1024..");
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: NAME PORT
+
+Available positional items:
+    NAME        User name
+    PORT        [1024..]
+
+Available options:
+    -h, --help  Prints help information
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn checks_apply_to_env_and_config_values() {
+    let parser = long("threads")
+        .env("BPAF_VALIDATE_TEST_THREADS")
+        .argument::<u32>("N")
+        .range(1..=8)
+        .to_options();
+
+    std::env::set_var("BPAF_VALIDATE_TEST_THREADS", "16");
+    let r = parser.run_inner(&[]).unwrap_err().unwrap_stderr();
+    std::env::remove_var("BPAF_VALIDATE_TEST_THREADS");
+    assert_eq!(r, "check failed: must be in range 1..=8");
+
+    let mut config = HashMap::new();
+    config.insert("threads".to_owned(), "16".to_owned());
+    let parser = parser.config_source(config);
+    let err = parser.run_inner(&[]).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        Some(&ErrorKind::ConfigFailed {
+            key: "threads".to_owned(),
+            message: "must be in range 1..=8".to_owned(),
+        })
+    );
+}
+
+#[test]
+fn guard_with_dynamic_message() {
+    let parser = long("width")
+        .argument::<usize>("W")
+        .guard_with(|w| {
+            if w % 2 == 0 {
+                Ok(())
+            } else {
+                Err(format!("width must be even, {} is odd", w))
+            }
+        })
+        .many()
+        .to_options();
+
+    let r = parser.run_inner(&["--width", "2", "--width", "4"]).unwrap();
+    assert_eq!(r, [2, 4]);
+
+    let r = parser
+        .run_inner(&["--width", "2", "--width", "5"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "`5`: width must be even, 5 is odd");
+}
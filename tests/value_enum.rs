@@ -0,0 +1,126 @@
+use bpaf::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Bpaf)]
+#[bpaf(value_enum)]
+enum Mode {
+    /// Go fast
+    Fast,
+    /// Go slow
+    Slow,
+    #[bpaf(long("snail"))]
+    SlowerStill,
+    #[bpaf(skip)]
+    Broken,
+}
+
+#[derive(Debug, Clone, Bpaf)]
+#[bpaf(options)]
+struct Options {
+    /// Processing mode
+    #[bpaf(argument("MODE"), possible_values)]
+    mode: Mode,
+}
+
+#[test]
+fn value_enum_from_str_and_display() {
+    assert_eq!("fast".parse::<Mode>(), Ok(Mode::Fast));
+    assert_eq!("snail".parse::<Mode>(), Ok(Mode::SlowerStill));
+    assert_eq!(
+        "broken".parse::<Mode>(),
+        Err("expected one of `fast`, `slow`, `snail`".to_owned())
+    );
+    assert_eq!(Mode::SlowerStill.to_string(), "snail");
+    assert_eq!(Mode::Broken.to_string(), "broken");
+    assert_eq!(
+        Mode::variants(),
+        &[
+            ("fast", Some("Go fast")),
+            ("slow", Some("Go slow")),
+            ("snail", None)
+        ]
+    );
+}
+
+#[test]
+fn value_enum_parse() {
+    let r = options().run_inner(&["--mode", "slow"]).unwrap();
+    assert_eq!(r.mode, Mode::Slow);
+
+    let r = options()
+        .run_inner(&["--mode", "slwo"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "couldn't parse `slwo`: expected one of `fast`, `slow`, `snail`, did you mean `slow`?"
+    );
+}
+
+#[test]
+fn value_enum_help() {
+    let r = options()
+        .run_inner(&["--help"])
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "\
+Usage: --mode=MODE
+
+Available options:
+        --mode=MODE  Processing mode
+                     [possible values: fast, slow, snail]
+    -h, --help       Prints help information
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn value_enum_complete() {
+    let r = options()
+        .run_inner(Args::from(&["--mode", ""]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(
+        r,
+        "\
+\tMODE\t\tProcessing mode
+fast\tfast\t\tGo fast
+slow\tslow\t\tGo slow
+snail\tsnail\t\t\n\n"
+    );
+
+    let r = options()
+        .run_inner(Args::from(&["--mode", "s"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(
+        r,
+        "\
+\tMODE\t\tProcessing mode
+slow\tslow\t\tGo slow
+snail\tsnail\t\t\n\n"
+    );
+
+    let r = options()
+        .run_inner(Args::from(&["--mode", "f"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "fast");
+}
+
+#[test]
+fn value_enum_static_completion() {
+    let r = options().render_completion_script("app", Shell::Bash);
+    assert!(r.contains(
+        r#"        ":--mode") COMPREPLY+=( $(compgen -W 'fast slow snail' -- "$cur") ); return ;;"#
+    ));
+
+    let r = options().render_completion_script("app", Shell::Zsh);
+    assert!(r.contains(
+        r#"'(--mode)--mode=[Processing mode]:MODE:((fast\:Go\ fast slow\:Go\ slow snail))'"#
+    ));
+
+    let r = options().render_completion_script("app", Shell::Fish);
+    assert!(r.contains(
+        r#"-l mode -r -a '(printf "%s\t%s\n" "fast" "Go fast" "slow" "Go slow" "snail" "")' -d 'Processing mode'"#
+    ));
+}